@@ -0,0 +1,219 @@
+//! ARUI'nin native yardımcılarını tek process ve tek tokio runtime'ı
+//! üzerinde alt sistem olarak çalıştırır. Hangi alt sistemlerin binary'ye
+//! gireceği cargo feature'larıyla seçilir.
+//!
+//! Her alt sistemin mesajı stdout'a alt sistemin adıyla sarılarak yazılır:
+//! `{"protocol_version": 3, "subsystem": "media_info", "message": {...}}`.
+//! stdin'e aynı şekilde yazılan satırların `message` kısmı ilgili alt
+//! sisteme iletilir. Tüm alt sistemler JSON-RPC istekleri kabul eder.
+
+use arui_protocol::{AruiError, Envelope, ErrorCode, HostMessage, Output, Subsystem};
+use clap::Parser;
+use env_logger::Env;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::sync::mpsc;
+use tokio::task::{Id, JoinSet};
+
+// Ctrl+C'den sonra taskbar manager'ın taskbar'ı geri getirmesi için beklenen süre
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Parser)]
+#[command(name = "arui-native")]
+#[command(about = "Runs ARUI's native helpers as subsystems of one process")]
+struct Cli {
+    /// Subsystems to leave out (taskbar_manager, taskbar_items, media_info)
+    #[arg(long = "disable", value_delimiter = ',')]
+    disabled: Vec<Subsystem>,
+    /// Start ARUI's pinned list empty instead of copying the Windows taskbar pins
+    /// when it is first created
+    #[cfg(feature = "taskbar-items")]
+    #[arg(long = "no-seed-from-windows", action = clap::ArgAction::SetFalse)]
+    seed_from_windows: bool,
+    #[cfg(feature = "taskbar-items")]
+    #[command(flatten)]
+    taskbar_items: win_taskbar_item_list::MonitorOptions,
+}
+
+impl Cli {
+    fn enabled(&self, subsystem: Subsystem) -> bool {
+        !self.disabled.contains(&subsystem)
+    }
+}
+
+/// Çalışan alt sistemler ve stdin'den gelen satırları alacakları kanallar
+#[derive(Default)]
+struct Host {
+    tasks: JoinSet<arui_protocol::Result<()>>,
+    names: HashMap<Id, Subsystem>,
+    inputs: HashMap<Subsystem, mpsc::UnboundedSender<String>>,
+    // Hatayla biten son alt sistemin çıkış kodu
+    exit_code: i32,
+}
+
+impl Host {
+    fn spawn<F>(&mut self, subsystem: Subsystem, task: F)
+    where
+        F: Future<Output = arui_protocol::Result<()>> + Send + 'static,
+    {
+        let handle = self.tasks.spawn(task);
+        self.names.insert(handle.id(), subsystem);
+    }
+
+    /// Alt sisteme stdin satırlarını iletecek kanalı açar
+    fn input(&mut self, subsystem: Subsystem) -> mpsc::UnboundedReceiver<String> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.inputs.insert(subsystem, tx);
+        rx
+    }
+
+    fn is_running(&self, subsystem: Subsystem) -> bool {
+        self.names.values().any(|name| *name == subsystem)
+    }
+
+    /// Bir sonraki biten alt sistemi bekler; hatayla bittiyse hatayı
+    /// alt sistemin adıyla yazar. Çalışan alt sistem kalmadıysa `None`.
+    async fn join_next(&mut self) -> Option<Subsystem> {
+        let (id, result) = match self.tasks.join_next_with_id().await? {
+            Ok((id, result)) => (id, result),
+            Err(error) => {
+                let message = format!("Subsystem stopped unexpectedly: {}", error);
+                (
+                    error.id(),
+                    Err(AruiError::new(ErrorCode::Internal, message)),
+                )
+            }
+        };
+
+        let subsystem = self.names.remove(&id).unwrap_or(Subsystem::Host);
+        if let Err(error) = result {
+            let envelope = Envelope::<()>::error(error);
+            Output::tagged(subsystem).emit(&envelope);
+            self.exit_code = envelope.exit_code();
+        }
+        Some(subsystem)
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    env_logger::Builder::from_env(Env::default().default_filter_or("info")).init();
+
+    let cli = Cli::parse();
+    let out = Output::tagged(Subsystem::Host);
+    let shutdown_flag = Arc::new(AtomicBool::new(false));
+    let mut host = Host::default();
+
+    #[cfg(feature = "taskbar-manager")]
+    if cli.enabled(Subsystem::TaskbarManager) {
+        let input = host.input(Subsystem::TaskbarManager);
+        let out = Output::tagged(Subsystem::TaskbarManager);
+        let task = win_taskbar_manager::run(out, input, shutdown_flag.clone());
+        host.spawn(Subsystem::TaskbarManager, task);
+    }
+
+    #[cfg(feature = "taskbar-items")]
+    if cli.enabled(Subsystem::TaskbarItems) {
+        let input = host.input(Subsystem::TaskbarItems);
+        let out = Output::tagged(Subsystem::TaskbarItems);
+        // Host'ta item list her zaman serve modunda çalışır
+        let serve = Some(cli.seed_from_windows);
+        let options = cli.taskbar_items.clone();
+        let task = win_taskbar_item_list::run_monitor(options, serve, out, input);
+        host.spawn(Subsystem::TaskbarItems, task);
+    }
+
+    #[cfg(feature = "media-info")]
+    if cli.enabled(Subsystem::MediaInfo) {
+        let input = host.input(Subsystem::MediaInfo);
+        let out = Output::tagged(Subsystem::MediaInfo);
+        host.spawn(Subsystem::MediaInfo, async move {
+            win_media_info::run_monitor(out, input).await;
+            Ok(())
+        });
+    }
+
+    if host.names.is_empty() {
+        let error = AruiError::new(ErrorCode::InvalidArgument, "No subsystem is enabled");
+        let envelope = Envelope::<()>::error(error);
+        out.emit(&envelope);
+        std::process::exit(envelope.exit_code());
+    }
+
+    tokio::spawn(route_stdin(std::mem::take(&mut host.inputs), out));
+
+    loop {
+        tokio::select! {
+            joined = host.join_next() => {
+                // Tüm alt sistemler bittiyse host da biter
+                if joined.is_none() {
+                    std::process::exit(host.exit_code);
+                }
+            }
+            _ = tokio::signal::ctrl_c() => break,
+        }
+    }
+
+    // Taskbar manager'ın taskbar'ı geri getirmesini bekle; diğer alt
+    // sistemlerin kapanırken yapacağı bir şey yok
+    shutdown_flag.store(true, Ordering::SeqCst);
+    if host.is_running(Subsystem::TaskbarManager) {
+        let restored = async {
+            while let Some(subsystem) = host.join_next().await {
+                if subsystem == Subsystem::TaskbarManager {
+                    break;
+                }
+            }
+        };
+        let _ = tokio::time::timeout(SHUTDOWN_TIMEOUT, restored).await;
+    }
+
+    // stdin okuması bloklayıcı bir thread'de sürüyor; runtime'ın onu
+    // beklemesine izin vermeden çık
+    std::process::exit(host.exit_code);
+}
+
+/// stdin'deki `HostMessage` satırlarının `message` kısmını ilgili alt
+/// sisteme iletir. Alt sistem mesajı, tek başına çalışırken stdin'den
+/// okuyacağı satır olarak alır.
+async fn route_stdin(inputs: HashMap<Subsystem, mpsc::UnboundedSender<String>>, out: Output) {
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+
+    while let Ok(Some(line)) = lines.next_line().await {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let message = match serde_json::from_str::<HostMessage<serde_json::Value>>(&line) {
+            Ok(message) => message,
+            Err(e) => {
+                let message = format!("Invalid host message: {}", e);
+                let error = AruiError::new(ErrorCode::InvalidArgument, message);
+                out.emit(&Envelope::<()>::error(error));
+                continue;
+            }
+        };
+
+        let line = match message.message {
+            serde_json::Value::String(line) => line,
+            other => other.to_string(),
+        };
+        let delivered = inputs
+            .get(&message.subsystem)
+            .is_some_and(|input| input.send(line).is_ok());
+        if !delivered {
+            let text = format!(
+                "Subsystem is not running or takes no input: {}",
+                message.subsystem
+            );
+            out.emit(&Envelope::<()>::error(AruiError::new(
+                ErrorCode::NotFound,
+                text,
+            )));
+        }
+    }
+}
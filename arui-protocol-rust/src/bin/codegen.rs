@@ -0,0 +1,175 @@
+//! Protokol tiplerinden JSON Schema ve TypeScript tanımlarını üretir.
+//!
+//! `cargo run --features schema,typescript --bin arui-protocol-codegen` dosyaları
+//! yeniden yazar; `-- --check` ise sadece karşılaştırır ve üretilen dosyalar
+//! Rust tiplerinden farklıysa sıfırdan farklı kodla çıkar.
+
+use arui_protocol::{
+    AruiError, Envelope, HostMessage, MediaEvent, TaskbarEvent, TaskbarGroupUpdate, TaskbarUpdate,
+    PROTOCOL_VERSION,
+};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use ts_rs::TS;
+
+// Svelte store'larının import ettiği klasör (crate köküne göre)
+const TYPESCRIPT_DIR: &str = "../frontend/src/lib/base/api/list/Native/generated";
+const SCHEMA_DIR: &str = "schema";
+
+type Files = BTreeMap<String, String>;
+
+fn main() {
+    let check = std::env::args().any(|arg| arg == "--check");
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+
+    let outputs = [
+        (root.join(SCHEMA_DIR), "json", schema_files()),
+        (root.join(TYPESCRIPT_DIR), "ts", typescript_files()),
+    ];
+
+    let mut stale = Vec::new();
+    for (dir, extension, files) in &outputs {
+        if check {
+            stale.extend(compare(dir, extension, files));
+        } else if let Err(e) = write(dir, extension, files) {
+            eprintln!("Could not write {}: {}", dir.display(), e);
+            std::process::exit(1);
+        }
+    }
+
+    if !stale.is_empty() {
+        for path in &stale {
+            eprintln!("out of date: {}", path.display());
+        }
+        eprintln!("Run `cargo run --features schema,typescript --bin arui-protocol-codegen`");
+        std::process::exit(1);
+    }
+}
+
+/// Her üst seviye mesaj için ayrı bir şema; ortak tipler `$defs` içinde
+fn schema_files() -> Files {
+    let schemas = [
+        ("TaskbarUpdate", schemars::schema_for!(TaskbarUpdate)),
+        (
+            "TaskbarGroupUpdate",
+            schemars::schema_for!(TaskbarGroupUpdate),
+        ),
+        ("TaskbarEvent", schemars::schema_for!(TaskbarEvent)),
+        ("MediaEvent", schemars::schema_for!(MediaEvent)),
+        (
+            "Envelope",
+            schemars::schema_for!(Envelope<serde_json::Value>),
+        ),
+        ("AruiError", schemars::schema_for!(AruiError)),
+        (
+            "HostMessage",
+            schemars::schema_for!(HostMessage<serde_json::Value>),
+        ),
+    ];
+
+    schemas
+        .into_iter()
+        .map(|(name, schema)| {
+            let json = serde_json::to_string_pretty(&schema).expect("schema serializes");
+            (format!("{}.schema.json", name), json + "\n")
+        })
+        .collect()
+}
+
+/// ts-rs her tipi bağımlılıklarıyla birlikte ayrı bir dosyaya yazar;
+/// önce geçici bir klasöre üretip oradan okuruz
+fn typescript_files() -> Files {
+    let tmp_dir = std::env::temp_dir().join(format!("arui-protocol-{}", std::process::id()));
+    let export = || -> Result<(), ts_rs::ExportError> {
+        TaskbarUpdate::export_all_to(&tmp_dir)?;
+        TaskbarGroupUpdate::export_all_to(&tmp_dir)?;
+        TaskbarEvent::export_all_to(&tmp_dir)?;
+        MediaEvent::export_all_to(&tmp_dir)?;
+        Envelope::<serde_json::Value>::export_all_to(&tmp_dir)?;
+        HostMessage::<serde_json::Value>::export_all_to(&tmp_dir)?;
+        Ok(())
+    };
+    if let Err(e) = export() {
+        eprintln!("Could not export TypeScript bindings: {}", e);
+        std::process::exit(1);
+    }
+
+    let mut files = read_dir(&tmp_dir, "ts");
+    let _ = std::fs::remove_dir_all(&tmp_dir);
+
+    // Tüm tipleri ve protokol sürümünü tek yerden export eden index
+    let mut index =
+        String::from("// This file was generated by arui-protocol-codegen. Do not edit.\n\n");
+    index += &format!("export const PROTOCOL_VERSION = {};\n\n", PROTOCOL_VERSION);
+    for name in files.keys() {
+        let module = name.trim_end_matches(".ts");
+        index += &format!("export type * from \"./{}\";\n", module);
+    }
+    files.insert("index.ts".to_string(), index);
+    files
+}
+
+/// Klasördeki (alt klasörler dahil) dosyalar, klasöre göre göreli yollarıyla
+fn read_dir(dir: &Path, extension: &str) -> Files {
+    let mut files = Files::new();
+    collect_files(dir, dir, extension, &mut files);
+    files
+}
+
+fn collect_files(root: &Path, dir: &Path, extension: &str, files: &mut Files) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for path in entries.flatten().map(|entry| entry.path()) {
+        if path.is_dir() {
+            collect_files(root, &path, extension, files);
+            continue;
+        }
+        if path.extension().is_none_or(|ext| ext != extension) {
+            continue;
+        }
+        let (Ok(relative), Ok(contents)) =
+            (path.strip_prefix(root), std::fs::read_to_string(&path))
+        else {
+            continue;
+        };
+        // Windows'ta da "/" ile; import yollarıyla aynı olsun
+        let name = relative.to_string_lossy().replace('\\', "/");
+        files.insert(name, contents);
+    }
+}
+
+/// Üretilen dosyaları yazar ve artık üretilmeyen eski dosyaları siler
+fn write(dir: &Path, extension: &str, files: &Files) -> std::io::Result<()> {
+    std::fs::create_dir_all(dir)?;
+    for name in read_dir(dir, extension).keys() {
+        if !files.contains_key(name) {
+            std::fs::remove_file(dir.join(name))?;
+        }
+    }
+    for (name, contents) in files {
+        let path = dir.join(name);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, contents)?;
+    }
+    Ok(())
+}
+
+/// Diskteki dosyalardan farklı, eksik veya fazla olanların yolları
+fn compare(dir: &Path, extension: &str, files: &Files) -> Vec<PathBuf> {
+    let existing = read_dir(dir, extension);
+    let mut stale: Vec<PathBuf> = files
+        .iter()
+        .filter(|(name, contents)| existing.get(*name) != Some(*contents))
+        .map(|(name, _)| dir.join(name))
+        .collect();
+    stale.extend(
+        existing
+            .keys()
+            .filter(|name| !files.contains_key(*name))
+            .map(|name| dir.join(name)),
+    );
+    stale
+}
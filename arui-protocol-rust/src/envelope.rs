@@ -0,0 +1,160 @@
+use crate::error::{AruiError, ErrorCode, Result};
+use serde::{Deserialize, Serialize};
+
+/// Komut yanıtlarının ortak zarfı: başarıda `{"ok": true, "data": ...}`,
+/// hatada `{"ok": false, "error": {...}}`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS), ts(bound = "T: ts_rs::TS"))]
+pub struct Envelope<T> {
+    #[serde(default = "crate::protocol_version")]
+    pub protocol_version: u32,
+    pub ok: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript", ts(optional))]
+    pub data: Option<T>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript", ts(optional))]
+    pub error: Option<AruiError>,
+}
+
+impl<T> Envelope<T> {
+    pub fn ok(data: T) -> Self {
+        Self {
+            protocol_version: crate::PROTOCOL_VERSION,
+            ok: true,
+            data: Some(data),
+            error: None,
+        }
+    }
+
+    pub fn error(error: AruiError) -> Self {
+        Self {
+            protocol_version: crate::PROTOCOL_VERSION,
+            ok: false,
+            data: None,
+            error: Some(error),
+        }
+    }
+
+    /// Başarıda 0, hatada hatanın koduna ait çıkış kodu
+    pub fn exit_code(&self) -> i32 {
+        match &self.error {
+            Some(error) => error.exit_code(),
+            None if self.ok => 0,
+            None => ErrorCode::Failed.exit_code(),
+        }
+    }
+
+    pub fn into_result(self) -> Result<T> {
+        match (self.ok, self.data, self.error) {
+            (_, _, Some(error)) => Err(error),
+            (true, Some(data), None) => Ok(data),
+            _ => Err(AruiError::new(
+                ErrorCode::Internal,
+                "Response has neither data nor error",
+            )),
+        }
+    }
+}
+
+impl<T: Serialize> Envelope<T> {
+    pub fn to_value(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or_else(|e| {
+            serde_json::json!({
+                "protocol_version": crate::PROTOCOL_VERSION,
+                "ok": false,
+                "error": AruiError::new(ErrorCode::Internal, e.to_string())
+            })
+        })
+    }
+
+    /// Zarfı stdout'a tek satır olarak yazar ve process'in çıkış kodunu döner
+    pub fn print(&self) -> i32 {
+        println!("{}", self.to_value());
+        self.exit_code()
+    }
+}
+
+impl<T> From<Result<T>> for Envelope<T> {
+    fn from(result: Result<T>) -> Self {
+        match result {
+            Ok(data) => Self::ok(data),
+            Err(error) => Self::error(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn success_has_data_and_no_error() {
+        let envelope = Envelope::ok(json!({"hwnd": "42"}));
+        assert_eq!(
+            envelope.to_value(),
+            json!({
+                "protocol_version": crate::PROTOCOL_VERSION,
+                "ok": true,
+                "data": {"hwnd": "42"}
+            })
+        );
+        assert_eq!(envelope.exit_code(), 0);
+    }
+
+    #[test]
+    fn error_has_error_and_no_data() {
+        let envelope = Envelope::<()>::error(AruiError::win32(1400, "gone"));
+        assert_eq!(
+            envelope.to_value(),
+            json!({
+                "protocol_version": crate::PROTOCOL_VERSION,
+                "ok": false,
+                "error": {"code": "invalid_handle", "message": "gone", "win32": 1400}
+            })
+        );
+        assert_eq!(envelope.exit_code(), ErrorCode::InvalidHandle.exit_code());
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        let ok: Envelope<u32> = serde_json::from_value(Envelope::ok(7).to_value()).unwrap();
+        assert_eq!(ok.into_result(), Ok(7));
+
+        let error = AruiError::new(ErrorCode::NoSession, "nothing playing");
+        let failed: Envelope<u32> =
+            serde_json::from_value(Envelope::<u32>::error(error.clone()).to_value()).unwrap();
+        assert_eq!(failed.into_result(), Err(error));
+
+        // Zarfta ne data ne error varsa iç hata sayılır
+        let empty: Envelope<u32> = serde_json::from_value(json!({"ok": true})).unwrap();
+        assert_eq!(empty.protocol_version, 1);
+        assert_eq!(empty.exit_code(), 0);
+        assert_eq!(empty.into_result().unwrap_err().code, ErrorCode::Internal);
+    }
+
+    #[test]
+    fn from_result_picks_the_matching_shape() {
+        assert_eq!(Envelope::from(Ok(1)), Envelope::ok(1));
+        let error = AruiError::new(ErrorCode::NotFound, "missing");
+        assert_eq!(
+            Envelope::<i32>::from(Err(error.clone())),
+            Envelope::error(error)
+        );
+    }
+
+    #[test]
+    fn serialization_failure_is_an_internal_error_envelope() {
+        // JSON nesnelerinin anahtarı string olmak zorunda
+        let data = BTreeMap::from([(vec![1u8], 1u8)]);
+        let value = Envelope::ok(data).to_value();
+
+        assert_eq!(value["protocol_version"], json!(crate::PROTOCOL_VERSION));
+        assert_eq!(value["ok"], json!(false));
+        assert_eq!(value["error"]["code"], json!("internal"));
+        assert!(value.get("data").is_none());
+    }
+}
@@ -0,0 +1,380 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+pub type Result<T> = std::result::Result<T, AruiError>;
+
+/// Renderer'ın mesaj metnine bakmadan tepki verebilmesi için makine
+/// tarafından okunabilir hata kodları
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    /// Komut parametreleri geçersiz
+    InvalidArgument,
+    /// Pencere handle'ı artık var olan bir pencereye ait değil
+    InvalidHandle,
+    /// Dosya, pencere, icon veya pinned item bulunamadı
+    NotFound,
+    /// Windows işlemi reddetti (yetki, foreground kilidi vb.)
+    AccessDenied,
+    /// İşlem bu pencere/uygulama/sistem tarafından desteklenmiyor
+    Unsupported,
+    /// Aktif bir medya oturumu yok
+    NoSession,
+    /// Dosya okuma/yazma hatası
+    Io,
+    /// İşlem denendi ama başarısız oldu
+    Failed,
+    /// Beklenmeyen iç hata
+    Internal,
+}
+
+impl ErrorCode {
+    /// Tek seferlik komutların process çıkış kodu; başarı her zaman 0'dır.
+    /// 2, clap'in kullanım hatalarıyla aynıdır.
+    pub fn exit_code(self) -> i32 {
+        match self {
+            ErrorCode::Failed => 1,
+            ErrorCode::InvalidArgument => 2,
+            ErrorCode::NotFound => 3,
+            ErrorCode::AccessDenied => 4,
+            ErrorCode::Unsupported => 5,
+            ErrorCode::NoSession => 6,
+            ErrorCode::InvalidHandle => 7,
+            ErrorCode::Io => 8,
+            ErrorCode::Internal => 9,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ErrorCode::InvalidArgument => "invalid_argument",
+            ErrorCode::InvalidHandle => "invalid_handle",
+            ErrorCode::NotFound => "not_found",
+            ErrorCode::AccessDenied => "access_denied",
+            ErrorCode::Unsupported => "unsupported",
+            ErrorCode::NoSession => "no_session",
+            ErrorCode::Io => "io",
+            ErrorCode::Failed => "failed",
+            ErrorCode::Internal => "internal",
+        }
+    }
+
+    /// `GetLastError()` değerini en yakın koda çevirir
+    pub fn from_win32(code: u32) -> Self {
+        match code {
+            // ERROR_FILE_NOT_FOUND, ERROR_PATH_NOT_FOUND, ERROR_MOD_NOT_FOUND, ERROR_NOT_FOUND
+            2 | 3 | 126 | 1168 => ErrorCode::NotFound,
+            // ERROR_ACCESS_DENIED, ERROR_PRIVILEGE_NOT_HELD
+            5 | 1314 => ErrorCode::AccessDenied,
+            // ERROR_INVALID_HANDLE, ERROR_INVALID_WINDOW_HANDLE
+            6 | 1400 => ErrorCode::InvalidHandle,
+            // ERROR_NOT_SUPPORTED, ERROR_CALL_NOT_IMPLEMENTED
+            50 | 120 => ErrorCode::Unsupported,
+            // ERROR_INVALID_PARAMETER
+            87 => ErrorCode::InvalidArgument,
+            _ => ErrorCode::Failed,
+        }
+    }
+
+    /// HRESULT'u en yakın koda çevirir; `HRESULT_FROM_WIN32` değerleri
+    /// Win32 koduna göre eşlenir
+    pub fn from_hresult(hresult: i32) -> Self {
+        let value = hresult as u32;
+        match value {
+            // E_NOTIMPL, E_NOINTERFACE
+            0x8000_4001 | 0x8000_4002 => ErrorCode::Unsupported,
+            // FACILITY_WIN32
+            _ if value & 0xFFFF_0000 == 0x8007_0000 => Self::from_win32(value & 0xFFFF),
+            _ => ErrorCode::Failed,
+        }
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Ortak hata tipi. Altta yatan Win32 hata kodu ya da HRESULT varsa
+/// kaybolmaması için yanında taşınır.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct AruiError {
+    pub code: ErrorCode,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript", ts(optional))]
+    pub win32: Option<u32>,
+    /// JSON'da "0x80070005" şeklinde yazılır
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_hresult",
+        deserialize_with = "deserialize_hresult"
+    )]
+    #[cfg_attr(feature = "schema", schemars(with = "Option<String>"))]
+    #[cfg_attr(feature = "typescript", ts(as = "Option<String>", optional))]
+    pub hresult: Option<i32>,
+}
+
+impl AruiError {
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            win32: None,
+            hresult: None,
+        }
+    }
+
+    /// Win32 hata kodundan; kod da ondan türetilir
+    pub fn win32(error: u32, message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::from_win32(error), message).with_win32(error)
+    }
+
+    /// HRESULT'tan; kod da ondan türetilir
+    pub fn hresult(hresult: i32, message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::from_hresult(hresult), message).with_hresult(hresult)
+    }
+
+    /// Son Win32 hatasından (`GetLastError`). Windows bir hata bırakmadıysa
+    /// `fallback` kodu kullanılır.
+    pub fn last_os_error(fallback: ErrorCode, message: impl Into<String>) -> Self {
+        match std::io::Error::last_os_error().raw_os_error() {
+            Some(error) if error != 0 => Self::win32(error as u32, message),
+            _ => Self::new(fallback, message),
+        }
+    }
+
+    /// Dosya hatasından; mesajın sonuna OS'un açıklaması eklenir, kod hatanın
+    /// türünden türetilir ve OS hata kodu korunur
+    pub fn io(error: std::io::Error, context: impl Into<String>) -> Self {
+        let mut result = Self::from(error);
+        result.message = format!("{}: {}", context.into(), result.message);
+        result
+    }
+
+    pub fn with_win32(mut self, error: u32) -> Self {
+        self.win32 = Some(error);
+        self
+    }
+
+    pub fn with_hresult(mut self, hresult: i32) -> Self {
+        self.hresult = Some(hresult);
+        self
+    }
+
+    pub fn exit_code(&self) -> i32 {
+        self.code.exit_code()
+    }
+}
+
+impl fmt::Display for AruiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.code, self.message)?;
+        if let Some(error) = self.win32 {
+            write!(f, " (Win32 error {})", error)?;
+        }
+        if let Some(hresult) = self.hresult {
+            write!(f, " (HRESULT 0x{:08X})", hresult as u32)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for AruiError {}
+
+impl From<std::io::Error> for AruiError {
+    fn from(error: std::io::Error) -> Self {
+        let code = match error.kind() {
+            std::io::ErrorKind::NotFound => ErrorCode::NotFound,
+            std::io::ErrorKind::PermissionDenied => ErrorCode::AccessDenied,
+            std::io::ErrorKind::InvalidInput | std::io::ErrorKind::InvalidData => {
+                ErrorCode::InvalidArgument
+            }
+            _ => ErrorCode::Io,
+        };
+
+        let mut result = Self::new(code, error.to_string());
+        result.win32 = error.raw_os_error().map(|error| error as u32);
+        result
+    }
+}
+
+fn serialize_hresult<S: Serializer>(
+    hresult: &Option<i32>,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    match hresult {
+        Some(hresult) => serializer.collect_str(&format_args!("0x{:08X}", *hresult as u32)),
+        None => serializer.serialize_none(),
+    }
+}
+
+fn deserialize_hresult<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<Option<i32>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Raw {
+        Number(i64),
+        Text(String),
+    }
+
+    match Option::<Raw>::deserialize(deserializer)? {
+        None => Ok(None),
+        Some(Raw::Number(value)) => Ok(Some(value as i32)),
+        Some(Raw::Text(value)) => {
+            let hex = value
+                .strip_prefix("0x")
+                .or_else(|| value.strip_prefix("0X"))
+                .unwrap_or(&value);
+            u32::from_str_radix(hex, 16)
+                .map(|value| Some(value as i32))
+                .map_err(|_| serde::de::Error::custom(format!("Invalid HRESULT: {}", value)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const CODES: [ErrorCode; 9] = [
+        ErrorCode::InvalidArgument,
+        ErrorCode::InvalidHandle,
+        ErrorCode::NotFound,
+        ErrorCode::AccessDenied,
+        ErrorCode::Unsupported,
+        ErrorCode::NoSession,
+        ErrorCode::Io,
+        ErrorCode::Failed,
+        ErrorCode::Internal,
+    ];
+
+    #[test]
+    fn exit_codes_are_distinct_and_never_success() {
+        let mut exit_codes: Vec<i32> = CODES.iter().map(|code| code.exit_code()).collect();
+        assert!(exit_codes.iter().all(|&exit_code| exit_code > 0));
+        exit_codes.sort();
+        exit_codes.dedup();
+        assert_eq!(exit_codes.len(), CODES.len());
+
+        // clap'in kullanım hatalarıyla aynı
+        assert_eq!(ErrorCode::InvalidArgument.exit_code(), 2);
+        assert_eq!(ErrorCode::Failed.exit_code(), 1);
+        assert_eq!(
+            AruiError::new(ErrorCode::NoSession, "").exit_code(),
+            ErrorCode::NoSession.exit_code()
+        );
+    }
+
+    #[test]
+    fn codes_serialize_as_their_names() {
+        for code in CODES {
+            assert_eq!(serde_json::to_value(code).unwrap(), json!(code.as_str()));
+            assert_eq!(code.to_string(), code.as_str());
+            let parsed: ErrorCode = serde_json::from_value(json!(code.as_str())).unwrap();
+            assert_eq!(parsed, code);
+        }
+    }
+
+    #[test]
+    fn win32_errors_map_to_codes() {
+        assert_eq!(ErrorCode::from_win32(1400), ErrorCode::InvalidHandle);
+        assert_eq!(ErrorCode::from_win32(6), ErrorCode::InvalidHandle);
+        assert_eq!(ErrorCode::from_win32(5), ErrorCode::AccessDenied);
+        assert_eq!(ErrorCode::from_win32(1314), ErrorCode::AccessDenied);
+        assert_eq!(ErrorCode::from_win32(2), ErrorCode::NotFound);
+        assert_eq!(ErrorCode::from_win32(1168), ErrorCode::NotFound);
+        assert_eq!(ErrorCode::from_win32(50), ErrorCode::Unsupported);
+        assert_eq!(ErrorCode::from_win32(87), ErrorCode::InvalidArgument);
+        assert_eq!(ErrorCode::from_win32(31), ErrorCode::Failed);
+
+        let error = AruiError::win32(1400, "Invalid window handle");
+        assert_eq!(error.code, ErrorCode::InvalidHandle);
+        assert_eq!(error.win32, Some(1400));
+        assert_eq!(error.hresult, None);
+    }
+
+    #[test]
+    fn hresults_map_to_codes() {
+        // HRESULT_FROM_WIN32(ERROR_ACCESS_DENIED)
+        assert_eq!(
+            ErrorCode::from_hresult(0x8007_0005_u32 as i32),
+            ErrorCode::AccessDenied
+        );
+        // HRESULT_FROM_WIN32(ERROR_INVALID_WINDOW_HANDLE)
+        assert_eq!(
+            ErrorCode::from_hresult(0x8007_0578_u32 as i32),
+            ErrorCode::InvalidHandle
+        );
+        // E_NOTIMPL, E_NOINTERFACE
+        assert_eq!(
+            ErrorCode::from_hresult(0x8000_4001_u32 as i32),
+            ErrorCode::Unsupported
+        );
+        assert_eq!(
+            ErrorCode::from_hresult(0x8000_4002_u32 as i32),
+            ErrorCode::Unsupported
+        );
+        // E_FAIL
+        assert_eq!(
+            ErrorCode::from_hresult(0x8000_4005_u32 as i32),
+            ErrorCode::Failed
+        );
+
+        let error = AruiError::hresult(0x8007_0005_u32 as i32, "denied");
+        assert_eq!(error.code, ErrorCode::AccessDenied);
+        assert_eq!(error.hresult, Some(0x8007_0005_u32 as i32));
+    }
+
+    #[test]
+    fn hresult_is_written_as_hex_and_read_from_either_form() {
+        let error = AruiError::hresult(0x8007_0005_u32 as i32, "denied");
+        let value = serde_json::to_value(&error).unwrap();
+        assert_eq!(
+            value,
+            json!({"code": "access_denied", "message": "denied", "hresult": "0x80070005"})
+        );
+        assert_eq!(serde_json::from_value::<AruiError>(value).unwrap(), error);
+
+        let from_number: AruiError = serde_json::from_value(
+            json!({"code": "access_denied", "message": "denied", "hresult": -2147024891}),
+        )
+        .unwrap();
+        assert_eq!(from_number, error);
+
+        let invalid = json!({"code": "failed", "message": "", "hresult": "0xZZ"});
+        assert!(serde_json::from_value::<AruiError>(invalid).is_err());
+    }
+
+    #[test]
+    fn display_includes_the_underlying_codes() {
+        let error = AruiError::win32(5, "denied").with_hresult(0x8007_0005_u32 as i32);
+        assert_eq!(
+            error.to_string(),
+            "access_denied: denied (Win32 error 5) (HRESULT 0x80070005)"
+        );
+    }
+
+    #[test]
+    fn io_errors_keep_the_os_code() {
+        let error = AruiError::io(
+            std::io::Error::from_raw_os_error(2),
+            "Could not read pinned.json",
+        );
+        assert_eq!(error.win32, Some(2));
+        assert!(error.message.starts_with("Could not read pinned.json: "));
+
+        let not_found = std::io::Error::new(std::io::ErrorKind::NotFound, "missing");
+        assert_eq!(AruiError::from(not_found).code, ErrorCode::NotFound);
+        let invalid = std::io::Error::new(std::io::ErrorKind::InvalidData, "bad");
+        assert_eq!(AruiError::from(invalid).code, ErrorCode::InvalidArgument);
+    }
+}
@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+
+/// arui-native host'unda aynı process içinde çalışan alt sistemler
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(rename_all = "snake_case")]
+pub enum Subsystem {
+    // Host'un kendi mesajları (örn. bilinmeyen alt sisteme gönderilen satır)
+    Host,
+    TaskbarManager,
+    TaskbarItems,
+    MediaInfo,
+}
+
+impl Subsystem {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Subsystem::Host => "host",
+            Subsystem::TaskbarManager => "taskbar_manager",
+            Subsystem::TaskbarItems => "taskbar_items",
+            Subsystem::MediaInfo => "media_info",
+        }
+    }
+}
+
+impl std::fmt::Display for Subsystem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for Subsystem {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        // Komut satırında "taskbar-items" de kabul edilir
+        match s.replace('-', "_").as_str() {
+            "host" => Ok(Subsystem::Host),
+            "taskbar_manager" => Ok(Subsystem::TaskbarManager),
+            "taskbar_items" => Ok(Subsystem::TaskbarItems),
+            "media_info" => Ok(Subsystem::MediaInfo),
+            _ => Err(format!("Unknown subsystem: {}", s)),
+        }
+    }
+}
+
+/// arui-native'in stdout/stdin satırı: bir alt sistemin mesajı, hangi
+/// alt sisteme ait olduğu bilgisiyle sarılmış halde
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS), ts(bound = "T: ts_rs::TS"))]
+pub struct HostMessage<T> {
+    #[serde(default = "crate::protocol_version")]
+    pub protocol_version: u32,
+    pub subsystem: Subsystem,
+    // Alt sistemin tek başına çalışırken yazacağı satırın aynısı
+    pub message: T,
+}
+
+impl<T> HostMessage<T> {
+    pub fn new(subsystem: Subsystem, message: T) -> Self {
+        Self {
+            protocol_version: crate::PROTOCOL_VERSION,
+            subsystem,
+            message,
+        }
+    }
+}
@@ -0,0 +1,44 @@
+//! ARUI native yardımcılarının (taskbar item list, taskbar manager,
+//! media info) ortak mesaj tipleri, hata modeli ve JSON yanıt zarfı.
+//!
+//! Her komut yanıtı `{"ok": true, "data": ...}` ya da
+//! `{"ok": false, "error": {"code": ..., "message": ...}}` şeklindedir ve
+//! process çıkış kodu hatanın koduna göre belirlenir. Stream mesajları
+//! `protocol_version` alanını taşır. arui-native host'unda her satır
+//! `{"subsystem": ..., "message": ...}` şeklinde alt sistemin adıyla sarılır.
+//!
+//! JSON Schema ve TypeScript tanımları bu tiplerden üretilir:
+//! `cargo run --features schema,typescript --bin arui-protocol-codegen`
+
+mod envelope;
+mod error;
+mod host;
+mod media;
+mod output;
+pub mod rpc;
+mod taskbar;
+mod taskbar_manager;
+mod window_handle;
+
+pub use envelope::Envelope;
+pub use error::{AruiError, ErrorCode, Result};
+pub use host::{HostMessage, Subsystem};
+pub use media::{MediaEvent, MediaEventKind, MediaInfo, PlaybackStatus};
+pub use output::Output;
+pub use taskbar::{
+    ItemChange, MatchedRules, TaskbarGroup, TaskbarGroupUpdate, TaskbarItem, TaskbarUpdate,
+};
+pub use taskbar_manager::{
+    MousePosition, RestoreMethod, TaskbarEvent, TaskbarEventKind, TaskbarKind,
+};
+pub use window_handle::WindowHandle;
+
+/// Mesaj şemalarının sürümü. Alan silmek, yeniden adlandırmak veya
+/// anlamını değiştirmek gibi uyumsuz değişikliklerde artırılır.
+pub const PROTOCOL_VERSION: u32 = 3;
+
+/// serde varsayılanı: alanı olmayan mesajlar, alan eklenmeden önceki ilk
+/// sürümden (1) sayılır
+pub fn protocol_version() -> u32 {
+    1
+}
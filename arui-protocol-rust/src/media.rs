@@ -0,0 +1,86 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// win-media-info'nun stream mesajları. Türü `event_type` alanındadır:
+/// `{"event_type": "current_session_changed", "app_name": "Spotify.exe", ...}`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct MediaEvent {
+    #[serde(default = "crate::protocol_version")]
+    pub protocol_version: u32,
+    #[serde(flatten)]
+    #[cfg_attr(feature = "typescript", ts(flatten))]
+    pub kind: MediaEventKind,
+}
+
+impl MediaEvent {
+    pub fn new(kind: MediaEventKind) -> Self {
+        Self {
+            protocol_version: crate::PROTOCOL_VERSION,
+            kind,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(tag = "event_type", rename_all = "snake_case")]
+pub enum MediaEventKind {
+    /// Bir oturum değişti, açıldı ya da kapandı. Tüm oturumlar
+    /// `SourceAppUserModelId`'lerine göre listelenir.
+    SessionsChanged {
+        sessions: BTreeMap<String, MediaInfo>,
+    },
+    /// Windows'un o anki saydığı oturum değişti; oturum yoksa `app_name` boştur
+    CurrentSessionChanged { app_name: Option<String> },
+}
+
+/// win-media-info'nun raporladığı bir medya oturumu
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct MediaInfo {
+    #[serde(default = "crate::protocol_version")]
+    pub protocol_version: u32,
+    pub title: Option<String>,
+    pub artist: Option<String>,
+    pub album: Option<String>,
+    pub playback_status: PlaybackStatus,
+    // Milisaniye
+    #[cfg_attr(feature = "typescript", ts(type = "number | null"))]
+    pub position: Option<u64>,
+    // Milisaniye
+    #[cfg_attr(feature = "typescript", ts(type = "number | null"))]
+    pub duration: Option<u64>,
+    pub app_name: Option<String>,
+    pub has_artwork: bool,
+}
+
+impl Default for MediaInfo {
+    fn default() -> Self {
+        Self {
+            protocol_version: crate::PROTOCOL_VERSION,
+            title: None,
+            artist: None,
+            album: None,
+            playback_status: PlaybackStatus::Unknown,
+            position: None,
+            duration: None,
+            app_name: None,
+            has_artwork: false,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub enum PlaybackStatus {
+    Playing,
+    Paused,
+    Stopped,
+    #[default]
+    Unknown,
+}
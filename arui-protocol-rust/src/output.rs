@@ -0,0 +1,39 @@
+use crate::host::{HostMessage, Subsystem};
+use serde::Serialize;
+
+/// Bir yardımcının mesajlarını yazdığı yer. Tek başına çalışan binary'ler
+/// mesajları olduğu gibi stdout'a yazar; arui-native'de her mesaj
+/// alt sistemin adıyla `HostMessage` içine sarılır.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Output {
+    subsystem: Option<Subsystem>,
+}
+
+impl Output {
+    pub fn stdout() -> Self {
+        Self { subsystem: None }
+    }
+
+    pub fn tagged(subsystem: Subsystem) -> Self {
+        Self {
+            subsystem: Some(subsystem),
+        }
+    }
+
+    pub fn subsystem(&self) -> Option<Subsystem> {
+        self.subsystem
+    }
+
+    /// Mesajı tek satır JSON olarak yazar. `println!` satırı yazarken
+    /// stdout'u kilitler, bu yüzden farklı alt sistemlerin satırları karışmaz.
+    pub fn emit<T: Serialize + ?Sized>(&self, message: &T) {
+        let line = match self.subsystem {
+            None => serde_json::to_string(message),
+            Some(subsystem) => serde_json::to_string(&HostMessage::new(subsystem, message)),
+        };
+        match line {
+            Ok(line) => println!("{}", line),
+            Err(e) => eprintln!("Could not serialize message: {}", e),
+        }
+    }
+}
@@ -0,0 +1,244 @@
+//! Serve modunda stdin'den gelen tek satırlık JSON-RPC 2.0 istekleri ve yanıtları
+
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::Value;
+
+// JSON-RPC 2.0 hata kodları
+pub const PARSE_ERROR: i64 = -32700;
+pub const INVALID_REQUEST: i64 = -32600;
+pub const METHOD_NOT_FOUND: i64 = -32601;
+pub const INVALID_PARAMS: i64 = -32602;
+
+/// stdin'den gelen tek satırlık JSON-RPC isteği
+#[derive(Deserialize, Clone, Debug)]
+pub struct RpcRequest {
+    #[serde(default)]
+    pub id: Value,
+    pub method: String,
+    #[serde(default)]
+    pub params: Option<Value>,
+}
+
+impl RpcRequest {
+    /// Geçersiz JSON `PARSE_ERROR`, JSON-RPC isteği olmayan bir nesne ya da
+    /// string, sayı veya `null` olmayan bir id `INVALID_REQUEST` döner
+    pub fn parse(line: &str) -> Result<Self, Value> {
+        let value: Value = serde_json::from_str(line).map_err(|e| {
+            error_response(Value::Null, PARSE_ERROR, &format!("Parse error: {}", e))
+        })?;
+
+        // Yanıt sadece geçerli bir id ile eşleştirilebilir
+        let id = match value.get("id") {
+            None => Value::Null,
+            Some(id @ (Value::Null | Value::String(_) | Value::Number(_))) => id.clone(),
+            Some(_) => {
+                let message = "Invalid request: id must be a string, number or null";
+                return Err(error_response(Value::Null, INVALID_REQUEST, message));
+            }
+        };
+
+        serde_json::from_value(value)
+            .map_err(|e| error_response(id, INVALID_REQUEST, &format!("Invalid request: {}", e)))
+    }
+
+    /// method ve params'ı `tag = "method", content = "params"` olan bir enum'a çevirir
+    pub fn command<T: DeserializeOwned>(&self) -> Result<T, Value> {
+        let mut request = serde_json::Map::new();
+        request.insert("method".to_string(), Value::String(self.method.clone()));
+        // Parametresiz method'lar için boş params gönderilmesine izin ver
+        match &self.params {
+            None | Some(Value::Null) => {}
+            Some(Value::Object(params)) if params.is_empty() => {}
+            Some(params) => {
+                request.insert("params".to_string(), params.clone());
+            }
+        }
+
+        // Parametrelerinin hepsi isteğe bağlı olan method'lar da params'sız çağrılabilir
+        let parsed = serde_json::from_value(Value::Object(request.clone()));
+        let parsed = match parsed {
+            Err(_) if !request.contains_key("params") => {
+                request.insert("params".to_string(), Value::Object(Default::default()));
+                serde_json::from_value(Value::Object(request))
+            }
+            parsed => parsed,
+        };
+
+        parsed.map_err(|e| {
+            let message = e.to_string();
+            // Parametrelerdeki bilinmeyen enum değerleri de "unknown variant" üretir
+            let unknown_method = format!("unknown variant `{}`", self.method);
+            let code = if message.starts_with(&unknown_method) {
+                METHOD_NOT_FOUND
+            } else {
+                INVALID_PARAMS
+            };
+            error_response(self.id.clone(), code, &message)
+        })
+    }
+}
+
+pub fn response(id: Value, result: Value) -> Value {
+    serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "result": result
+    })
+}
+
+pub fn error_response(id: Value, code: i64, message: &str) -> Value {
+    serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": {
+            "code": code,
+            "message": message
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    #[serde(tag = "method", content = "params", rename_all = "snake_case")]
+    enum Command {
+        Status,
+        Focus {
+            hwnd: u64,
+        },
+        Resize {
+            #[serde(default)]
+            width: Option<u32>,
+        },
+        Dock {
+            edge: Edge,
+        },
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    #[serde(rename_all = "snake_case")]
+    enum Edge {
+        Top,
+    }
+
+    fn error_code(response: &Value) -> i64 {
+        response["error"]["code"].as_i64().unwrap()
+    }
+
+    #[test]
+    fn invalid_json_is_a_parse_error() {
+        for line in ["", "{", "not json", "{\"id\": 1, \"method\": }"] {
+            let response = RpcRequest::parse(line).unwrap_err();
+            assert_eq!(error_code(&response), PARSE_ERROR, "{:?}", line);
+            assert_eq!(response["id"], Value::Null);
+            assert_eq!(response["jsonrpc"], "2.0");
+        }
+    }
+
+    #[test]
+    fn requests_without_a_method_are_invalid() {
+        for line in ["[]", "42", "{\"id\": 7}", "{\"id\": 7, \"method\": 3}"] {
+            let response = RpcRequest::parse(line).unwrap_err();
+            assert_eq!(error_code(&response), INVALID_REQUEST, "{:?}", line);
+        }
+        // Geçerli id varsa hata yine de istekle eşleşir
+        let response = RpcRequest::parse("{\"id\": 7}").unwrap_err();
+        assert_eq!(response["id"], json!(7));
+    }
+
+    #[test]
+    fn id_is_optional_but_must_be_scalar() {
+        let request = RpcRequest::parse("{\"method\": \"status\"}").unwrap();
+        assert_eq!(request.id, Value::Null);
+
+        for id in ["1", "\"abc\"", "null", "1.5"] {
+            let line = format!("{{\"id\": {}, \"method\": \"status\"}}", id);
+            assert!(RpcRequest::parse(&line).is_ok(), "{}", id);
+        }
+
+        for id in ["{}", "[1]", "true"] {
+            let line = format!("{{\"id\": {}, \"method\": \"status\"}}", id);
+            let response = RpcRequest::parse(&line).unwrap_err();
+            assert_eq!(error_code(&response), INVALID_REQUEST, "{}", id);
+            assert_eq!(response["id"], Value::Null);
+        }
+    }
+
+    #[test]
+    fn unknown_methods_are_reported_with_the_request_id() {
+        let request = RpcRequest::parse("{\"id\": \"a1\", \"method\": \"explode\"}").unwrap();
+        let response = request.command::<Command>().unwrap_err();
+        assert_eq!(error_code(&response), METHOD_NOT_FOUND);
+        assert_eq!(response["id"], json!("a1"));
+    }
+
+    #[test]
+    fn bad_params_are_invalid_params() {
+        for params in ["{}", "{\"hwnd\": \"x\"}", "[1]"] {
+            let line = format!(
+                "{{\"id\": 3, \"method\": \"focus\", \"params\": {}}}",
+                params
+            );
+            let request = RpcRequest::parse(&line).unwrap();
+            let response = request.command::<Command>().unwrap_err();
+            assert_eq!(error_code(&response), INVALID_PARAMS, "{}", params);
+            assert_eq!(response["id"], json!(3));
+        }
+    }
+
+    #[test]
+    fn unknown_enum_values_in_params_are_invalid_params() {
+        let line = "{\"id\": 3, \"method\": \"dock\", \"params\": {\"edge\": \"middle\"}}";
+        let request = RpcRequest::parse(line).unwrap();
+        let response = request.command::<Command>().unwrap_err();
+        assert_eq!(error_code(&response), INVALID_PARAMS);
+    }
+
+    #[test]
+    fn params_may_be_omitted_when_none_are_required() {
+        let parse = |line: &str| RpcRequest::parse(line).unwrap().command::<Command>();
+
+        assert_eq!(parse("{\"method\": \"status\"}").unwrap(), Command::Status);
+        assert_eq!(
+            parse("{\"method\": \"status\", \"params\": {}}").unwrap(),
+            Command::Status
+        );
+        assert_eq!(
+            parse("{\"method\": \"status\", \"params\": null}").unwrap(),
+            Command::Status
+        );
+        assert_eq!(
+            parse("{\"method\": \"resize\"}").unwrap(),
+            Command::Resize { width: None }
+        );
+        assert_eq!(
+            parse("{\"method\": \"focus\", \"params\": {\"hwnd\": 5}}").unwrap(),
+            Command::Focus { hwnd: 5 }
+        );
+    }
+
+    #[test]
+    fn responses_echo_the_request_id() {
+        for id in [json!(1), json!("req-1"), Value::Null] {
+            let line = json!({"id": id, "method": "status"}).to_string();
+            let request = RpcRequest::parse(&line).unwrap();
+
+            let response = response(request.id.clone(), json!({"ok": true}));
+            assert_eq!(
+                response,
+                json!({"jsonrpc": "2.0", "id": id, "result": {"ok": true}})
+            );
+
+            let error = error_response(request.id, INVALID_PARAMS, "bad");
+            assert_eq!(error["id"], id);
+            assert_eq!(
+                error["error"],
+                json!({"code": INVALID_PARAMS, "message": "bad"})
+            );
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use crate::window_handle::WindowHandle;
+use serde::{Deserialize, Serialize};
+
+/// win-taskbar-item-list'in raporladığı tek bir pencere veya pinned item
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct TaskbarItem {
+    pub title: String,
+    pub process_name: String,
+    pub process_id: u32,
+    // 64-bit handle'lar kaybolmasın diye string olarak yazılır; "0" = pencere yok
+    pub hwnd: WindowHandle,
+    pub is_visible: bool,
+    pub is_minimized: bool,
+    pub is_maximized: bool,
+    pub class_name: String,
+    pub has_taskbar_button: bool,
+    pub window_state: String, // "normal", "minimized", "maximized", "hidden"
+    pub is_pinned: bool,
+    pub executable_path: String,
+    pub item_type: String, // "running", "pinned", "both"
+    pub is_tray_icon: bool,
+    pub is_focused: bool, // Pencere şu anda odakta mı
+    pub is_running: bool, // Uygulama şu anda çalışıyor mu (process_id > 0)
+    // Yeni filtreleme için özel alanlar
+    pub is_definitely_taskbar: bool, // Kesin olarak taskbar'da görünen
+    pub is_definitely_tray: bool,    // Kesin olarak system tray'de olan
+    pub is_system_window: bool,      // Sistem penceresi (Windows Explorer, etc.)
+    pub display_location: String,    // "taskbar", "tray", "both", "hidden"
+    // Her karar için eşleşen kuralın adı
+    #[serde(default)]
+    pub matched_rules: MatchedRules,
+    // Executable icon'unun önbellek id'si; aynı id'ye sahip item'lar aynı icon'u kullanır
+    #[serde(default)]
+    pub icon_id: String,
+}
+
+/// Her hedef için hangi kuralın karar verdiği
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct MatchedRules {
+    pub taskbar: Option<String>,
+    pub tray: Option<String>,
+    pub system: Option<String>,
+}
+
+/// Monitor modunun item listesi mesajı: tam liste ya da diff modunda
+/// eklenen/kaldırılan/güncellenen item'lar
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct TaskbarUpdate {
+    #[serde(default = "crate::protocol_version")]
+    pub protocol_version: u32,
+    pub action: String, // "list", "added", "removed", "updated"
+    pub items: Vec<TaskbarItem>,
+    // "removed" için kaldırılan item key'leri
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "typescript", ts(as = "Option<Vec<String>>", optional))]
+    pub removed: Vec<String>,
+    // "updated" için sadece değişen alanlar
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "typescript", ts(as = "Option<Vec<ItemChange>>", optional))]
+    pub changes: Vec<ItemChange>,
+    // Unix zamanı (saniye)
+    #[cfg_attr(feature = "typescript", ts(type = "number"))]
+    pub timestamp: u64,
+}
+
+impl TaskbarUpdate {
+    pub fn new(action: &str, items: Vec<TaskbarItem>) -> Self {
+        Self {
+            protocol_version: crate::PROTOCOL_VERSION,
+            action: action.to_string(),
+            items,
+            removed: Vec::new(),
+            changes: Vec::new(),
+            timestamp: unix_timestamp(),
+        }
+    }
+}
+
+/// Bir item'ın değişen alanları ve yeni değerleri
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct ItemChange {
+    pub key: String,
+    // TaskbarItem alanlarının bir alt kümesi
+    pub changes: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Aynı uygulamaya ait pencereler (taskbar'daki birleşik buton)
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct TaskbarGroup {
+    pub app_id: String,
+    pub name: String,
+    pub executable_path: String,
+    pub pinned: bool,
+    // Sadece çalışan pencereler; pinned-only gruplar için boş
+    pub windows: Vec<TaskbarItem>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct TaskbarGroupUpdate {
+    #[serde(default = "crate::protocol_version")]
+    pub protocol_version: u32,
+    pub action: String, // "groups"
+    pub groups: Vec<TaskbarGroup>,
+    // Unix zamanı (saniye)
+    #[cfg_attr(feature = "typescript", ts(type = "number"))]
+    pub timestamp: u64,
+}
+
+impl TaskbarGroupUpdate {
+    pub fn new(groups: Vec<TaskbarGroup>) -> Self {
+        Self {
+            protocol_version: crate::PROTOCOL_VERSION,
+            action: "groups".to_string(),
+            groups,
+            timestamp: unix_timestamp(),
+        }
+    }
+}
+
+fn unix_timestamp() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
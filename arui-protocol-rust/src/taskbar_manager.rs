@@ -0,0 +1,279 @@
+use crate::error::AruiError;
+use crate::window_handle::WindowHandle;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// win-taskbar-manager'ın taskbar ve mouse event'leri. Event'in türü
+/// `event_type` alanındadır, türe özgü alanlar aynı nesneye yazılır:
+/// `{"event_type": "auto_hide", "which": "secondary", "hwnd": "1234", ...}`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct TaskbarEvent {
+    #[serde(default = "crate::protocol_version")]
+    pub protocol_version: u32,
+    pub timestamp: DateTime<Utc>,
+    pub mouse_position: MousePosition,
+    #[serde(flatten)]
+    #[cfg_attr(feature = "typescript", ts(flatten))]
+    pub kind: TaskbarEventKind,
+}
+
+impl TaskbarEvent {
+    pub fn new(kind: TaskbarEventKind, mouse_position: MousePosition) -> Self {
+        Self {
+            protocol_version: crate::PROTOCOL_VERSION,
+            timestamp: Utc::now(),
+            mouse_position,
+            kind,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(tag = "event_type", rename_all = "snake_case")]
+pub enum TaskbarEventKind {
+    TaskbarHidden,
+    TaskbarShown,
+    /// Taskbar gizlenmeden önceki durumuna getirildi (`restore` ya da kapanış)
+    TaskbarRestored {
+        method: RestoreMethod,
+    },
+    /// Başlangıçta çökmüş bir çalışmadan kalan kayıt uygulandı
+    TaskbarRecovered {
+        method: RestoreMethod,
+    },
+    /// Guardian görünür hale gelen bir taskbar'ı tekrar gizledi
+    AutoHide {
+        which: TaskbarKind,
+        hwnd: WindowHandle,
+    },
+    /// Mouse bir hot zone'a girdi; ARUI bar'ı gösterebilir
+    HotZoneEntered {
+        monitor: String,
+        zone: String,
+    },
+    HotZoneLeft {
+        monitor: String,
+        zone: String,
+    },
+    /// Explorer AppBar'ların yerini değiştirdi; bar yeniden yerleştirildi
+    AppbarPositionChanged,
+    /// AppBar'ın monitöründe tam ekran bir uygulama açıldı ya da kapandı
+    AppbarFullscreenApp {
+        active: bool,
+    },
+    /// Ön plandaki pencere bir monitörde tam ekran oldu
+    FullscreenEntered {
+        hwnd: WindowHandle,
+        monitor: String,
+    },
+    FullscreenExited {
+        hwnd: WindowHandle,
+        monitor: String,
+    },
+    /// Explorer yeniden başladı; yeni taskbar bulundu ve gizleme yeniden uygulandı
+    ExplorerRestarted {
+        taskbar: WindowHandle,
+    },
+    /// Taskbar manager'ı durdurmayan bir hata
+    Error {
+        error: AruiError,
+    },
+}
+
+/// Taskbar'ın hangi yolla geri getirildiği
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(rename_all = "snake_case")]
+pub enum RestoreMethod {
+    /// Kaydedilen durum uygulandı ve doğrulandı
+    RestoredInPlace,
+    /// Kaydedilen durum tutmadı; Explorer yeniden başlatıldı
+    RestoredViaExplorerRestart,
+}
+
+/// Ana taskbar (`Shell_TrayWnd`) ya da diğer monitörlerdekiler
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[serde(rename_all = "snake_case")]
+pub enum TaskbarKind {
+    Main,
+    Secondary,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+pub struct MousePosition {
+    pub x: i32,
+    pub y: i32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorCode;
+    use serde_json::{json, Value};
+
+    /// Her variant ve event'in JSON'daki beklenen türe özgü alanları
+    fn cases() -> Vec<(TaskbarEventKind, Value)> {
+        let hwnd = WindowHandle::from_raw(0x1234);
+        vec![
+            (
+                TaskbarEventKind::TaskbarHidden,
+                json!({"event_type": "taskbar_hidden"}),
+            ),
+            (
+                TaskbarEventKind::TaskbarShown,
+                json!({"event_type": "taskbar_shown"}),
+            ),
+            (
+                TaskbarEventKind::TaskbarRestored {
+                    method: RestoreMethod::RestoredInPlace,
+                },
+                json!({"event_type": "taskbar_restored", "method": "restored_in_place"}),
+            ),
+            (
+                TaskbarEventKind::TaskbarRecovered {
+                    method: RestoreMethod::RestoredViaExplorerRestart,
+                },
+                json!({"event_type": "taskbar_recovered", "method": "restored_via_explorer_restart"}),
+            ),
+            (
+                TaskbarEventKind::AutoHide {
+                    which: TaskbarKind::Secondary,
+                    hwnd,
+                },
+                json!({"event_type": "auto_hide", "which": "secondary", "hwnd": "4660"}),
+            ),
+            (
+                TaskbarEventKind::HotZoneEntered {
+                    monitor: "\\\\.\\DISPLAY1".to_string(),
+                    zone: "bottom".to_string(),
+                },
+                json!({"event_type": "hot_zone_entered", "monitor": "\\\\.\\DISPLAY1", "zone": "bottom"}),
+            ),
+            (
+                TaskbarEventKind::HotZoneLeft {
+                    monitor: "\\\\.\\DISPLAY1".to_string(),
+                    zone: "bottom".to_string(),
+                },
+                json!({"event_type": "hot_zone_left", "monitor": "\\\\.\\DISPLAY1", "zone": "bottom"}),
+            ),
+            (
+                TaskbarEventKind::AppbarPositionChanged,
+                json!({"event_type": "appbar_position_changed"}),
+            ),
+            (
+                TaskbarEventKind::AppbarFullscreenApp { active: true },
+                json!({"event_type": "appbar_fullscreen_app", "active": true}),
+            ),
+            (
+                TaskbarEventKind::FullscreenEntered {
+                    hwnd,
+                    monitor: "\\\\.\\DISPLAY2".to_string(),
+                },
+                json!({"event_type": "fullscreen_entered", "hwnd": "4660", "monitor": "\\\\.\\DISPLAY2"}),
+            ),
+            (
+                TaskbarEventKind::FullscreenExited {
+                    hwnd,
+                    monitor: "\\\\.\\DISPLAY2".to_string(),
+                },
+                json!({"event_type": "fullscreen_exited", "hwnd": "4660", "monitor": "\\\\.\\DISPLAY2"}),
+            ),
+            (
+                TaskbarEventKind::ExplorerRestarted { taskbar: hwnd },
+                json!({"event_type": "explorer_restarted", "taskbar": "4660"}),
+            ),
+            (
+                TaskbarEventKind::Error {
+                    error: AruiError::new(ErrorCode::AccessDenied, "denied"),
+                },
+                json!({"event_type": "error", "error": {"code": "access_denied", "message": "denied"}}),
+            ),
+        ]
+    }
+
+    /// Yeni bir variant eklendiğinde derleme burada durur; `cases()`'e de eklenmeli
+    fn event_type(kind: &TaskbarEventKind) -> &'static str {
+        match kind {
+            TaskbarEventKind::TaskbarHidden => "taskbar_hidden",
+            TaskbarEventKind::TaskbarShown => "taskbar_shown",
+            TaskbarEventKind::TaskbarRestored { .. } => "taskbar_restored",
+            TaskbarEventKind::TaskbarRecovered { .. } => "taskbar_recovered",
+            TaskbarEventKind::AutoHide { .. } => "auto_hide",
+            TaskbarEventKind::HotZoneEntered { .. } => "hot_zone_entered",
+            TaskbarEventKind::HotZoneLeft { .. } => "hot_zone_left",
+            TaskbarEventKind::AppbarPositionChanged => "appbar_position_changed",
+            TaskbarEventKind::AppbarFullscreenApp { .. } => "appbar_fullscreen_app",
+            TaskbarEventKind::FullscreenEntered { .. } => "fullscreen_entered",
+            TaskbarEventKind::FullscreenExited { .. } => "fullscreen_exited",
+            TaskbarEventKind::ExplorerRestarted { .. } => "explorer_restarted",
+            TaskbarEventKind::Error { .. } => "error",
+        }
+    }
+
+    #[test]
+    fn every_kind_round_trips_with_its_tag() {
+        let cases = cases();
+        let tags: std::collections::HashSet<_> =
+            cases.iter().map(|(kind, _)| event_type(kind)).collect();
+        assert_eq!(tags.len(), cases.len(), "each variant is covered once");
+
+        for (kind, expected) in cases {
+            let json = serde_json::to_value(&kind).unwrap();
+            assert_eq!(json, expected);
+            assert_eq!(json["event_type"], event_type(&kind));
+            assert_eq!(
+                serde_json::from_value::<TaskbarEventKind>(json).unwrap(),
+                kind
+            );
+        }
+    }
+
+    #[test]
+    fn event_flattens_the_kind_next_to_the_envelope_fields() {
+        for (kind, expected) in cases() {
+            let event = TaskbarEvent::new(kind, MousePosition { x: -5, y: 1080 });
+            let json = serde_json::to_value(&event).unwrap();
+
+            let object = json.as_object().unwrap();
+            assert_eq!(object["protocol_version"], crate::PROTOCOL_VERSION);
+            assert_eq!(object["mouse_position"], json!({"x": -5, "y": 1080}));
+            assert!(object["timestamp"].is_string());
+            for (field, value) in expected.as_object().unwrap() {
+                assert_eq!(&object[field], value, "{}", field);
+            }
+            assert_eq!(object.len(), 3 + expected.as_object().unwrap().len());
+
+            let parsed: TaskbarEvent = serde_json::from_value(json).unwrap();
+            assert_eq!(parsed, event);
+        }
+    }
+
+    #[test]
+    fn missing_protocol_version_is_the_first_version() {
+        let event: TaskbarEvent = serde_json::from_value(json!({
+            "timestamp": "2024-01-01T00:00:00Z",
+            "mouse_position": {"x": 0, "y": 0},
+            "event_type": "taskbar_hidden",
+        }))
+        .unwrap();
+
+        assert_eq!(event.protocol_version, 1);
+        assert_eq!(event.kind, TaskbarEventKind::TaskbarHidden);
+    }
+
+    #[test]
+    fn unknown_event_type_is_rejected() {
+        let result =
+            serde_json::from_value::<TaskbarEventKind>(json!({"event_type": "taskbar_exploded"}));
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,197 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+/// Kayıpsız pencere handle'ı. HWND pointer boyutundadır; `i32`'ye
+/// çevirmek 64-bit handle'ları keser ve komutların yanlış pencereye
+/// gitmesine yol açar.
+///
+/// JSON'da string olarak yazılır (JavaScript'in 2^53 sınırına takılmaz),
+/// okunurken hem string hem de sayı kabul edilir.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS), ts(type = "string"))]
+pub struct WindowHandle(u64);
+
+impl WindowHandle {
+    pub const NULL: WindowHandle = WindowHandle(0);
+
+    pub fn from_raw(raw: isize) -> Self {
+        Self(raw as usize as u64)
+    }
+
+    pub fn as_raw(self) -> isize {
+        self.0 as usize as isize
+    }
+
+    pub fn is_null(self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl fmt::Display for WindowHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for WindowHandle {
+    type Err = String;
+
+    /// Ondalık ("132456"), hex ("0x2056C") veya negatif (eski `i32`
+    /// çıktıları, "-123") değerleri kabul eder
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim();
+        let parsed = if let Some(hex) = value
+            .strip_prefix("0x")
+            .or_else(|| value.strip_prefix("0X"))
+        {
+            u64::from_str_radix(hex, 16).ok()
+        } else if value.starts_with('-') {
+            value
+                .parse::<i64>()
+                .ok()
+                .map(|v| v as isize as usize as u64)
+        } else {
+            value.parse::<u64>().ok()
+        };
+
+        parsed
+            .map(WindowHandle)
+            .ok_or_else(|| format!("Invalid window handle: {}", value))
+    }
+}
+
+impl Serialize for WindowHandle {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for WindowHandle {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Unsigned(u64),
+            Signed(i64),
+            Text(String),
+        }
+
+        match Raw::deserialize(deserializer)? {
+            Raw::Unsigned(value) => Ok(WindowHandle(value)),
+            Raw::Signed(value) => Ok(WindowHandle(value as isize as usize as u64)),
+            Raw::Text(value) => value.parse().map_err(serde::de::Error::custom),
+        }
+    }
+}
+
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for WindowHandle {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "WindowHandle".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "type": "string",
+            "pattern": "^[0-9]+$",
+            "description": "Window handle (HWND) as a decimal string; \"0\" means no window"
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn parse(value: serde_json::Value) -> Result<WindowHandle, serde_json::Error> {
+        serde_json::from_value(value)
+    }
+
+    #[test]
+    fn serializes_as_a_decimal_string_and_reads_it_back() {
+        let hwnd = WindowHandle::from_raw(0x2056C);
+        assert_eq!(serde_json::to_value(hwnd).unwrap(), json!("132460"));
+        assert_eq!(parse(json!("132460")).unwrap(), hwnd);
+        assert_eq!(parse(serde_json::to_value(hwnd).unwrap()).unwrap(), hwnd);
+    }
+
+    #[test]
+    fn reads_numbers_hex_and_negative_values() {
+        let hwnd = WindowHandle::from_raw(0x2056C);
+        assert_eq!(parse(json!(132460)).unwrap(), hwnd);
+        assert_eq!(parse(json!("0x2056C")).unwrap(), hwnd);
+        assert_eq!(parse(json!("0X2056c")).unwrap(), hwnd);
+        assert_eq!(parse(json!(" 132460 ")).unwrap(), hwnd);
+
+        // Eski `i32` çıktıları negatif olabilir; aynı pointer'a dönmeli
+        assert_eq!(parse(json!(-123)).unwrap().as_raw(), -123);
+        assert_eq!(parse(json!("-123")).unwrap().as_raw(), -123);
+        assert_eq!(parse(json!(-123)).unwrap(), parse(json!("-123")).unwrap());
+
+        assert_eq!(parse(json!(0)).unwrap(), WindowHandle::NULL);
+        assert!(parse(json!("0")).unwrap().is_null());
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn keeps_values_above_i32_max() {
+        for raw in [i32::MAX as isize + 1, 0x0001_0002_056C, isize::MAX] {
+            let hwnd = WindowHandle::from_raw(raw);
+            assert_eq!(hwnd.as_raw(), raw);
+
+            let json = serde_json::to_value(hwnd).unwrap();
+            assert_eq!(json, json!(raw.to_string()));
+            assert_eq!(parse(json).unwrap().as_raw(), raw);
+            assert_eq!(parse(json!(raw as u64)).unwrap().as_raw(), raw);
+            assert_eq!(parse(json!(format!("{:#x}", raw))).unwrap().as_raw(), raw);
+        }
+    }
+
+    #[test]
+    fn rejects_garbage() {
+        for value in [
+            "",
+            "abc",
+            "0x",
+            "0xZZ",
+            "12abc",
+            "1.5",
+            "--1",
+            "99999999999999999999",
+        ] {
+            assert!(value.parse::<WindowHandle>().is_err(), "{:?}", value);
+            assert!(parse(json!(value)).is_err(), "{:?}", value);
+        }
+        assert!(parse(json!(1.5)).is_err());
+        assert!(parse(json!(null)).is_err());
+        assert!(parse(json!(true)).is_err());
+        assert!(parse(json!({"hwnd": 1})).is_err());
+    }
+
+    #[test]
+    fn parses_command_line_arguments() {
+        use clap::Parser;
+
+        #[derive(Parser)]
+        struct Args {
+            #[arg(long)]
+            hwnd: WindowHandle,
+        }
+
+        let parse_args = |value: &str| Args::try_parse_from(["test", "--hwnd", value]);
+        assert_eq!(
+            parse_args("132460").unwrap().hwnd,
+            WindowHandle::from_raw(0x2056C)
+        );
+        assert_eq!(
+            parse_args("0x2056C").unwrap().hwnd,
+            WindowHandle::from_raw(0x2056C)
+        );
+        // Negatif değer clap'te bir flag sanılmasın diye `=` ile verilir
+        let args = Args::try_parse_from(["test", "--hwnd=-123"]).unwrap();
+        assert_eq!(args.hwnd.as_raw(), -123);
+        assert!(parse_args("not-a-handle").is_err());
+    }
+}
@@ -8,22 +8,19 @@ const JSONStream = require('json-stream');
 const execAsync = util.promisify(cp.exec);
 
 import type { Native } from ".";
+import type { MediaEvent, MediaInfo as MediaState } from "./generated";
+import { NativeError, parseCommandOutput } from "./protocol";
 
 
-export interface MediaState {
-  title?: string;
-  artist?: string;
-  album?: string;
-  playback_status: "Playing" | "Paused" | "Stopped" | "Unknown";
-  position?: number;
-  duration?: number;
-  app_name?: string;
-  has_artwork: boolean;
-}
+export type { MediaState };
 
 export class MediaInfo {
   artwork: string | null = $state(null);
-  media = $state<MediaState | null>(null);
+  // Tüm oturumlar, SourceAppUserModelId'lerine göre
+  sessions = $state<Record<string, MediaState>>({});
+  // Windows'un o anki saydığı oturumun app id'si
+  current = $state<string | null>(null);
+  media = $derived<MediaState | null>(this.current ? this.sessions[this.current] ?? null : null);
 
   exePath!: string;
   process: import("child_process").ChildProcessWithoutNullStreams | null = null;
@@ -40,24 +37,42 @@ export class MediaInfo {
     this.stop();
   }
 
-  async skipTrack() {
-    await execAsync(`"${this.exePath}" skip-track`);
+  async skipTrack(app?: string) {
+    return await this.control("skip-track", app);
   }
 
-  async previousTrack() {
-    await execAsync(`"${this.exePath}" previous-track`);
+  async previousTrack(app?: string) {
+    return await this.control("previous-track", app);
   }
 
-  async togglePlayPause() {
-    await execAsync(`"${this.exePath}" toggle-play-pause`);
+  async togglePlayPause(app?: string) {
+    return await this.control("toggle-play-pause", app);
   }
 
-  async pause() {
-    await execAsync(`"${this.exePath}" pause`);
+  async pause(app?: string) {
+    return await this.control("pause", app);
   }
 
-  async resume() {
-    await execAsync(`"${this.exePath}" resume`);
+  async resume(app?: string) {
+    return await this.control("resume", app);
+  }
+
+  // Medya komutunu çalıştırır; app verilmezse o anki oturuma gider. Oturum yoksa
+  // veya uygulama komutu reddederse false döner
+  async control(command: string, app?: string): Promise<boolean> {
+    try {
+      const target = app ? ` --app "${app}"` : "";
+      // Hata durumunda process sıfırdan farklı kodla çıkar, zarf yine stdout'tadır
+      parseCommandOutput(await execAsync(`"${this.exePath}" ${command}${target}`).catch((e) => e));
+      return true;
+    } catch (e) {
+      if (e instanceof NativeError && (e.code === "no_session" || e.code === "failed")) {
+        this.native.api.logger.info("MediaControls", `${command}: ${e.message}`);
+      } else {
+        this.native.api.logger.error("MediaControls", `${command} failed: ${e}`);
+      }
+      return false;
+    }
   }
 
   start() {
@@ -77,10 +92,14 @@ export class MediaInfo {
         jsonStream.write(data);
       });
 
-      jsonStream.on("data", async (data: any) => {
+      jsonStream.on("data", async (data: MediaEvent) => {
         let oldKey = `${this.media?.title}-${this.media?.artist}-${this.media?.album}-${this.media?.app_name}`;
-        let newKey = `${data.title}-${data.artist}-${data.album}-${data.app_name}`;
-        this.media = data as MediaState;
+        if (data.event_type === "sessions_changed") {
+          this.sessions = data.sessions as Record<string, MediaState>;
+        } else {
+          this.current = data.app_name;
+        }
+        let newKey = `${this.media?.title}-${this.media?.artist}-${this.media?.album}-${this.media?.app_name}`;
         try {
           if (oldKey !== newKey) {
             this.artwork = await fs.promises.readFile(path.join(path.dirname(this.exePath), 'current_album_artwork.png'), "base64")
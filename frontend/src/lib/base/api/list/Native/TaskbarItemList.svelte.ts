@@ -8,42 +8,48 @@ const JSONStream = require('json-stream');
 const execAsync = util.promisify(cp.exec);
 
 import type { Native } from "."
+import type { ItemChange, TaskbarGroup, TaskbarItem } from "./generated";
+import { NativeError, parseCommandOutput, unwrapResponse } from "./protocol";
 
-export interface TaskbarItem {
-  title: string
-  process_name: string
-  process_id: number
-  hwnd: number
-  is_visible: boolean
-  is_minimized: boolean
-  is_maximized: boolean
-  class_name: string
-  has_taskbar_button: boolean
-  window_state: string
-  is_pinned: boolean
-  executable_path: string
-  item_type: string
-  is_tray_icon: boolean
-  is_definitely_taskbar: boolean
-  is_definitely_tray: boolean
-  is_system_window: boolean
-  display_location: string
-  is_focused: boolean
-  is_running: boolean
+export type { TaskbarGroup, TaskbarItem };
+
+// `ItemChange.changes` TaskbarItem alanlarının bir alt kümesidir
+export type TaskbarItemChange = Omit<ItemChange, "changes"> & { changes: Partial<TaskbarItem> };
+
+export const NULL_HWND = "0";
+
+// Rust tarafındaki `item_key` ile aynı olmalı
+export function taskbarItemKey(item: TaskbarItem): string {
+  if (item.hwnd !== NULL_HWND) return `hwnd:${item.hwnd}`;
+  if (item.executable_path) return `pinned:${item.executable_path.toLowerCase()}`;
+  return `pinned:${item.process_name.toLowerCase()}`;
 }
 
 export class TaskbarItemList {
   items = $state<TaskbarItem[]>([]);
   focusedItem = $derived(this.items.find(item => item.is_focused) || null);
-  taskbarItems = $derived(this.items.filter(item => item.is_definitely_taskbar && item.title && item.hwnd));
-  taskbarItemsGrouped = $derived(Object.values(Object.groupBy(this.taskbarItems, item => item.process_id))) as TaskbarItem[][];
+  taskbarItems = $derived(this.items.filter(item => item.is_definitely_taskbar && item.title && item.hwnd !== NULL_HWND));
+  groups = $state<TaskbarGroup[]>([]);
+  // Native taraftan gelen uygulama grupları, sadece taskbar pencereleriyle
+  taskbarGroups = $derived(
+    this.groups
+      .map(group => ({ ...group, windows: group.windows.filter(item => item.is_definitely_taskbar && item.title) }))
+      .filter(group => group.windows.length > 0)
+  );
   trayItems = $derived(this.items.filter(item => item.is_definitely_tray));
 
+  // icon_id -> base64 PNG; aynı icon'u paylaşan item'lar için tek istek yapılır
   icons: Record<string, string | null> = $state({});
-  screenshots: Record<number, { at: number, data: string, width: number, height: number }> = $state({});
+  pendingIcons = new Map<string, Promise<string | null>>();
+  screenshots: Record<string, { at: number, data: string, width: number, height: number }> = $state({});
 
   exePath!: string;
   process: import("child_process").ChildProcessWithoutNullStreams | null = null;
+  thumbnailProcess: import("child_process").ChildProcessWithoutNullStreams | null = null;
+
+  // serve modundaki JSON-RPC istekleri, id ile eşleştirilir
+  rpcId = 0;
+  pendingRequests = new Map<number, { resolve: (result: any) => void, reject: (error: Error) => void }>();
 
   checkInterval: NodeJS.Timeout | null = null;
   constructor(public native: Native) {
@@ -55,7 +61,7 @@ export class TaskbarItemList {
     this.checkInterval = setInterval(() => {
       Object.entries(this.screenshots).forEach(([hwnd, data]) => {
         if (Date.now() - data.at > 60000 * 15) {
-          delete this.screenshots[Number(hwnd)];
+          delete this.screenshots[hwnd];
         }
       });
     }, 1000);
@@ -69,26 +75,37 @@ export class TaskbarItemList {
     }
   }
 
-  async getExecutableImage(exePath: string, force: boolean = false): Promise<string | null> {
-    if (this.icons[exePath] && !force) {
-      return this.icons[exePath];
+  async getExecutableImage(exePath: string, force: boolean = false, iconId: string = exePath): Promise<string | null> {
+    if (this.icons[iconId] && !force) {
+      return this.icons[iconId];
     }
 
-    const res = await execAsync(`"${this.exePath}" get-executable-icon --path "${exePath}"`)
-    res.stdout = res.stdout.trim();
-    const json = JSON.parse(res.stdout);
-    if (!json.success) {
-      delete this.icons[exePath];
-      return null;
+    // Aynı icon için devam eden istek varsa onu bekle
+    const pending = this.pendingIcons.get(iconId);
+    if (pending && !force) {
+      return pending;
     }
 
-    this.icons[exePath] = json.icon_base64 as string;
-    return json.icon_base64 as string;
+    const request = this.call("get-executable-icon", { path: exePath }).then((data) => {
+      this.icons[iconId] = data.icon_base64 as string;
+      return data.icon_base64 as string;
+    }).catch(() => {
+      delete this.icons[iconId];
+      return null;
+    }).finally(() => this.pendingIcons.delete(iconId));
+
+    this.pendingIcons.set(iconId, request);
+    return request;
   }
 
-  async getWindowScreenshot(hwnd: number, force: boolean = false): Promise<string | null> {
+  async clearIconCache() {
+    this.icons = {};
+    return await this.call("clear-icon-cache");
+  }
+
+  async getWindowScreenshot(hwnd: string, force: boolean = false): Promise<string | null> {
     // HWND 0 olan itemlar için screenshot alınamaz
-    if (hwnd === 0) {
+    if (hwnd === NULL_HWND) {
       return null;
     }
 
@@ -96,61 +113,168 @@ export class TaskbarItemList {
       return this.screenshots[hwnd].data;
     }
 
-    const res = await execAsync(`"${this.exePath}" get-window-screenshot --hwnd ${hwnd} --size 256x256`);
-    res.stdout = res.stdout.trim();
-    const json = JSON.parse(res.stdout);
-    if (!json.success) {
+    let data: any;
+    try {
+      data = await this.call("get-window-screenshot", { hwnd, size: "256x256" });
+    } catch {
       delete this.screenshots[hwnd];
       return null;
     }
 
-    if (json.width < 50 || json.height < 50) {
+    if (data.width < 50 || data.height < 50) {
       return null;
     }
 
     this.screenshots[hwnd] = {
       at: Date.now(),
-      data: json.screenshot_base64 as string,
-      width: json.width,
-      height: json.height
+      data: data.screenshot_base64 as string,
+      width: data.width,
+      height: data.height
     };
 
-    return json.screenshot_base64 as string;
+    return data.screenshot_base64 as string;
   }
 
   async openStartMenu() {
-    await execAsync(`"${this.exePath}" open-start-menu`);
+    await this.call("open-start-menu");
   }
-  async minimizeWindow(hwnd: number) {
-    await execAsync(`"${this.exePath}" minimize-window --hwnd ${hwnd}`);
+  async minimizeWindow(hwnd: string) {
+    await this.call("minimize-window", { hwnd });
   }
 
-  async maximizeWindow(hwnd: number) {
-    await execAsync(`"${this.exePath}" maximize-window --hwnd ${hwnd}`);
+  async maximizeWindow(hwnd: string) {
+    await this.call("maximize-window", { hwnd });
   }
 
-  async restoreWindow(hwnd: number) {
-    await execAsync(`"${this.exePath}" restore-window --hwnd ${hwnd}`);
+  async restoreWindow(hwnd: string) {
+    await this.call("restore-window", { hwnd });
   }
 
-  async closeWindow(hwnd: number) {
-    await execAsync(`"${this.exePath}" close-window --hwnd ${hwnd}`);
+  async closeWindow(hwnd: string) {
+    await this.call("close-window", { hwnd });
   }
 
-  async focusWindow(hwnd: number) {
-    await execAsync(`"${this.exePath}" focus-window --hwnd ${hwnd}`);
+  async focusWindow(hwnd: string) {
+    await this.call("focus-window", { hwnd });
   }
 
-  async unfocusWindow(hwnd: number) {
-    await execAsync(`"${this.exePath}" unfocus-window --hwnd ${hwnd}`);
+  async unfocusWindow(hwnd: string) {
+    await this.call("unfocus-window", { hwnd });
   }
 
-  async toggleFocusWindow(hwnd: number) {
-    await execAsync(`"${this.exePath}" toggle-focus-window --hwnd ${hwnd}`);
+  async toggleFocusWindow(hwnd: string) {
+    await this.call("toggle-focus-window", { hwnd });
   }
 
   async startExecutable(exePath: string) {
-    await execAsync(`"${this.exePath}" start-executable --path "${exePath}"`);
+    await this.call("start-executable", { path: exePath });
+  }
+
+  async pinExecutable(exePath: string, name?: string, index?: number) {
+    return await this.call("pin-executable", { path: exePath, name, index });
+  }
+
+  async unpinItem(exePath: string) {
+    return await this.call("unpin-item", { path: exePath });
+  }
+
+  async reorderPinned(exePath: string, index: number) {
+    return await this.call("reorder-pinned", { path: exePath, index });
+  }
+
+  // Çalışan serve process'ine JSON-RPC isteği gönderir; process yoksa komutu ayrı bir process'te çalıştırır.
+  // Yanıtın `data`'sını döner, hata durumunda NativeError fırlatır.
+  async call(method: string, params: Record<string, any> = {}): Promise<any> {
+    try {
+      if (!this.process) {
+        const args = Object.entries(params)
+          .filter(([, value]) => value !== undefined)
+          .map(([key, value]) => `--${key.replace(/_/g, "-")} "${value}"`)
+          .join(" ");
+        // Hata durumunda process sıfırdan farklı kodla çıkar, zarf yine stdout'tadır
+        const res = await execAsync(`"${this.exePath}" ${method} ${args}`).catch((e) => e);
+        return parseCommandOutput(res);
+      }
+
+      const id = ++this.rpcId;
+      return await new Promise((resolve, reject) => {
+        this.pendingRequests.set(id, { resolve, reject });
+        this.process!.stdin.write(JSON.stringify({ jsonrpc: "2.0", id, method, params }) + "\n");
+      });
+    } catch (e) {
+      // Kapanmış bir pencereye komut gönderildiyse liste eskimiştir
+      if (e instanceof NativeError && e.code === "invalid_handle") this.resync();
+      throw e;
+    }
+  }
+
+  rejectPendingRequests(reason: string) {
+    this.pendingRequests.forEach(({ reject }) => reject(new Error(reason)));
+    this.pendingRequests.clear();
+  }
+
+  // Hover önizlemeleri için canlı küçük resimler; önceki stream durdurulur
+  startThumbnailStream(hwnds: string[], fps: number = 5) {
+    this.stopThumbnailStream();
+    hwnds = hwnds.filter(hwnd => hwnd !== NULL_HWND);
+    if (hwnds.length === 0) return;
+
+    const jsonStream = new JSONStream();
+    const process = cp.spawn(this.exePath, [
+      "stream-thumbnails",
+      ...hwnds.flatMap(hwnd => ["--hwnd", hwnd]),
+      "--fps", String(fps),
+      "--size", "256x256",
+    ], {
+      cwd: path.dirname(this.exePath),
+    });
+
+    process.stdout.setEncoding("utf-8");
+    process.stdout.on("data", (data: any) => {
+      jsonStream.write(data);
+    });
+
+    jsonStream.on("data", (data: any) => {
+      if (data.action === "frame" && data.width >= 50 && data.height >= 50) {
+        this.screenshots[data.hwnd] = {
+          at: Date.now(),
+          data: data.screenshot_base64 as string,
+          width: data.width,
+          height: data.height
+        };
+      } else if (data.action === "closed") {
+        delete this.screenshots[data.hwnd];
+      }
+    });
+
+    process.once("exit", () => {
+      process.removeAllListeners();
+      if (this.thumbnailProcess === process) this.thumbnailProcess = null;
+    });
+
+    this.thumbnailProcess = process;
+  }
+
+  stopThumbnailStream() {
+    if (this.thumbnailProcess) {
+      // stdin'i kapatmak stream'i temiz bir şekilde durdurur
+      this.thumbnailProcess.stdin.end();
+      this.thumbnailProcess = null;
+    }
+  }
+
+  prefetchItem(item: TaskbarItem) {
+    if (item.is_definitely_taskbar || item.is_definitely_tray || item.is_focused) {
+      this.getExecutableImage(item.executable_path, false, item.icon_id || item.executable_path);
+    }
+    // Sadece HWND'si 0'dan farklı olan taskbar itemları için screenshot al
+    if (item.is_definitely_taskbar && item.hwnd !== NULL_HWND) {
+      this.getWindowScreenshot(item.hwnd);
+    }
+  }
+
+  resync() {
+    this.process?.stdin.write("resync\n");
   }
 
   start() {
@@ -159,7 +283,7 @@ export class TaskbarItemList {
     try {
       const jsonStream = new JSONStream();
 
-      const process = cp.spawn(this.exePath, {
+      const process = cp.spawn(this.exePath, ["serve", "--diff", "--grouped"], {
         cwd: path.dirname(this.exePath),
       });
 
@@ -171,18 +295,53 @@ export class TaskbarItemList {
       });
 
       jsonStream.on("data", (data: any) => {
-        this.native.api.events.emit("TastkbarItemMessage", data);
-        if (data.action === "list") {
-          this.items = data.items as TaskbarItem[];
-          this.items.forEach((item) => {
-            if (item.is_definitely_taskbar || item.is_definitely_tray || item.is_focused) {
-              this.getExecutableImage(item.executable_path);
-            }
-            // Sadece HWND'si 0'dan farklı olan taskbar itemları için screenshot al
-            if (item.is_definitely_taskbar && item.hwnd !== 0) {
-              this.getWindowScreenshot(item.hwnd);
+        // JSON-RPC yanıtları monitor event'leriyle aynı stream'den gelir
+        if (data.jsonrpc) {
+          const pending = this.pendingRequests.get(data.id);
+          if (!pending) return;
+          this.pendingRequests.delete(data.id);
+          if (data.error) pending.reject(new Error(data.error.message));
+          else {
+            try {
+              pending.resolve(unwrapResponse(data.result));
+            } catch (e) {
+              pending.reject(e as Error);
             }
-          });
+          }
+          return;
+        }
+
+        this.native.api.events.emit("TastkbarItemMessage", data);
+        switch (data.action) {
+          case "list": {
+            this.items = data.items as TaskbarItem[];
+            this.items.forEach((item) => this.prefetchItem(item));
+            break;
+          }
+          case "groups": {
+            this.groups = data.groups as TaskbarGroup[];
+            break;
+          }
+          case "added": {
+            const added = data.items as TaskbarItem[];
+            this.items.push(...added);
+            added.forEach((item) => this.prefetchItem(item));
+            break;
+          }
+          case "removed": {
+            const removed = new Set(data.removed as string[]);
+            this.items = this.items.filter(item => !removed.has(taskbarItemKey(item)));
+            break;
+          }
+          case "updated": {
+            (data.changes as TaskbarItemChange[]).forEach(({ key, changes }) => {
+              const item = this.items.find(item => taskbarItemKey(item) === key);
+              if (!item) return;
+              Object.assign(item, changes);
+              this.prefetchItem(item);
+            });
+            break;
+          }
         }
       }); process.once("error", (err) => {
         this.native.api.logger.error("TastkbarItemMessage", `Listener error: ${err}`);
@@ -191,6 +350,7 @@ export class TaskbarItemList {
       process.once("exit", () => {
         process?.removeAllListeners();
         this.process = null;
+        this.rejectPendingRequests("TaskbarItemList process exited");
       });
 
       this.process = process;
@@ -200,11 +360,13 @@ export class TaskbarItemList {
   }
 
   stop() {
+    this.stopThumbnailStream();
     if (this.process) {
       this.native.api.logger.info("TaskbarItemList", "Stopping TaskbarItemList process");
       this.process.removeAllListeners();
       this.process.kill();
       this.process = null;
     }
+    this.rejectPendingRequests("TaskbarItemList process stopped");
   }
 }
\ No newline at end of file
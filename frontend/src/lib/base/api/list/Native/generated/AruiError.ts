@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ErrorCode } from "./ErrorCode";
+
+/**
+ * Ortak hata tipi. Altta yatan Win32 hata kodu ya da HRESULT varsa
+ * kaybolmaması için yanında taşınır.
+ */
+export type AruiError = { code: ErrorCode, message: string, win32?: number, 
+/**
+ * JSON'da "0x80070005" şeklinde yazılır
+ */
+hresult?: string, };
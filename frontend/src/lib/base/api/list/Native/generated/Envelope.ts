@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AruiError } from "./AruiError";
+
+/**
+ * Komut yanıtlarının ortak zarfı: başarıda `{"ok": true, "data": ...}`,
+ * hatada `{"ok": false, "error": {...}}`
+ */
+export type Envelope<T> = { protocol_version: number, ok: boolean, data?: T, error?: AruiError, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Renderer'ın mesaj metnine bakmadan tepki verebilmesi için makine
+ * tarafından okunabilir hata kodları
+ */
+export type ErrorCode = "invalid_argument" | "invalid_handle" | "not_found" | "access_denied" | "unsupported" | "no_session" | "io" | "failed" | "internal";
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Subsystem } from "./Subsystem";
+
+/**
+ * arui-native'in stdout/stdin satırı: bir alt sistemin mesajı, hangi
+ * alt sisteme ait olduğu bilgisiyle sarılmış halde
+ */
+export type HostMessage<T> = { protocol_version: number, subsystem: Subsystem, message: T, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { JsonValue } from "./serde_json/JsonValue";
+
+/**
+ * Bir item'ın değişen alanları ve yeni değerleri
+ */
+export type ItemChange = { key: string, changes: { [key in string]?: JsonValue }, };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Her hedef için hangi kuralın karar verdiği
+ */
+export type MatchedRules = { taskbar: string | null, tray: string | null, system: string | null, };
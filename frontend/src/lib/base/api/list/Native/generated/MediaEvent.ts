@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { MediaInfo } from "./MediaInfo";
+
+/**
+ * win-media-info'nun stream mesajları. Türü `event_type` alanındadır:
+ * `{"event_type": "current_session_changed", "app_name": "Spotify.exe", ...}`
+ */
+export type MediaEvent = { protocol_version: number, } & ({ "event_type": "sessions_changed", sessions: { [key in string]?: MediaInfo }, } | { "event_type": "current_session_changed", app_name: string | null, });
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PlaybackStatus } from "./PlaybackStatus";
+
+/**
+ * win-media-info'nun raporladığı bir medya oturumu
+ */
+export type MediaInfo = { protocol_version: number, title: string | null, artist: string | null, album: string | null, playback_status: PlaybackStatus, position: number | null, duration: number | null, app_name: string | null, has_artwork: boolean, };
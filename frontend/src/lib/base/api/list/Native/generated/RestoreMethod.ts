@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Taskbar'ın hangi yolla geri getirildiği
+ */
+export type RestoreMethod = "restored_in_place" | "restored_via_explorer_restart";
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * arui-native host'unda aynı process içinde çalışan alt sistemler
+ */
+export type Subsystem = "host" | "taskbar_manager" | "taskbar_items" | "media_info";
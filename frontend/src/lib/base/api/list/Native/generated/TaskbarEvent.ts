@@ -0,0 +1,13 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AruiError } from "./AruiError";
+import type { MousePosition } from "./MousePosition";
+import type { RestoreMethod } from "./RestoreMethod";
+import type { TaskbarKind } from "./TaskbarKind";
+import type { WindowHandle } from "./WindowHandle";
+
+/**
+ * win-taskbar-manager'ın taskbar ve mouse event'leri. Event'in türü
+ * `event_type` alanındadır, türe özgü alanlar aynı nesneye yazılır:
+ * `{"event_type": "auto_hide", "which": "secondary", "hwnd": "1234", ...}`
+ */
+export type TaskbarEvent = { protocol_version: number, timestamp: string, mouse_position: MousePosition, } & ({ "event_type": "taskbar_hidden" } | { "event_type": "taskbar_shown" } | { "event_type": "taskbar_restored", method: RestoreMethod, } | { "event_type": "taskbar_recovered", method: RestoreMethod, } | { "event_type": "auto_hide", which: TaskbarKind, hwnd: WindowHandle, } | { "event_type": "hot_zone_entered", monitor: string, zone: string, } | { "event_type": "hot_zone_left", monitor: string, zone: string, } | { "event_type": "appbar_position_changed" } | { "event_type": "appbar_fullscreen_app", active: boolean, } | { "event_type": "fullscreen_entered", hwnd: WindowHandle, monitor: string, } | { "event_type": "fullscreen_exited", hwnd: WindowHandle, monitor: string, } | { "event_type": "explorer_restarted", taskbar: WindowHandle, } | { "event_type": "error", error: AruiError, });
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TaskbarItem } from "./TaskbarItem";
+
+/**
+ * Aynı uygulamaya ait pencereler (taskbar'daki birleşik buton)
+ */
+export type TaskbarGroup = { app_id: string, name: string, executable_path: string, pinned: boolean, windows: Array<TaskbarItem>, };
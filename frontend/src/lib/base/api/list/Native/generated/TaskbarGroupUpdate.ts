@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TaskbarGroup } from "./TaskbarGroup";
+
+export type TaskbarGroupUpdate = { protocol_version: number, action: string, groups: Array<TaskbarGroup>, timestamp: number, };
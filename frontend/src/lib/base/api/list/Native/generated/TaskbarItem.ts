@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { MatchedRules } from "./MatchedRules";
+import type { WindowHandle } from "./WindowHandle";
+
+/**
+ * win-taskbar-item-list'in raporladığı tek bir pencere veya pinned item
+ */
+export type TaskbarItem = { title: string, process_name: string, process_id: number, hwnd: WindowHandle, is_visible: boolean, is_minimized: boolean, is_maximized: boolean, class_name: string, has_taskbar_button: boolean, window_state: string, is_pinned: boolean, executable_path: string, item_type: string, is_tray_icon: boolean, is_focused: boolean, is_running: boolean, is_definitely_taskbar: boolean, is_definitely_tray: boolean, is_system_window: boolean, display_location: string, matched_rules: MatchedRules, icon_id: string, };
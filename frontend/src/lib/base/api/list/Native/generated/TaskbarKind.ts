@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Ana taskbar (`Shell_TrayWnd`) ya da diğer monitörlerdekiler
+ */
+export type TaskbarKind = "main" | "secondary";
@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ItemChange } from "./ItemChange";
+import type { TaskbarItem } from "./TaskbarItem";
+
+/**
+ * Monitor modunun item listesi mesajı: tam liste ya da diff modunda
+ * eklenen/kaldırılan/güncellenen item'lar
+ */
+export type TaskbarUpdate = { protocol_version: number, action: string, items: Array<TaskbarItem>, removed?: Array<string>, changes?: Array<ItemChange>, timestamp: number, };
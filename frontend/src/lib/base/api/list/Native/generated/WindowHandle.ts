@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Kayıpsız pencere handle'ı. HWND pointer boyutundadır; `i32`'ye
+ * çevirmek 64-bit handle'ları keser ve komutların yanlış pencereye
+ * gitmesine yol açar.
+ *
+ * JSON'da string olarak yazılır (JavaScript'in 2^53 sınırına takılmaz),
+ * okunurken hem string hem de sayı kabul edilir.
+ */
+export type WindowHandle = string;
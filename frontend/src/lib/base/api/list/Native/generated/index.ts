@@ -0,0 +1,24 @@
+// This file was generated by arui-protocol-codegen. Do not edit.
+
+export const PROTOCOL_VERSION = 3;
+
+export type * from "./AruiError";
+export type * from "./Envelope";
+export type * from "./ErrorCode";
+export type * from "./HostMessage";
+export type * from "./ItemChange";
+export type * from "./MatchedRules";
+export type * from "./MediaEvent";
+export type * from "./MediaInfo";
+export type * from "./MousePosition";
+export type * from "./PlaybackStatus";
+export type * from "./RestoreMethod";
+export type * from "./Subsystem";
+export type * from "./TaskbarEvent";
+export type * from "./TaskbarGroup";
+export type * from "./TaskbarGroupUpdate";
+export type * from "./TaskbarItem";
+export type * from "./TaskbarKind";
+export type * from "./TaskbarUpdate";
+export type * from "./WindowHandle";
+export type * from "./serde_json/JsonValue";
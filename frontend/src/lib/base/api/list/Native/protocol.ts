@@ -0,0 +1,38 @@
+/* eslint-disable @typescript-eslint/no-explicit-any */
+// Native yardımcıların ortak yanıt zarfı ve hata kodları; tipler arui-protocol-rust'tan üretilir
+import type { AruiError, ErrorCode } from "./generated";
+
+export type NativeErrorCode = ErrorCode;
+export type NativeErrorInfo = AruiError;
+
+export type NativeResponse<T = any> =
+  | { protocol_version?: number, ok: true, data: T }
+  | { protocol_version?: number, ok: false, error: NativeErrorInfo };
+
+export class NativeError extends Error {
+  code: NativeErrorCode;
+  win32?: number;
+  hresult?: string;
+
+  constructor(info: NativeErrorInfo) {
+    super(info.message);
+    this.name = "NativeError";
+    this.code = info.code;
+    this.win32 = info.win32;
+    this.hresult = info.hresult;
+  }
+}
+
+// Başarılıysa `data`'yı döner, değilse NativeError fırlatır
+export function unwrapResponse<T = any>(response: NativeResponse<T>): T {
+  if (!response.ok) throw new NativeError(response.error);
+  return response.data;
+}
+
+// Tek seferlik komutların çıktısını okur. Hata durumunda process sıfırdan
+// farklı bir kodla çıkar ama zarf yine stdout'a yazılmıştır.
+export function parseCommandOutput<T = any>(output: { stdout?: string } | undefined): T {
+  const stdout = output?.stdout?.trim();
+  if (!stdout) throw new NativeError({ code: "internal", message: "Native command produced no output" });
+  return unwrapResponse<T>(JSON.parse(stdout));
+}
@@ -112,6 +112,10 @@ async function createApp() {
       });
 
       jsonStream.on("data", (data) => {
+        // Startup failures arrive as an `{ ok: false, error }` envelope
+        if (data.ok === false) {
+          console.error(`Taskbar Manager failed: [${data.error.code}] ${data.error.message}`);
+        }
         broadcastMessage("TaskbarManagerMessage", data);
       });
 
@@ -119,7 +123,8 @@ async function createApp() {
         console.error(`Taskbar Manager error: ${err}`);
       });
 
-      process.once("exit", () => {
+      process.once("exit", (code) => {
+        if (code) console.error(`Taskbar Manager exited with code ${code}`);
         process?.removeAllListeners();
       });
 
@@ -0,0 +1,475 @@
+//! Reports every Windows media session and controls their playback.
+//! Runs on its own as the `win-media-info` binary or as the `media_info`
+//! subsystem inside arui-native.
+
+#[cfg(windows)]
+mod sessions;
+
+use arui_protocol::rpc::{self, RpcRequest};
+use arui_protocol::{
+    AruiError, Envelope, ErrorCode, MediaEvent, MediaEventKind, MediaInfo, Output,
+};
+use clap::{Parser, Subcommand};
+use serde::Deserialize;
+#[cfg(windows)]
+use sessions::{execute_media_control, get_media_sessions};
+use std::collections::BTreeMap;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time;
+
+#[derive(Parser)]
+#[command(name = "win-media-info")]
+#[command(about = "Windows Media Information and Control Tool")]
+pub struct Cli {
+    #[command(subcommand)]
+    command: Option<Commands>,
+}
+
+/// Inside arui-native every command is also a JSON-RPC method
+/// (e.g. `{"id": 1, "method": "skip-track", "params": {"app": "Spotify.exe"}}`).
+/// `app` is the target session's `SourceAppUserModelId`; without it the
+/// command goes to the session Windows considers current.
+#[derive(Subcommand, Deserialize, Debug)]
+#[serde(tag = "method", content = "params", rename_all = "kebab-case")]
+enum Commands {
+    /// Skip to next track
+    SkipTrack {
+        /// Target session's app id (defaults to the current session)
+        #[arg(long)]
+        #[serde(default)]
+        app: Option<String>,
+    },
+    /// Go to previous track
+    PreviousTrack {
+        /// Target session's app id (defaults to the current session)
+        #[arg(long)]
+        #[serde(default)]
+        app: Option<String>,
+    },
+    /// Toggle play/pause
+    TogglePlayPause {
+        /// Target session's app id (defaults to the current session)
+        #[arg(long)]
+        #[serde(default)]
+        app: Option<String>,
+    },
+    /// Pause playback
+    Pause {
+        /// Target session's app id (defaults to the current session)
+        #[arg(long)]
+        #[serde(default)]
+        app: Option<String>,
+    },
+    /// Resume playback
+    Resume {
+        /// Target session's app id (defaults to the current session)
+        #[arg(long)]
+        #[serde(default)]
+        app: Option<String>,
+    },
+    /// Monitor media info (default behavior)
+    #[serde(skip)]
+    Monitor,
+}
+
+// Only the WinRT sessions module reads these
+#[cfg_attr(not(windows), allow(dead_code))]
+impl Commands {
+    fn name(&self) -> &'static str {
+        match self {
+            Commands::SkipTrack { .. } => "SkipTrack",
+            Commands::PreviousTrack { .. } => "PreviousTrack",
+            Commands::TogglePlayPause { .. } => "TogglePlayPause",
+            Commands::Pause { .. } => "Pause",
+            Commands::Resume { .. } => "Resume",
+            Commands::Monitor => "Monitor",
+        }
+    }
+
+    fn app(&self) -> Option<&str> {
+        match self {
+            Commands::SkipTrack { app }
+            | Commands::PreviousTrack { app }
+            | Commands::TogglePlayPause { app }
+            | Commands::Pause { app }
+            | Commands::Resume { app } => app.as_deref(),
+            Commands::Monitor => None,
+        }
+    }
+}
+
+/// Every media session keyed by its `SourceAppUserModelId`, and the id of
+/// the one Windows considers current
+struct MediaSessions {
+    current: Option<String>,
+    sessions: BTreeMap<String, MediaInfo>,
+}
+
+/// Media sessions are only reachable through WinRT
+#[cfg(not(windows))]
+fn get_media_sessions() -> arui_protocol::Result<MediaSessions> {
+    Err(unsupported())
+}
+
+#[cfg(not(windows))]
+fn execute_media_control(_command: &Commands) -> arui_protocol::Result<serde_json::Value> {
+    Err(unsupported())
+}
+
+#[cfg(not(windows))]
+fn unsupported() -> AruiError {
+    AruiError::new(
+        ErrorCode::Unsupported,
+        "Media sessions are only available on Windows",
+    )
+}
+
+/// Runs a media command on the blocking pool
+async fn run_media_control(command: Commands) -> arui_protocol::Result<serde_json::Value> {
+    tokio::task::spawn_blocking(move || execute_media_control(&command))
+        .await
+        .unwrap_or_else(|e| Err(AruiError::new(ErrorCode::Internal, e.to_string())))
+}
+
+/// Runs one JSON-RPC request line and returns the response
+async fn handle_request(line: &str) -> serde_json::Value {
+    let request = match RpcRequest::parse(line) {
+        Ok(request) => request,
+        Err(error) => return error,
+    };
+    match request.command::<Commands>() {
+        Ok(command) => {
+            let result = run_media_control(command).await;
+            rpc::response(request.id, Envelope::from(result).to_value())
+        }
+        Err(error) => error,
+    }
+}
+
+/// Entry point of the standalone binary
+pub async fn run(cli: Cli) {
+    // If a command is provided, execute it and exit with its error code
+    if let Some(command) = cli
+        .command
+        .filter(|command| !matches!(command, Commands::Monitor))
+    {
+        let result = run_media_control(command).await;
+        std::process::exit(Envelope::from(result).print());
+    }
+
+    // Default behavior: monitor media info. The standalone binary takes no
+    // requests on stdin, so the input channel starts out closed.
+    let (_, input) = mpsc::unbounded_channel();
+    run_monitor(Output::stdout(), input).await;
+}
+
+/// Whether a session changed enough to be reported again
+fn has_changed(last: &MediaInfo, media_info: &MediaInfo) -> bool {
+    last.title != media_info.title ||
+    last.artist != media_info.artist ||
+    last.album != media_info.album ||
+    last.playback_status != media_info.playback_status ||
+    last.app_name != media_info.app_name ||
+    last.has_artwork != media_info.has_artwork ||
+    // For position, only report a significant change (more than 1 second)
+    match (last.position, media_info.position) {
+        (Some(last_pos), Some(curr_pos)) => (last_pos as i64 - curr_pos as i64).abs() > 1000,
+        (None, Some(_)) | (Some(_), None) => true,
+        _ => false,
+    }
+}
+
+/// What the monitor last reported, used to decide which events a new poll
+/// of the sessions produces
+#[derive(Default)]
+struct SessionTracker {
+    sessions: Option<BTreeMap<String, MediaInfo>>,
+    current: Option<String>,
+}
+
+impl SessionTracker {
+    /// Records a poll and returns the events to emit, in order. `None` means
+    /// the sessions could not be read.
+    fn update(&mut self, media_sessions: Option<MediaSessions>) -> Vec<MediaEventKind> {
+        let mut events = Vec::new();
+        match media_sessions {
+            Some(MediaSessions { current, sessions }) => {
+                // Only report if a session has changed, opened or closed, or if this is the first time
+                let should_report = match &self.sessions {
+                    Some(last) => {
+                        last.len() != sessions.len()
+                            || sessions
+                                .iter()
+                                .any(|(app_id, media_info)| match last.get(app_id) {
+                                    Some(last) => has_changed(last, media_info),
+                                    None => true,
+                                })
+                    }
+                    None => true,
+                };
+
+                if should_report {
+                    events.push(MediaEventKind::SessionsChanged {
+                        sessions: sessions.clone(),
+                    });
+                    self.sessions = Some(sessions);
+                }
+
+                // Reported after the sessions so the new current one is already known
+                if current != self.current {
+                    events.push(MediaEventKind::CurrentSessionChanged {
+                        app_name: current.clone(),
+                    });
+                    self.current = current;
+                }
+            }
+            None => {
+                // If we can't get the sessions, only report if we previously had valid info
+                if self.sessions.take().is_some() {
+                    events.push(MediaEventKind::SessionsChanged {
+                        sessions: BTreeMap::new(),
+                    });
+                }
+                if self.current.take().is_some() {
+                    events.push(MediaEventKind::CurrentSessionChanged { app_name: None });
+                }
+            }
+        }
+        events
+    }
+}
+
+/// Writes every session to `out` whenever one of them changes, and the
+/// current session's app id whenever Windows switches to another one. Lines
+/// received on `input` are run as JSON-RPC media commands and answered on `out`.
+pub async fn run_monitor(out: Output, mut input: mpsc::UnboundedReceiver<String>) {
+    let mut tracker = SessionTracker::default();
+    let mut input_open = true;
+
+    loop {
+        let media_sessions = tokio::task::spawn_blocking(get_media_sessions).await;
+        for kind in tracker.update(media_sessions.ok().and_then(Result::ok)) {
+            out.emit(&MediaEvent::new(kind));
+        }
+
+        // Wait 500ms before checking again, answering commands in the meantime
+        let tick = time::sleep(Duration::from_millis(500));
+        tokio::pin!(tick);
+
+        loop {
+            tokio::select! {
+                _ = &mut tick => break,
+                line = input.recv(), if input_open => match line {
+                    Some(line) if !line.trim().is_empty() => out.emit(&handle_request(&line).await),
+                    Some(_) => {}
+                    None => input_open = false,
+                },
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arui_protocol::PlaybackStatus;
+
+    fn track(title: &str, position: u64) -> MediaInfo {
+        MediaInfo {
+            title: Some(title.to_string()),
+            playback_status: PlaybackStatus::Playing,
+            position: Some(position),
+            ..Default::default()
+        }
+    }
+
+    fn poll(current: Option<&str>, sessions: &[&(&str, MediaInfo)]) -> Option<MediaSessions> {
+        Some(MediaSessions {
+            current: current.map(str::to_string),
+            sessions: sessions
+                .iter()
+                .map(|(app_id, media_info)| (app_id.to_string(), media_info.clone()))
+                .collect(),
+        })
+    }
+
+    fn sessions_changed(sessions: &[&(&str, MediaInfo)]) -> MediaEventKind {
+        MediaEventKind::SessionsChanged {
+            sessions: sessions
+                .iter()
+                .map(|(app_id, media_info)| (app_id.to_string(), media_info.clone()))
+                .collect(),
+        }
+    }
+
+    fn current_changed(app_name: Option<&str>) -> MediaEventKind {
+        MediaEventKind::CurrentSessionChanged {
+            app_name: app_name.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn metadata_changes_are_reported() {
+        let last = track("Song", 0);
+        assert!(!has_changed(&last, &last.clone()));
+        assert!(has_changed(&last, &track("Other song", 0)));
+        assert!(has_changed(
+            &last,
+            &MediaInfo {
+                playback_status: PlaybackStatus::Paused,
+                ..last.clone()
+            }
+        ));
+        assert!(has_changed(
+            &last,
+            &MediaInfo {
+                has_artwork: true,
+                ..last.clone()
+            }
+        ));
+    }
+
+    #[test]
+    fn only_position_jumps_over_a_second_are_reported() {
+        let last = track("Song", 10_000);
+        assert!(!has_changed(&last, &track("Song", 11_000)));
+        assert!(!has_changed(&last, &track("Song", 9_000)));
+        assert!(has_changed(&last, &track("Song", 11_001)));
+        assert!(has_changed(&last, &track("Song", 8_999)));
+        assert!(has_changed(
+            &last,
+            &MediaInfo {
+                position: None,
+                ..last.clone()
+            }
+        ));
+    }
+
+    #[test]
+    fn first_poll_reports_sessions_and_current() {
+        let mut tracker = SessionTracker::default();
+        let spotify = ("Spotify.exe", track("Song", 0));
+
+        assert_eq!(
+            tracker.update(poll(Some("Spotify.exe"), &[&spotify])),
+            vec![
+                sessions_changed(&[&spotify]),
+                current_changed(Some("Spotify.exe"))
+            ]
+        );
+        assert_eq!(
+            tracker.update(poll(Some("Spotify.exe"), &[&spotify])),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn empty_first_poll_reports_no_sessions() {
+        let mut tracker = SessionTracker::default();
+        assert_eq!(tracker.update(poll(None, &[])), vec![sessions_changed(&[])]);
+        assert_eq!(tracker.update(poll(None, &[])), vec![]);
+    }
+
+    #[test]
+    fn opened_changed_and_closed_sessions_are_reported() {
+        let mut tracker = SessionTracker::default();
+        let spotify = ("Spotify.exe", track("Song", 0));
+        let chrome = ("Chrome", track("Video", 0));
+        tracker.update(poll(Some("Spotify.exe"), &[&spotify]));
+
+        // Opened
+        assert_eq!(
+            tracker.update(poll(Some("Spotify.exe"), &[&spotify, &chrome])),
+            vec![sessions_changed(&[&spotify, &chrome])]
+        );
+
+        // Changed
+        let next_song = ("Spotify.exe", track("Next song", 0));
+        assert_eq!(
+            tracker.update(poll(Some("Spotify.exe"), &[&next_song, &chrome])),
+            vec![sessions_changed(&[&next_song, &chrome])]
+        );
+
+        // A small position change is not reported
+        let playing = ("Spotify.exe", track("Next song", 500));
+        assert_eq!(
+            tracker.update(poll(Some("Spotify.exe"), &[&playing, &chrome])),
+            vec![]
+        );
+
+        // Closed
+        assert_eq!(
+            tracker.update(poll(Some("Spotify.exe"), &[&next_song])),
+            vec![sessions_changed(&[&next_song])]
+        );
+    }
+
+    #[test]
+    fn replaced_session_is_reported() {
+        let mut tracker = SessionTracker::default();
+        let spotify = ("Spotify.exe", track("Song", 0));
+        let chrome = ("Chrome", track("Song", 0));
+        tracker.update(poll(None, &[&spotify]));
+
+        // Same count, different app
+        assert_eq!(
+            tracker.update(poll(None, &[&chrome])),
+            vec![sessions_changed(&[&chrome])]
+        );
+    }
+
+    #[test]
+    fn current_session_switch_is_reported_after_the_sessions() {
+        let mut tracker = SessionTracker::default();
+        let spotify = ("Spotify.exe", track("Song", 0));
+        let chrome = ("Chrome", track("Video", 0));
+        tracker.update(poll(Some("Spotify.exe"), &[&spotify]));
+
+        assert_eq!(
+            tracker.update(poll(Some("Chrome"), &[&spotify, &chrome])),
+            vec![
+                sessions_changed(&[&spotify, &chrome]),
+                current_changed(Some("Chrome"))
+            ]
+        );
+
+        // A switch without any session change is reported on its own
+        assert_eq!(
+            tracker.update(poll(Some("Spotify.exe"), &[&spotify, &chrome])),
+            vec![current_changed(Some("Spotify.exe"))]
+        );
+        assert_eq!(
+            tracker.update(poll(None, &[&spotify, &chrome])),
+            vec![current_changed(None)]
+        );
+    }
+
+    #[test]
+    fn failed_poll_clears_what_was_reported() {
+        let mut tracker = SessionTracker::default();
+        let spotify = ("Spotify.exe", track("Song", 0));
+        tracker.update(poll(Some("Spotify.exe"), &[&spotify]));
+
+        assert_eq!(
+            tracker.update(None),
+            vec![sessions_changed(&[]), current_changed(None)]
+        );
+        assert_eq!(tracker.update(None), vec![]);
+
+        // Recovering reports everything again
+        assert_eq!(
+            tracker.update(poll(Some("Spotify.exe"), &[&spotify])),
+            vec![
+                sessions_changed(&[&spotify]),
+                current_changed(Some("Spotify.exe"))
+            ]
+        );
+    }
+
+    #[test]
+    fn failed_first_poll_reports_nothing() {
+        let mut tracker = SessionTracker::default();
+        assert_eq!(tracker.update(None), vec![]);
+    }
+}
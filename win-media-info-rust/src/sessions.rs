@@ -0,0 +1,215 @@
+//! Media sessions through WinRT's `GlobalSystemMediaTransportControlsSessionManager`.
+
+use crate::{Commands, MediaSessions};
+use arui_protocol::{AruiError, ErrorCode, MediaInfo, PlaybackStatus};
+use std::collections::BTreeMap;
+use std::fs;
+use windows::{core::*, Media::Control::*, Storage::Streams::*, Win32::Foundation::*};
+
+// WinRT operations are waited on with `get()`, which blocks the calling
+// thread; the async callers in the crate root run these on tokio's blocking pool.
+
+fn save_album_artwork(thumbnail: &IRandomAccessStreamReference) -> Result<()> {
+    let stream = thumbnail.OpenReadAsync()?.get()?;
+    let size = stream.Size()? as usize;
+
+    if size == 0 {
+        return Err(windows::core::Error::from_hresult(E_FAIL));
+    }
+
+    let buffer = Buffer::Create(size as u32)?;
+    let bytes_read = stream
+        .ReadAsync(&buffer, size as u32, InputStreamOptions::None)?
+        .get()?;
+
+    if bytes_read.Length()? == 0 {
+        return Err(windows::core::Error::from_hresult(E_FAIL));
+    }
+
+    // Get current directory path
+    let current_dir = std::env::current_dir().map_err(windows::core::Error::from)?;
+    let artwork_path = current_dir.join("current_album_artwork.png");
+
+    // Convert buffer to bytes and save
+    let data_reader = DataReader::FromBuffer(&bytes_read)?;
+    let mut bytes = vec![0u8; bytes_read.Length()? as usize];
+    data_reader.ReadBytes(&mut bytes)?;
+
+    fs::write(&artwork_path, &bytes).map_err(windows::core::Error::from)?;
+
+    Ok(())
+}
+
+fn app_id(session: &GlobalSystemMediaTransportControlsSession) -> Option<String> {
+    let app_id = session.SourceAppUserModelId().ok()?;
+    if app_id.is_empty() {
+        return None;
+    }
+    Some(app_id.to_string())
+}
+
+pub(crate) fn get_media_sessions() -> Result<MediaSessions> {
+    let session_manager =
+        GlobalSystemMediaTransportControlsSessionManager::RequestAsync()?.get()?;
+
+    let current = session_manager
+        .GetCurrentSession()
+        .ok()
+        .and_then(|session| app_id(&session));
+
+    let mut sessions = BTreeMap::new();
+    for session in session_manager.GetSessions()? {
+        let Some(app_id) = app_id(&session) else {
+            continue;
+        };
+        // An app may own several sessions; the first one stands for the app
+        if sessions.contains_key(&app_id) {
+            continue;
+        }
+        // The artwork goes to a single file, so only the current session's is saved
+        let save_artwork = current.as_deref() == Some(app_id.as_str());
+        // A session that closes while being read is picked up as gone next time
+        if let Ok(media_info) = get_media_info(&session, save_artwork) {
+            sessions.insert(app_id, media_info);
+        }
+    }
+
+    Ok(MediaSessions { current, sessions })
+}
+
+fn get_media_info(
+    session: &GlobalSystemMediaTransportControlsSession,
+    save_artwork: bool,
+) -> Result<MediaInfo> {
+    let mut media_info = MediaInfo::default();
+
+    // Get playback info
+    if let Ok(playback_info) = session.GetPlaybackInfo() {
+        let status = playback_info.PlaybackStatus()?;
+        media_info.playback_status = match status {
+            GlobalSystemMediaTransportControlsSessionPlaybackStatus::Playing => {
+                PlaybackStatus::Playing
+            }
+            GlobalSystemMediaTransportControlsSessionPlaybackStatus::Paused => {
+                PlaybackStatus::Paused
+            }
+            GlobalSystemMediaTransportControlsSessionPlaybackStatus::Stopped => {
+                PlaybackStatus::Stopped
+            }
+            _ => PlaybackStatus::Unknown,
+        };
+    }
+
+    // Get timeline properties
+    if let Ok(timeline_props) = session.GetTimelineProperties() {
+        if let Ok(position) = timeline_props.Position() {
+            media_info.position = Some(position.Duration as u64 / 10_000); // Convert to milliseconds
+        }
+        if let Ok(duration) = timeline_props.EndTime() {
+            media_info.duration = Some(duration.Duration as u64 / 10_000); // Convert to milliseconds
+        }
+    }
+
+    // Get media properties
+    if let Ok(media_properties) = session.TryGetMediaPropertiesAsync()?.get() {
+        if let Ok(title) = media_properties.Title() {
+            if !title.is_empty() {
+                media_info.title = Some(title.to_string());
+            }
+        }
+        if let Ok(artist) = media_properties.Artist() {
+            if !artist.is_empty() {
+                media_info.artist = Some(artist.to_string());
+            }
+        }
+        if let Ok(album) = media_properties.AlbumTitle() {
+            if !album.is_empty() {
+                media_info.album = Some(album.to_string());
+            }
+        }
+
+        // Save album artwork
+        if save_artwork {
+            if let Ok(thumbnail) = media_properties.Thumbnail() {
+                if save_album_artwork(&thumbnail).is_ok() {
+                    media_info.has_artwork = true;
+                }
+            }
+        }
+    }
+
+    // Get source app info
+    if let Ok(source_app_info) = session.SourceAppUserModelId() {
+        if !source_app_info.is_empty() {
+            media_info.app_name = Some(source_app_info.to_string());
+        }
+    }
+
+    Ok(media_info)
+}
+
+/// Attach the HRESULT of a WinRT error to one of our error codes
+fn winrt_error(code: ErrorCode, message: &str, error: &windows::core::Error) -> AruiError {
+    AruiError::new(code, format!("{}: {}", message, error.message())).with_hresult(error.code().0)
+}
+
+pub(crate) fn execute_media_control(
+    command: &Commands,
+) -> arui_protocol::Result<serde_json::Value> {
+    let session_manager = match GlobalSystemMediaTransportControlsSessionManager::RequestAsync() {
+        Ok(operation) => operation.get(),
+        Err(e) => Err(e),
+    }
+    .map_err(|e| {
+        let message = format!("Could not reach the media session manager: {}", e.message());
+        AruiError::hresult(e.code().0, message)
+    })?;
+
+    let session = match command.app() {
+        Some(app) => session_manager
+            .GetSessions()
+            .ok()
+            .and_then(|sessions| {
+                sessions
+                    .into_iter()
+                    .find(|session| app_id(session).as_deref() == Some(app))
+            })
+            .ok_or_else(|| {
+                let message = format!("No media session found for {}", app);
+                AruiError::new(ErrorCode::NoSession, message)
+            })?,
+        None => session_manager
+            .GetCurrentSession()
+            .map_err(|e| winrt_error(ErrorCode::NoSession, "No active media session found", &e))?,
+    };
+
+    let operation = match command {
+        Commands::SkipTrack { .. } => session.TrySkipNextAsync(),
+        Commands::PreviousTrack { .. } => session.TrySkipPreviousAsync(),
+        Commands::TogglePlayPause { .. } => session.TryTogglePlayPauseAsync(),
+        Commands::Pause { .. } => session.TryPauseAsync(),
+        Commands::Resume { .. } => session.TryPlayAsync(),
+        Commands::Monitor => {
+            let message = "Monitor is not a media command";
+            return Err(AruiError::new(ErrorCode::InvalidArgument, message));
+        }
+    };
+    let result = match operation {
+        Ok(operation) => operation.get(),
+        Err(e) => Err(e),
+    };
+
+    // The session reports `false` when the app rejects the command (e.g. no next track)
+    match result {
+        Ok(true) => Ok(serde_json::json!({ "command": command.name(), "app": app_id(&session) })),
+        Ok(false) => {
+            let message = format!("{} was rejected by the media session", command.name());
+            Err(AruiError::new(ErrorCode::Failed, message))
+        }
+        Err(e) => Err(winrt_error(
+            ErrorCode::Unsupported,
+            "Command not supported",
+            &e,
+        )),
+    }
+}
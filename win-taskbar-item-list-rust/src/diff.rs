@@ -0,0 +1,363 @@
+use crate::pinned::normalize_path;
+use arui_protocol::{ItemChange, TaskbarItem, TaskbarUpdate};
+use std::collections::HashMap;
+
+/// Item'ı iki tick arasında eşleştirmek için kullanılan key.
+/// Çalışan pencereler hwnd ile, sadece pinned olanlar executable path ile eşleşir.
+pub fn item_key(item: &TaskbarItem) -> String {
+    if !item.hwnd.is_null() {
+        format!("hwnd:{}", item.hwnd)
+    } else if !item.executable_path.is_empty() {
+        format!("pinned:{}", normalize_path(&item.executable_path))
+    } else {
+        format!("pinned:{}", item.process_name.to_lowercase())
+    }
+}
+
+/// Önceki listeyi hatırlayıp sadece farkları üreten motor
+#[derive(Default)]
+pub struct TaskbarDiffer {
+    previous: Option<HashMap<String, TaskbarItem>>,
+    previous_order: Vec<String>,
+}
+
+impl TaskbarDiffer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Bir sonraki diff'in tam liste göndermesini sağlar
+    pub fn request_resync(&mut self) {
+        self.previous = None;
+    }
+
+    /// Mevcut item'ları önceki durumla karşılaştırır. İlk çağrıda veya
+    /// resync istendiğinde tek bir "list" update'i döner.
+    pub fn diff(&mut self, items: Vec<TaskbarItem>) -> Vec<TaskbarUpdate> {
+        let keys: Vec<String> = items.iter().map(item_key).collect();
+
+        let previous = match self.previous.take() {
+            Some(previous) => previous,
+            None => {
+                self.remember(&keys, &items);
+                return vec![TaskbarUpdate::new("list", items)];
+            }
+        };
+
+        let mut added = Vec::new();
+        let mut changes = Vec::new();
+
+        for (key, item) in keys.iter().zip(items.iter()) {
+            match previous.get(key) {
+                None => added.push(item.clone()),
+                Some(old) if old != item => changes.push(ItemChange {
+                    key: key.clone(),
+                    changes: changed_fields(old, item),
+                }),
+                Some(_) => {}
+            }
+        }
+
+        let current: std::collections::HashSet<&String> = keys.iter().collect();
+        let removed: Vec<String> = self
+            .previous_order
+            .iter()
+            .filter(|key| !current.contains(key))
+            .cloned()
+            .collect();
+
+        self.remember(&keys, &items);
+
+        let mut updates = Vec::new();
+        if !removed.is_empty() {
+            let mut update = TaskbarUpdate::new("removed", Vec::new());
+            update.removed = removed;
+            updates.push(update);
+        }
+        if !added.is_empty() {
+            updates.push(TaskbarUpdate::new("added", added));
+        }
+        if !changes.is_empty() {
+            let mut update = TaskbarUpdate::new("updated", Vec::new());
+            update.changes = changes;
+            updates.push(update);
+        }
+        updates
+    }
+
+    fn remember(&mut self, keys: &[String], items: &[TaskbarItem]) {
+        self.previous = Some(
+            keys.iter()
+                .cloned()
+                .zip(items.iter().cloned())
+                .collect::<HashMap<_, _>>(),
+        );
+        self.previous_order = keys.to_vec();
+    }
+}
+
+fn changed_fields(
+    old: &TaskbarItem,
+    new: &TaskbarItem,
+) -> serde_json::Map<String, serde_json::Value> {
+    let mut changes = serde_json::Map::new();
+
+    if let (Ok(serde_json::Value::Object(old)), Ok(serde_json::Value::Object(new))) =
+        (serde_json::to_value(old), serde_json::to_value(new))
+    {
+        for (field, value) in new {
+            if old.get(&field) != Some(&value) {
+                changes.insert(field, value);
+            }
+        }
+    }
+
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::items::collect_taskbar_items;
+    use crate::pinned::PinnedIndex;
+    use crate::pinned_store::PinnedStore;
+    use crate::rules::RuleSet;
+    use crate::window_source::{
+        FixtureFrame, FixtureWindowSource, ProcessInfo, RawWindow, WindowSource, WS_CAPTION,
+        WS_VISIBLE,
+    };
+    use serde_json::json;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    const NOTEPAD: &str = "C:\\Windows\\notepad.exe";
+    const CODE: &str = "C:\\Program Files\\Microsoft VS Code\\Code.exe";
+
+    fn window(hwnd: isize, process_id: u32, title: &str) -> RawWindow {
+        RawWindow {
+            hwnd,
+            process_id,
+            class_name: "Window".to_string(),
+            title: title.to_string(),
+            style: WS_CAPTION | WS_VISIBLE,
+            is_visible: true,
+            ..Default::default()
+        }
+    }
+
+    fn frame(windows: Vec<RawWindow>, foreground: isize) -> FixtureFrame {
+        FixtureFrame {
+            windows,
+            processes: HashMap::from([
+                (
+                    10,
+                    ProcessInfo {
+                        name: "notepad.exe".to_string(),
+                        executable_path: NOTEPAD.to_string(),
+                    },
+                ),
+                (
+                    20,
+                    ProcessInfo {
+                        name: "Code.exe".to_string(),
+                        executable_path: CODE.to_string(),
+                    },
+                ),
+            ]),
+            foreground,
+        }
+    }
+
+    /// Fixture'ı frame frame okuyup her tick'in update'lerini döner
+    struct Ticker {
+        source: FixtureWindowSource,
+        pinned: PinnedIndex,
+        rules: RuleSet,
+        differ: TaskbarDiffer,
+        started: bool,
+    }
+
+    impl Ticker {
+        fn new(dir: &TempDir, frames: Vec<FixtureFrame>, pinned: &[&str]) -> Self {
+            let store_path = dir.path().join("pinned.json");
+            let mut store = PinnedStore::load(&store_path).unwrap();
+            for path in pinned {
+                store.pin(path, Some(path.to_string()), None);
+            }
+            store.save().unwrap();
+
+            Self {
+                source: FixtureWindowSource::new(frames),
+                pinned: PinnedIndex::new(dir.path().join("TaskBar")).with_store(store_path),
+                rules: RuleSet::defaults(),
+                differ: TaskbarDiffer::new(),
+                started: false,
+            }
+        }
+
+        fn items(&self) -> Vec<TaskbarItem> {
+            collect_taskbar_items(&self.source, &self.pinned, &self.rules)
+        }
+
+        fn tick(&mut self) -> Vec<TaskbarUpdate> {
+            if self.started {
+                self.source.advance();
+            }
+            self.started = true;
+            self.differ.diff(self.items())
+        }
+    }
+
+    fn actions(updates: &[TaskbarUpdate]) -> Vec<&str> {
+        updates
+            .iter()
+            .map(|update| update.action.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn first_tick_sends_the_full_list_then_nothing_while_unchanged() {
+        let dir = TempDir::new().unwrap();
+        let still = frame(vec![window(1, 10, "notes.txt - Notepad")], 1);
+        let mut ticker = Ticker::new(&dir, vec![still.clone(), still], &[]);
+
+        let updates = ticker.tick();
+        assert_eq!(actions(&updates), ["list"]);
+        assert_eq!(updates[0].items, ticker.items());
+
+        assert!(ticker.tick().is_empty());
+    }
+
+    #[test]
+    fn reports_added_removed_and_updated_items() {
+        let dir = TempDir::new().unwrap();
+        let mut ticker = Ticker::new(
+            &dir,
+            vec![
+                frame(
+                    vec![
+                        window(1, 10, "notes.txt - Notepad"),
+                        window(2, 20, "main.rs - Visual Studio Code"),
+                    ],
+                    1,
+                ),
+                frame(
+                    vec![
+                        window(1, 10, "todo.txt - Notepad"),
+                        window(3, 20, "lib.rs - Visual Studio Code"),
+                    ],
+                    1,
+                ),
+            ],
+            &[],
+        );
+        ticker.tick();
+
+        let updates = ticker.tick();
+        assert_eq!(actions(&updates), ["removed", "added", "updated"]);
+        assert_eq!(updates[0].removed, ["hwnd:2"]);
+        assert!(updates[0].items.is_empty());
+        assert_eq!(updates[1].items.len(), 1);
+        assert_eq!(updates[1].items[0].title, "lib.rs - Visual Studio Code");
+
+        // Sadece değişen alanlar gönderilir
+        let changes = &updates[2].changes;
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].key, "hwnd:1");
+        assert_eq!(
+            serde_json::Value::Object(changes[0].changes.clone()),
+            json!({"title": "todo.txt - Notepad"})
+        );
+    }
+
+    #[test]
+    fn field_delta_covers_every_changed_field() {
+        let dir = TempDir::new().unwrap();
+        let mut minimized = window(1, 10, "notes.txt - Notepad");
+        minimized.is_minimized = true;
+        let mut ticker = Ticker::new(
+            &dir,
+            vec![
+                frame(vec![window(1, 10, "notes.txt - Notepad")], 1),
+                frame(vec![minimized], 0),
+            ],
+            &[],
+        );
+        ticker.tick();
+
+        let updates = ticker.tick();
+        assert_eq!(actions(&updates), ["updated"]);
+        let fields = &updates[0].changes[0].changes;
+        assert_eq!(fields.get("window_state"), Some(&json!("minimized")));
+        assert_eq!(fields.get("is_focused"), Some(&json!(false)));
+        assert!(!fields.contains_key("title"));
+        assert!(!fields.contains_key("hwnd"));
+    }
+
+    #[test]
+    fn resync_sends_the_full_list_again() {
+        let dir = TempDir::new().unwrap();
+        let still = frame(vec![window(1, 10, "notes.txt - Notepad")], 1);
+        let mut ticker = Ticker::new(&dir, vec![still.clone(), still.clone(), still], &[]);
+        ticker.tick();
+        assert!(ticker.tick().is_empty());
+
+        ticker.differ.request_resync();
+        let updates = ticker.tick();
+        assert_eq!(actions(&updates), ["list"]);
+        assert_eq!(updates[0].items, ticker.items());
+    }
+
+    #[test]
+    fn pinned_items_are_matched_by_normalized_path() {
+        let dir = TempDir::new().unwrap();
+        let ticker = Ticker::new(&dir, vec![frame(Vec::new(), 0)], &[CODE]);
+        let items = ticker.items();
+        assert_eq!(items.len(), 1);
+        assert!(items[0].hwnd.is_null());
+
+        // Aynı executable farklı yazılışlarla raporlanabilir
+        let key = item_key(&items[0]);
+        for path in [
+            "c:\\program files\\microsoft vs code\\CODE.EXE",
+            "C:/Program Files/Microsoft VS Code/Code.exe",
+            "\\\\?\\C:\\Program Files\\Microsoft VS Code\\Code.exe",
+        ] {
+            let mut item = items[0].clone();
+            item.executable_path = path.to_string();
+            assert_eq!(item_key(&item), key, "{}", path);
+        }
+
+        // Aynı item'ın yolu farklı yazılsa da yeni item sayılmaz
+        let mut differ = TaskbarDiffer::new();
+        differ.diff(items.clone());
+        let mut renamed = items[0].clone();
+        renamed.executable_path = "C:/Program Files/Microsoft VS Code/Code.exe".to_string();
+        let updates = differ.diff(vec![renamed]);
+        assert_eq!(actions(&updates), ["updated"]);
+        assert_eq!(updates[0].changes[0].key, key);
+    }
+
+    #[test]
+    fn pinned_app_that_starts_running_is_added_and_its_placeholder_removed() {
+        let dir = TempDir::new().unwrap();
+        let mut ticker = Ticker::new(
+            &dir,
+            vec![
+                frame(Vec::new(), 0),
+                frame(vec![window(2, 20, "main.rs - Visual Studio Code")], 2),
+            ],
+            &[CODE],
+        );
+        let first = ticker.tick();
+        assert_eq!(first[0].items.len(), 1);
+        let placeholder = item_key(&first[0].items[0]);
+        assert!(placeholder.starts_with("pinned:"));
+
+        let updates = ticker.tick();
+        assert_eq!(actions(&updates), ["removed", "added"]);
+        assert_eq!(updates[0].removed, [placeholder]);
+        assert_eq!(updates[1].items[0].hwnd.as_raw(), 2);
+        assert!(updates[1].items[0].is_pinned);
+    }
+}
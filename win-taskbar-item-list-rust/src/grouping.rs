@@ -0,0 +1,341 @@
+use crate::pinned::{normalize_path, PinnedEntry};
+use arui_protocol::{TaskbarGroup, TaskbarItem};
+use std::collections::HashMap;
+
+/// Grup key'i: executable path, bilinmiyorsa process adı
+pub fn app_id(executable_path: &str, process_name: &str) -> String {
+    if !executable_path.is_empty() {
+        format!("path:{}", normalize_path(executable_path))
+    } else {
+        format!(
+            "process:{}",
+            process_name.to_lowercase().trim_end_matches(".exe")
+        )
+    }
+}
+
+fn pinned_app_id(entry: &PinnedEntry) -> String {
+    app_id(&entry.executable_path, &entry.display_name)
+}
+
+/// Item'ları uygulamalara göre gruplar. Pinned gruplar pinned listesindeki
+/// sırayla önce gelir; diğerleri ilk görüldükleri sırada kalır, böylece
+/// focus veya Z-order değişiklikleri butonların yerini değiştirmez.
+#[derive(Default)]
+pub struct TaskbarGrouper {
+    first_seen: HashMap<String, u64>,
+    counter: u64,
+}
+
+impl TaskbarGrouper {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn seen(&mut self, key: &str) -> u64 {
+        if let Some(order) = self.first_seen.get(key) {
+            return *order;
+        }
+        self.counter += 1;
+        self.first_seen.insert(key.to_string(), self.counter);
+        self.counter
+    }
+
+    pub fn group(&mut self, items: &[TaskbarItem], pinned: &[PinnedEntry]) -> Vec<TaskbarGroup> {
+        let mut groups: Vec<TaskbarGroup> = Vec::new();
+        let mut index_of: HashMap<String, usize> = HashMap::new();
+
+        for item in items {
+            let id = app_id(&item.executable_path, &item.process_name);
+            let index = *index_of.entry(id.clone()).or_insert_with(|| {
+                groups.push(TaskbarGroup {
+                    app_id: id.clone(),
+                    name: item.process_name.trim_end_matches(".exe").to_string(),
+                    executable_path: item.executable_path.clone(),
+                    pinned: false,
+                    windows: Vec::new(),
+                });
+                groups.len() - 1
+            });
+
+            let group = &mut groups[index];
+            group.pinned |= item.is_pinned;
+            if !item.hwnd.is_null() {
+                group.windows.push(item.clone());
+            }
+        }
+
+        // Pinned listesindeki sıra
+        let pinned_order: HashMap<String, usize> = pinned
+            .iter()
+            .enumerate()
+            .map(|(position, entry)| (pinned_app_id(entry), position))
+            .collect();
+
+        // Target'ı çözülemeyen shortcut'lar process adıyla eşleşir
+        let pinned_position = |group: &TaskbarGroup| {
+            pinned_order
+                .get(&group.app_id)
+                .or_else(|| pinned_order.get(&app_id("", &group.name)))
+                .copied()
+        };
+
+        let mut keyed: Vec<(Option<usize>, u64, TaskbarGroup)> = Vec::new();
+        for mut group in groups {
+            let position = pinned_position(&group);
+            if let Some(position) = position {
+                group.pinned = true;
+                group.name = pinned[position].display_name.clone();
+            }
+
+            let mut windows: Vec<(u64, TaskbarItem)> = group
+                .windows
+                .drain(..)
+                .map(|item| (self.seen(&format!("hwnd:{}", item.hwnd)), item))
+                .collect();
+            windows.sort_by_key(|(order, _)| *order);
+            group.windows = windows.into_iter().map(|(_, item)| item).collect();
+
+            let order = self.seen(&group.app_id);
+            keyed.push((position, order, group));
+        }
+
+        // Pinned gruplar (pinned sırasıyla) önce, sonra ilk görülme sırası
+        keyed.sort_by(|a, b| match (a.0, b.0) {
+            (Some(a), Some(b)) => a.cmp(&b),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => a.1.cmp(&b.1),
+        });
+
+        // Artık görünmeyen grup ve pencereleri unut
+        let alive: std::collections::HashSet<String> = keyed
+            .iter()
+            .flat_map(|(_, _, group)| {
+                std::iter::once(group.app_id.clone()).chain(
+                    group
+                        .windows
+                        .iter()
+                        .map(|item| format!("hwnd:{}", item.hwnd)),
+                )
+            })
+            .collect();
+        self.first_seen.retain(|key, _| alive.contains(key));
+
+        keyed.into_iter().map(|(_, _, group)| group).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::items::collect_taskbar_items;
+    use crate::pinned::PinnedIndex;
+    use crate::rules::RuleSet;
+    use crate::window_source::{
+        FixtureFrame, FixtureWindowSource, ProcessInfo, RawWindow, WS_CAPTION, WS_VISIBLE,
+    };
+    use tempfile::TempDir;
+
+    const NOTEPAD: &str = "C:\\Windows\\notepad.exe";
+    const CODE: &str = "C:\\Program Files\\Microsoft VS Code\\Code.exe";
+    const NOTEPAD_ID: &str = "path:c:\\windows\\notepad.exe";
+    const CODE_ID: &str = "path:c:\\program files\\microsoft vs code\\code.exe";
+
+    fn window(hwnd: isize, process_id: u32, title: &str) -> RawWindow {
+        RawWindow {
+            hwnd,
+            process_id,
+            class_name: "Window".to_string(),
+            title: title.to_string(),
+            style: WS_CAPTION | WS_VISIBLE,
+            is_visible: true,
+            ..Default::default()
+        }
+    }
+
+    /// Pencereleri verilen Z-order'la sınıflandırır. 10 ve 11 aynı Notepad'in
+    /// iki process'i, 20 VS Code, 30 ise path'i okunamayan bir process'tir.
+    fn items(windows: Vec<RawWindow>) -> Vec<TaskbarItem> {
+        let dir = TempDir::new().unwrap();
+        let process = |name: &str, executable_path: &str| ProcessInfo {
+            name: name.to_string(),
+            executable_path: executable_path.to_string(),
+        };
+        let source = FixtureWindowSource::new(vec![FixtureFrame {
+            windows,
+            processes: HashMap::from([
+                (10, process("notepad.exe", NOTEPAD)),
+                (11, process("notepad.exe", "c:/windows/NOTEPAD.EXE")),
+                (20, process("Code.exe", CODE)),
+                (30, process("Elevated.exe", "")),
+                (31, process("elevated.exe", "")),
+            ]),
+            foreground: 0,
+        }]);
+        let pinned = PinnedIndex::new(dir.path().join("TaskBar"));
+        collect_taskbar_items(&source, &pinned, &RuleSet::defaults())
+    }
+
+    fn pinned(name: &str, executable_path: &str) -> PinnedEntry {
+        PinnedEntry {
+            display_name: name.to_string(),
+            executable_path: executable_path.to_string(),
+        }
+    }
+
+    fn summary(groups: &[TaskbarGroup]) -> Vec<(String, Vec<isize>)> {
+        groups
+            .iter()
+            .map(|group| {
+                let windows = group
+                    .windows
+                    .iter()
+                    .map(|item| item.hwnd.as_raw())
+                    .collect();
+                (group.app_id.clone(), windows)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn groups_windows_by_executable() {
+        let mut grouper = TaskbarGrouper::new();
+        let groups = grouper.group(
+            &items(vec![
+                window(1, 10, "a.txt - Notepad"),
+                window(2, 20, "main.rs - Visual Studio Code"),
+                window(3, 11, "b.txt - Notepad"),
+            ]),
+            &[],
+        );
+
+        // Aynı executable'ın farklı yazılışları tek grupta toplanır
+        assert_eq!(
+            summary(&groups),
+            [
+                (CODE_ID.to_string(), vec![2]),
+                (NOTEPAD_ID.to_string(), vec![1, 3]),
+            ]
+        );
+        assert_eq!(groups[1].name, "notepad");
+        assert_eq!(groups[1].executable_path, NOTEPAD);
+        assert!(!groups[1].pinned);
+    }
+
+    #[test]
+    fn falls_back_to_the_process_name() {
+        let mut grouper = TaskbarGrouper::new();
+        let groups = grouper.group(
+            &items(vec![
+                window(1, 30, "Admin tool"),
+                window(2, 31, "Admin tool (2)"),
+            ]),
+            &[],
+        );
+
+        assert_eq!(
+            summary(&groups),
+            [("process:elevated".to_string(), vec![1, 2])]
+        );
+        assert_eq!(groups[0].name, "Elevated");
+        assert!(groups[0].executable_path.is_empty());
+    }
+
+    #[test]
+    fn keeps_group_and_window_order_across_ticks() {
+        let mut grouper = TaskbarGrouper::new();
+        let first = grouper.group(
+            &items(vec![
+                window(1, 10, "a.txt - Notepad"),
+                window(3, 10, "b.txt - Notepad"),
+            ]),
+            &[],
+        );
+        assert_eq!(summary(&first), [(NOTEPAD_ID.to_string(), vec![1, 3])]);
+
+        // Focus değişince Z-order tersine döner; VS Code alfabetik olarak önce
+        // gelse de sonradan açıldığı için sonda kalır
+        let second = grouper.group(
+            &items(vec![
+                window(2, 20, "main.rs - Visual Studio Code"),
+                window(3, 10, "b.txt - Notepad"),
+                window(1, 10, "a.txt - Notepad"),
+            ]),
+            &[],
+        );
+        assert_eq!(
+            summary(&second),
+            [
+                (NOTEPAD_ID.to_string(), vec![1, 3]),
+                (CODE_ID.to_string(), vec![2]),
+            ]
+        );
+
+        // Yeni uygulama ve pencereler sona eklenir
+        let third = grouper.group(
+            &items(vec![
+                window(5, 10, "c.txt - Notepad"),
+                window(4, 30, "Admin tool"),
+                window(1, 10, "a.txt - Notepad"),
+                window(2, 20, "main.rs - Visual Studio Code"),
+                window(3, 10, "b.txt - Notepad"),
+            ]),
+            &[],
+        );
+        assert_eq!(
+            summary(&third),
+            [
+                (NOTEPAD_ID.to_string(), vec![1, 3, 5]),
+                (CODE_ID.to_string(), vec![2]),
+                ("process:elevated".to_string(), vec![4]),
+            ]
+        );
+    }
+
+    #[test]
+    fn closed_apps_lose_their_place() {
+        let mut grouper = TaskbarGrouper::new();
+        let both = || {
+            items(vec![
+                window(1, 10, "a.txt - Notepad"),
+                window(2, 20, "main.rs - Visual Studio Code"),
+            ])
+        };
+        let first = grouper.group(&both(), &[]);
+        assert_eq!(first[0].app_id, CODE_ID);
+
+        grouper.group(&items(vec![window(1, 10, "a.txt - Notepad")]), &[]);
+
+        // VS Code yeniden açıldığında artık sonda
+        let groups = grouper.group(&both(), &[]);
+        assert_eq!(
+            summary(&groups),
+            [
+                (NOTEPAD_ID.to_string(), vec![1]),
+                (CODE_ID.to_string(), vec![2]),
+            ]
+        );
+    }
+
+    #[test]
+    fn pinned_groups_come_first_in_pinned_order() {
+        let mut grouper = TaskbarGrouper::new();
+        let groups = grouper.group(
+            &items(vec![
+                window(1, 10, "a.txt - Notepad"),
+                window(2, 20, "main.rs - Visual Studio Code"),
+                window(3, 30, "Admin tool"),
+            ]),
+            &[
+                // Target'ı çözülemeyen shortcut process adıyla eşleşir
+                pinned("Elevated", ""),
+                pinned("Visual Studio Code", CODE),
+            ],
+        );
+
+        let names: Vec<&str> = groups.iter().map(|group| group.name.as_str()).collect();
+        assert_eq!(names, ["Elevated", "Visual Studio Code", "notepad"]);
+        assert!(groups[0].pinned && groups[1].pinned && !groups[2].pinned);
+    }
+}
@@ -0,0 +1,361 @@
+use crate::pinned::normalize_path;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Bellekte tutulacak en fazla icon sayısı
+const MAX_MEMORY_ENTRIES: usize = 256;
+// Disk önbelleğinin en fazla boyutu (byte)
+const MAX_DISK_BYTES: u64 = 32 * 1024 * 1024;
+
+/// FNV-1a 64-bit; icon key'leri için yeterince hızlı ve dağılımı iyi
+pub fn fnv1a64(data: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in data {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    hash
+}
+
+/// Executable icon'unun id'si: normalize edilmiş path + dosyanın mtime'ı.
+/// Executable güncellendiğinde id de değişir.
+pub fn executable_icon_id(executable_path: &str) -> String {
+    if executable_path.is_empty() {
+        return String::new();
+    }
+
+    let mtime = std::fs::metadata(executable_path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let key = format!("{}|{}", normalize_path(executable_path), mtime);
+    format!("exe-{:016x}", fnv1a64(key.as_bytes()))
+}
+
+/// Pencere icon'unun id'si: render edilmiş piksel verisinin hash'i
+pub fn content_icon_id(pixels: &[u8]) -> String {
+    format!("win-{:016x}", fnv1a64(pixels))
+}
+
+/// Yarım kalmış `.tmp` yazımları hariç önbellek dosyaları
+fn is_cache_file(path: &Path) -> bool {
+    path.is_file() && path.extension().is_none_or(|ext| ext != "tmp")
+}
+
+/// Bir icon'un belirli boyut ve formattaki önbellek key'i
+pub fn cache_key(icon_id: &str, size: u32, extension: &str) -> String {
+    if icon_id.is_empty() {
+        return String::new();
+    }
+    format!("{}-{}.{}", icon_id, size, extension)
+}
+
+struct MemoryEntry {
+    data: Vec<u8>,
+    last_used: u64,
+}
+
+/// Bellek (LRU) + disk icon önbelleği. Key'ler `<id>-<boyut>.<uzantı>`
+/// şeklindedir ve disk'te aynı isimle saklanır; en eski erişilenler boyut
+/// sınırı aşıldığında silinir.
+pub struct IconCache {
+    dir: PathBuf,
+    memory: HashMap<String, MemoryEntry>,
+    clock: u64,
+}
+
+impl IconCache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            memory: HashMap::new(),
+            clock: 0,
+        }
+    }
+
+    /// %LOCALAPPDATA%\ARUI\icon-cache
+    pub fn default_dir() -> PathBuf {
+        let local_appdata = std::env::var("LOCALAPPDATA").unwrap_or_default();
+        Path::new(&local_appdata).join("ARUI").join("icon-cache")
+    }
+
+    fn file_path(&self, key: &str) -> PathBuf {
+        self.dir.join(key)
+    }
+
+    /// Önbellekteki veriyi döner; bellekte yoksa diskten yükler
+    pub fn get(&mut self, key: &str) -> Option<Vec<u8>> {
+        if key.is_empty() {
+            return None;
+        }
+
+        self.clock += 1;
+        if let Some(entry) = self.memory.get_mut(key) {
+            entry.last_used = self.clock;
+            return Some(entry.data.clone());
+        }
+
+        let path = self.file_path(key);
+        let data = std::fs::read(&path).ok()?;
+        // Disk LRU'su için son erişim zamanını güncelle
+        if let Ok(file) = std::fs::File::options().write(true).open(&path) {
+            let _ = file.set_modified(SystemTime::now());
+        }
+        self.insert_memory(key, data.clone());
+        Some(data)
+    }
+
+    pub fn insert(&mut self, key: &str, data: Vec<u8>) {
+        if key.is_empty() {
+            return;
+        }
+
+        if std::fs::create_dir_all(&self.dir).is_ok() {
+            let path = self.file_path(key);
+            let tmp_path = self.file_path(&format!("{}.tmp", key));
+            if std::fs::write(&tmp_path, &data).is_ok() {
+                let _ = std::fs::rename(&tmp_path, &path);
+            }
+            self.evict_disk();
+        }
+
+        self.clock += 1;
+        self.insert_memory(key, data);
+    }
+
+    /// Bellek ve disk önbelleğini temizler; silinen dosya sayısını döner
+    pub fn clear(&mut self) -> usize {
+        self.memory.clear();
+
+        let Ok(entries) = std::fs::read_dir(&self.dir) else {
+            return 0;
+        };
+        entries
+            .flatten()
+            .filter(|entry| is_cache_file(&entry.path()))
+            .filter(|entry| std::fs::remove_file(entry.path()).is_ok())
+            .count()
+    }
+
+    fn insert_memory(&mut self, key: &str, data: Vec<u8>) {
+        self.memory.insert(
+            key.to_string(),
+            MemoryEntry {
+                data,
+                last_used: self.clock,
+            },
+        );
+
+        // En uzun süredir kullanılmayan icon'ları çıkar
+        while self.memory.len() > MAX_MEMORY_ENTRIES {
+            let Some(oldest) = self
+                .memory
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone())
+            else {
+                break;
+            };
+            self.memory.remove(&oldest);
+        }
+    }
+
+    fn evict_disk(&self) {
+        let Ok(entries) = std::fs::read_dir(&self.dir) else {
+            return;
+        };
+
+        let mut files: Vec<(PathBuf, u64, SystemTime)> = entries
+            .flatten()
+            .filter(|entry| is_cache_file(&entry.path()))
+            .filter_map(|entry| {
+                let metadata = entry.metadata().ok()?;
+                let modified = metadata.modified().unwrap_or(UNIX_EPOCH);
+                Some((entry.path(), metadata.len(), modified))
+            })
+            .collect();
+
+        let mut total: u64 = files.iter().map(|(_, size, _)| size).sum();
+        if total <= MAX_DISK_BYTES {
+            return;
+        }
+
+        // En eski erişilenden başlayarak sınırın altına inene kadar sil
+        files.sort_by_key(|(_, _, modified)| *modified);
+        for (path, size, _) in files {
+            if total <= MAX_DISK_BYTES {
+                break;
+            }
+            if std::fs::remove_file(&path).is_ok() {
+                total -= size;
+            }
+        }
+    }
+}
+
+/// Process genelinde paylaşılan önbellek (serve modunda istekler farklı
+/// thread'lerden gelir)
+pub fn icon_cache() -> &'static Mutex<IconCache> {
+    static CACHE: OnceLock<Mutex<IconCache>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(IconCache::new(IconCache::default_dir())))
+}
+
+/// Paylaşılan önbellekten oku
+pub fn cached_icon(key: &str) -> Option<Vec<u8>> {
+    icon_cache().lock().ok()?.get(key)
+}
+
+/// Paylaşılan önbelleğe yaz
+pub fn store_icon(key: &str, data: Vec<u8>) {
+    if let Ok(mut cache) = icon_cache().lock() {
+        cache.insert(key, data);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    fn set_mtime(path: &Path, secs: u64) {
+        std::fs::File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(UNIX_EPOCH + Duration::from_secs(secs))
+            .unwrap();
+    }
+
+    #[test]
+    fn ids_are_stable() {
+        let dir = TempDir::new().unwrap();
+        let exe = dir.path().join("app.exe");
+        std::fs::write(&exe, b"MZ").unwrap();
+        let exe = exe.to_string_lossy().to_string();
+
+        let id = executable_icon_id(&exe);
+        assert!(id.starts_with("exe-"));
+        assert_eq!(executable_icon_id(&exe), id);
+        // Aynı executable'ın farklı yazılışları aynı id'yi alır
+        assert_eq!(
+            executable_icon_id("C:\\Apps\\Tool.exe"),
+            executable_icon_id("c:/apps/TOOL.EXE")
+        );
+        assert_ne!(executable_icon_id("C:\\Apps\\Tool.exe"), id);
+        assert_eq!(executable_icon_id(""), "");
+
+        assert_eq!(content_icon_id(b"pixels"), content_icon_id(b"pixels"));
+        assert_ne!(content_icon_id(b"pixels"), content_icon_id(b"pixelz"));
+
+        assert_eq!(cache_key(&id, 32, "png"), format!("{}-32.png", id));
+        assert_eq!(cache_key("", 32, "png"), "");
+    }
+
+    #[test]
+    fn executable_id_changes_with_mtime() {
+        let dir = TempDir::new().unwrap();
+        let exe = dir.path().join("app.exe");
+        std::fs::write(&exe, b"MZ").unwrap();
+        let path = exe.to_string_lossy().to_string();
+
+        set_mtime(&exe, 1_000_000);
+        let before = executable_icon_id(&path);
+        set_mtime(&exe, 2_000_000);
+        let after = executable_icon_id(&path);
+
+        assert_ne!(before, after);
+        assert_ne!(cache_key(&before, 32, "png"), cache_key(&after, 32, "png"));
+    }
+
+    #[test]
+    fn reads_back_from_memory_and_disk() {
+        let dir = TempDir::new().unwrap();
+        let mut cache = IconCache::new(dir.path().join("icon-cache"));
+        cache.insert("exe-1-32.png", vec![1, 2, 3]);
+        assert_eq!(cache.get("exe-1-32.png"), Some(vec![1, 2, 3]));
+        assert_eq!(cache.get("exe-2-32.png"), None);
+
+        // Yeni bir process diskteki kopyayı bulur
+        let mut fresh = IconCache::new(dir.path().join("icon-cache"));
+        assert_eq!(fresh.get("exe-1-32.png"), Some(vec![1, 2, 3]));
+        assert_eq!(fresh.get(""), None);
+    }
+
+    #[test]
+    fn memory_keeps_the_most_recently_used_entries() {
+        let dir = TempDir::new().unwrap();
+        let mut cache = IconCache::new(dir.path().join("icon-cache"));
+        let key = |i: usize| format!("win-{:016x}-32.png", i);
+
+        for i in 0..MAX_MEMORY_ENTRIES {
+            cache.insert(&key(i), vec![i as u8]);
+        }
+        // İlk item kullanıldı; en eskisi artık ikincisi
+        assert!(cache.get(&key(0)).is_some());
+        cache.insert(&key(MAX_MEMORY_ENTRIES), vec![0]);
+
+        assert_eq!(cache.memory.len(), MAX_MEMORY_ENTRIES);
+        assert!(cache.memory.contains_key(&key(0)));
+        assert!(!cache.memory.contains_key(&key(1)));
+        assert!(cache.memory.contains_key(&key(MAX_MEMORY_ENTRIES)));
+
+        // Bellekten çıkan item diskten geri yüklenir
+        assert_eq!(cache.get(&key(1)), Some(vec![1]));
+        assert!(cache.memory.contains_key(&key(1)));
+    }
+
+    #[test]
+    fn disk_stays_under_the_size_cap() {
+        let dir = TempDir::new().unwrap();
+        let cache_dir = dir.path().join("icon-cache");
+        let mut cache = IconCache::new(&cache_dir);
+        let chunk = (MAX_DISK_BYTES / 4) as usize;
+
+        // Tam sınırda: hiçbir şey silinmez
+        for (i, name) in ["a", "b", "c", "d"].iter().enumerate() {
+            let key = format!("{}.png", name);
+            cache.insert(&key, vec![0; chunk]);
+            set_mtime(&cache_dir.join(&key), 1_000 + i as u64);
+        }
+        assert_eq!(std::fs::read_dir(&cache_dir).unwrap().count(), 4);
+
+        // "a" yeni kullanıldı; sınır aşılınca en eski erişilen "b" silinir
+        let mut fresh = IconCache::new(&cache_dir);
+        assert!(fresh.get("a.png").is_some());
+        fresh.insert("e.png", vec![0; chunk]);
+
+        let exists = |name: &str| cache_dir.join(name).exists();
+        assert!(exists("a.png") && exists("c.png") && exists("d.png") && exists("e.png"));
+        assert!(!exists("b.png"));
+
+        let total: u64 = std::fs::read_dir(&cache_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().metadata().unwrap().len())
+            .sum();
+        assert!(total <= MAX_DISK_BYTES);
+    }
+
+    #[test]
+    fn clear_removes_memory_and_disk_entries() {
+        let dir = TempDir::new().unwrap();
+        let cache_dir = dir.path().join("icon-cache");
+        let mut cache = IconCache::new(&cache_dir);
+        cache.insert("exe-1-32.png", vec![1]);
+        cache.insert("exe-1-32.webp", vec![2]);
+        // Yarım kalmış yazım sayılmaz
+        std::fs::write(cache_dir.join("exe-2-32.png.tmp"), b"partial").unwrap();
+
+        assert_eq!(cache.clear(), 2);
+        assert!(cache.memory.is_empty());
+        assert_eq!(cache.get("exe-1-32.png"), None);
+        assert_eq!(cache.clear(), 0);
+
+        // Hiç oluşturulmamış önbellek
+        assert_eq!(IconCache::new(dir.path().join("missing")).clear(), 0);
+    }
+}
@@ -0,0 +1,287 @@
+use clap::ValueEnum;
+use image::imageops::FilterType;
+use image::{ImageBuffer, Rgba};
+use serde::Deserialize;
+use std::fmt;
+use std::io::Cursor;
+use std::str::FromStr;
+
+/// Desteklenen icon boyutları (piksel)
+pub const ICON_SIZES: [u32; 5] = [16, 24, 32, 48, 256];
+
+pub fn default_icon_size() -> u32 {
+    32
+}
+
+pub fn validate_icon_size(size: u32) -> Result<u32, String> {
+    if ICON_SIZES.contains(&size) {
+        Ok(size)
+    } else {
+        Err(format!(
+            "Unsupported icon size {}; expected one of 16, 24, 32, 48, 256",
+            size
+        ))
+    }
+}
+
+/// clap için `--size` parser'ı
+pub fn parse_icon_size(value: &str) -> Result<u32, String> {
+    let size = value
+        .parse::<u32>()
+        .map_err(|_| format!("Invalid icon size: {}", value))?;
+    validate_icon_size(size)
+}
+
+/// Screenshot ve thumbnail'ların en büyük boyutu, `WIDTHxHEIGHT` şeklinde
+/// (örn. "512x512")
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(try_from = "String")]
+pub struct MaxSize {
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Default for MaxSize {
+    fn default() -> Self {
+        Self {
+            width: 256,
+            height: 256,
+        }
+    }
+}
+
+impl fmt::Display for MaxSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}x{}", self.width, self.height)
+    }
+}
+
+impl FromStr for MaxSize {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            format!(
+                "Invalid size {:?}; expected WIDTHxHEIGHT (e.g., 512x512)",
+                value
+            )
+        };
+        let (width, height) = value.trim().split_once('x').ok_or_else(invalid)?;
+        match (width.parse::<u32>(), height.parse::<u32>()) {
+            (Ok(width), Ok(height)) if width > 0 && height > 0 => Ok(Self { width, height }),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+impl TryFrom<String> for MaxSize {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+#[derive(ValueEnum, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum IconFormat {
+    #[default]
+    Png,
+    Webp,
+    RawRgba,
+}
+
+impl IconFormat {
+    /// Yanıtlardaki "format" alanı
+    pub fn name(self) -> &'static str {
+        match self {
+            IconFormat::Png => "PNG",
+            IconFormat::Webp => "WEBP",
+            IconFormat::RawRgba => "RAW_RGBA",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            IconFormat::Png => "png",
+            IconFormat::Webp => "webp",
+            IconFormat::RawRgba => "rgba",
+        }
+    }
+}
+
+/// RGBA pikselleri oranını koruyarak `size`x`size` kareye sığdırıp istenen
+/// formatta encode eder. Büyük icon'lar Lanczos3 ile küçültülür, küçükler
+/// büyütülmez; boşluk şeffaf bırakılır ve icon ortalanır. Boyut zaten aynıysa
+/// piksellere dokunulmaz.
+pub fn encode_icon(
+    pixels: Vec<u8>,
+    width: u32,
+    height: u32,
+    size: u32,
+    format: IconFormat,
+) -> Option<Vec<u8>> {
+    let mut image = ImageBuffer::<Rgba<u8>, Vec<u8>>::from_raw(width, height, pixels)?;
+    if width != size || height != size {
+        let (fit_width, fit_height) = fit_within(width, height, size);
+        if (fit_width, fit_height) != (width, height) {
+            image = image::imageops::resize(&image, fit_width, fit_height, FilterType::Lanczos3);
+        }
+
+        let mut canvas = ImageBuffer::from_pixel(size, size, Rgba([0, 0, 0, 0]));
+        let x = (size - fit_width) / 2;
+        let y = (size - fit_height) / 2;
+        image::imageops::replace(&mut canvas, &image, x as i64, y as i64);
+        image = canvas;
+    }
+
+    encode_rgba(image.into_raw(), size, size, format)
+}
+
+/// `width`x`height`'ı oranını koruyarak `size`x`size` kareye sığdırır; zaten
+/// sığıyorsa boyut değişmez
+fn fit_within(width: u32, height: u32, size: u32) -> (u32, u32) {
+    let longest = width.max(height);
+    if longest <= size {
+        return (width, height);
+    }
+    let scale =
+        |side: u32| ((side as u64 * size as u64 + longest as u64 / 2) / longest as u64).max(1);
+    (scale(width) as u32, scale(height) as u32)
+}
+
+/// RGBA pikselleri olduğu gibi istenen formatta encode eder
+pub fn encode_rgba(
+    pixels: Vec<u8>,
+    width: u32,
+    height: u32,
+    format: IconFormat,
+) -> Option<Vec<u8>> {
+    match format {
+        IconFormat::RawRgba => Some(pixels),
+        IconFormat::Png => {
+            let image = ImageBuffer::<Rgba<u8>, Vec<u8>>::from_raw(width, height, pixels)?;
+            let mut data = Vec::new();
+            image
+                .write_to(&mut Cursor::new(&mut data), image::ImageOutputFormat::Png)
+                .ok()?;
+            Some(data)
+        }
+        IconFormat::Webp => {
+            let mut data = Vec::new();
+            image::codecs::webp::WebPEncoder::new_lossless(&mut data)
+                .encode(&pixels, width, height, image::ColorType::Rgba8)
+                .ok()?;
+            Some(data)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Her pikseli opak kırmızı olan `width`x`height` icon
+    fn red(width: u32, height: u32) -> Vec<u8> {
+        [255, 0, 0, 255].repeat((width * height) as usize)
+    }
+
+    fn decode(data: &[u8]) -> image::RgbaImage {
+        image::load_from_memory(data).unwrap().to_rgba8()
+    }
+
+    #[test]
+    fn fits_the_longest_side_and_never_upscales() {
+        assert_eq!(fit_within(256, 256, 32), (32, 32));
+        assert_eq!(fit_within(256, 128, 32), (32, 16));
+        assert_eq!(fit_within(100, 300, 48), (16, 48));
+        assert_eq!(fit_within(1000, 1, 32), (32, 1));
+        assert_eq!(fit_within(16, 16, 32), (16, 16));
+        assert_eq!(fit_within(20, 10, 32), (20, 10));
+    }
+
+    #[test]
+    fn every_format_is_size_by_size() {
+        for (width, height) in [(32, 32), (256, 256), (64, 32), (16, 16)] {
+            let encode = |format| encode_icon(red(width, height), width, height, 32, format);
+
+            let png = encode(IconFormat::Png).unwrap();
+            assert!(png.starts_with(b"\x89PNG"));
+            assert_eq!(decode(&png).dimensions(), (32, 32));
+
+            let webp = encode(IconFormat::Webp).unwrap();
+            assert!(webp.starts_with(b"RIFF") && &webp[8..12] == b"WEBP");
+            assert_eq!(decode(&webp).dimensions(), (32, 32));
+
+            let raw = encode(IconFormat::RawRgba).unwrap();
+            assert_eq!(raw.len(), 32 * 32 * 4);
+        }
+    }
+
+    #[test]
+    fn wide_icons_keep_their_aspect_ratio() {
+        let png = encode_icon(red(64, 32), 64, 32, 32, IconFormat::Png).unwrap();
+        let image = decode(&png);
+
+        // 32x16'ya küçülür ve dikey olarak ortalanır
+        assert_eq!(image.get_pixel(16, 7).0, [0, 0, 0, 0]);
+        assert_eq!(image.get_pixel(16, 8).0, [255, 0, 0, 255]);
+        assert_eq!(image.get_pixel(16, 23).0, [255, 0, 0, 255]);
+        assert_eq!(image.get_pixel(16, 24).0, [0, 0, 0, 0]);
+        assert_eq!(image.get_pixel(0, 16).0, [255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn small_icons_are_centered_without_scaling() {
+        let raw = encode_icon(red(16, 16), 16, 16, 32, IconFormat::RawRgba).unwrap();
+        let image = ImageBuffer::<Rgba<u8>, Vec<u8>>::from_raw(32, 32, raw).unwrap();
+
+        assert_eq!(image.get_pixel(7, 7).0, [0, 0, 0, 0]);
+        assert_eq!(image.get_pixel(8, 8).0, [255, 0, 0, 255]);
+        assert_eq!(image.get_pixel(23, 23).0, [255, 0, 0, 255]);
+        assert_eq!(image.get_pixel(24, 24).0, [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn same_size_pixels_are_untouched() {
+        let mut pixels = red(32, 32);
+        pixels[0..4].copy_from_slice(&[1, 2, 3, 4]);
+        let raw = encode_icon(pixels.clone(), 32, 32, 32, IconFormat::RawRgba).unwrap();
+        assert_eq!(raw, pixels);
+    }
+
+    #[test]
+    fn mismatched_buffer_is_rejected() {
+        assert_eq!(encode_icon(vec![0; 10], 32, 32, 32, IconFormat::Png), None);
+    }
+
+    #[test]
+    fn max_size_parses_width_and_height() {
+        assert_eq!(
+            "512x384".parse::<MaxSize>(),
+            Ok(MaxSize {
+                width: 512,
+                height: 384
+            })
+        );
+        assert_eq!(MaxSize::default().to_string(), "256x256");
+        assert_eq!(
+            serde_json::from_str::<MaxSize>("\"64x32\"").unwrap(),
+            MaxSize {
+                width: 64,
+                height: 32
+            }
+        );
+    }
+
+    #[test]
+    fn malformed_max_size_is_rejected() {
+        for value in [
+            "", "512", "512x", "x512", "0x256", "256x-1", "axb", "512*512",
+        ] {
+            assert!(value.parse::<MaxSize>().is_err(), "{:?}", value);
+        }
+        assert!(serde_json::from_str::<MaxSize>("\"512\"").is_err());
+        assert!(serde_json::from_str::<MaxSize>("512").is_err());
+    }
+}
@@ -0,0 +1,432 @@
+use crate::pinned::{normalize_path, PinnedIndex};
+use crate::rules::RuleSet;
+use crate::window_source::{RawWindow, WindowSource, WS_CAPTION, WS_EX_TOOLWINDOW};
+use arui_protocol::{MatchedRules, TaskbarItem, WindowHandle};
+use std::collections::HashSet;
+
+/// Tek bir pencereyi TaskbarItem'a çevirir; process adı bilinmiyorsa None döner
+pub fn classify_window(
+    window: &RawWindow,
+    source: &dyn WindowSource,
+    pinned: &PinnedIndex,
+    rules: &RuleSet,
+    foreground_window: isize,
+) -> Option<TaskbarItem> {
+    let process = source.process_info(window.process_id);
+
+    // En azından process name'i olmalı
+    if process.name.is_empty() {
+        return None;
+    }
+
+    let hwnd = window.hwnd;
+    let process_id = window.process_id;
+    let title = window.title.clone();
+    let process_name = process.name;
+    let class_name = window.class_name.clone();
+    let executable_path = process.executable_path;
+    let is_minimized = window.is_minimized;
+    let is_visible = window.is_visible;
+    let is_maximized = window.is_maximized;
+    let style = window.style;
+    let ex_style = window.ex_style;
+
+    let window_state = if is_minimized {
+        "minimized".to_string()
+    } else if is_maximized {
+        "maximized".to_string()
+    } else if is_visible {
+        "normal".to_string()
+    } else {
+        "hidden".to_string()
+    };
+
+    // Taskbar button olup olmadığını kontrol et
+    let has_taskbar_button = (ex_style & WS_EX_TOOLWINDOW) == 0 && (style & WS_CAPTION) != 0;
+
+    // Pinned olup olmadığını kontrol et
+    let is_pinned = pinned.is_pinned(&executable_path);
+
+    // Taskbar, tray ve sistem penceresi kararları kural motorundan gelir
+    let classification = rules.classify(window, &process_name);
+    let matched_rules = classification.matched_rules;
+    let is_tray_icon = classification.is_tray_icon;
+    let is_system_window = classification.is_system_window;
+    let is_definitely_tray = classification.is_definitely_tray;
+    let is_definitely_taskbar = if is_pinned && hwnd == 0 {
+        // Pinned itemlar her zaman taskbar'da görünür (çalışmasalar bile)
+        true
+    } else {
+        classification.is_definitely_taskbar
+    };
+
+    // Display location belirleme
+    let display_location = if is_definitely_taskbar && is_definitely_tray {
+        "both".to_string()
+    } else if is_definitely_taskbar {
+        "taskbar".to_string()
+    } else if is_definitely_tray {
+        "tray".to_string()
+    } else {
+        "hidden".to_string()
+    };
+
+    let item_type = if is_pinned {
+        "both".to_string() // Hem running hem pinned
+    } else {
+        "running".to_string()
+    };
+
+    // Pencere focus durumunu kontrol et
+    let is_focused = hwnd == foreground_window;
+
+    Some(TaskbarItem {
+        title,
+        process_name,
+        process_id,
+        hwnd: WindowHandle::from_raw(hwnd),
+        is_visible,
+        is_minimized,
+        is_maximized,
+        class_name,
+        has_taskbar_button,
+        window_state,
+        is_pinned,
+        executable_path,
+        item_type,
+        is_tray_icon,
+        is_focused,
+        is_running: process_id > 0, // Process ID varsa çalışıyor
+        is_definitely_taskbar,
+        is_definitely_tray,
+        is_system_window,
+        display_location,
+        matched_rules,
+        icon_id: String::new(),
+    })
+}
+
+/// Pencere kaynağından ve pinned listesinden tam taskbar item listesini üretir
+pub fn collect_taskbar_items(
+    source: &dyn WindowSource,
+    pinned: &PinnedIndex,
+    rules: &RuleSet,
+) -> Vec<TaskbarItem> {
+    let foreground_window = source.foreground_window();
+
+    // Çalışan pencerelerden taskbar item'larını al
+    let mut items: Vec<TaskbarItem> = source
+        .windows()
+        .iter()
+        .filter(|window| rules.lists(window))
+        .filter_map(|window| classify_window(window, source, pinned, rules, foreground_window))
+        .collect();
+
+    // Pinned item'ları da ekle (şu anda çalışmayan olanlar)
+    let running_paths: HashSet<String> = items
+        .iter()
+        .filter(|item| !item.executable_path.is_empty())
+        .map(|item| normalize_path(&item.executable_path))
+        .collect();
+    let running_processes: HashSet<String> = items
+        .iter()
+        .map(|item| item.process_name.to_lowercase().replace(".exe", ""))
+        .collect();
+
+    for entry in pinned.entries() {
+        // Target'ı çözülebilen shortcut'lar path ile, diğerleri isim ile eşleşir
+        let is_running = if entry.executable_path.is_empty() {
+            running_processes.contains(&entry.display_name.to_lowercase())
+        } else {
+            running_paths.contains(&normalize_path(&entry.executable_path))
+        };
+
+        // Eğer bu pinned item şu anda çalışmıyorsa, sadece pinned olarak ekle
+        if !is_running {
+            let item = TaskbarItem {
+                title: entry.display_name.clone(),
+                process_name: format!("{}.exe", entry.display_name),
+                process_id: 0,
+                hwnd: WindowHandle::NULL,
+                is_visible: false,
+                is_minimized: false,
+                is_maximized: false,
+                class_name: String::new(),
+                has_taskbar_button: true,
+                window_state: "pinned_only".to_string(),
+                is_pinned: true,
+                executable_path: entry.executable_path.clone(),
+                item_type: "pinned".to_string(),
+                is_tray_icon: false,
+                is_focused: false, // Pinned item'lar focused olamazlar (çalışmadıkları için)
+                is_running: false, // Pinned-only item'lar çalışmıyor
+                is_definitely_taskbar: true,
+                is_definitely_tray: false,
+                is_system_window: false,
+                display_location: "taskbar".to_string(),
+                matched_rules: MatchedRules::default(),
+                icon_id: String::new(),
+            };
+            items.push(item);
+        }
+    }
+
+    // Itemları tutarlı bir şekilde sırala
+    items.sort_by(|a, b| {
+        // Önce çalışan/pinned durumuna göre sırala
+        match (a.is_running, b.is_running) {
+            (true, false) => std::cmp::Ordering::Less, // Çalışan itemlar önce
+            (false, true) => std::cmp::Ordering::Greater, // Pinned itemlar sonra
+            // Pinned-only item'lar pinned listesindeki sırayla eklendi, sort_by stable
+            // olduğu için kullanıcının belirlediği sıra korunur
+            (false, false) => std::cmp::Ordering::Equal,
+            (true, true) => {
+                // İkisi de çalışıyorsa process name'e göre alfabetik sırala
+                a.process_name
+                    .to_lowercase()
+                    .cmp(&b.process_name.to_lowercase())
+            }
+        }
+    });
+
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pinned_store::PinnedStore;
+    use crate::window_source::{FixtureFrame, FixtureWindowSource, ProcessInfo, WS_VISIBLE};
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    const NOTEPAD: &str = "C:\\Windows\\notepad.exe";
+    const CODE: &str = "C:\\Program Files\\Microsoft VS Code\\Code.exe";
+    const SPOTIFY: &str = "C:\\Users\\arui\\AppData\\Roaming\\Spotify\\Spotify.exe";
+
+    fn window(hwnd: isize, process_id: u32, class_name: &str, title: &str) -> RawWindow {
+        RawWindow {
+            hwnd,
+            process_id,
+            class_name: class_name.to_string(),
+            title: title.to_string(),
+            style: WS_CAPTION | WS_VISIBLE,
+            is_visible: true,
+            ..Default::default()
+        }
+    }
+
+    fn process(name: &str, executable_path: &str) -> ProcessInfo {
+        ProcessInfo {
+            name: name.to_string(),
+            executable_path: executable_path.to_string(),
+        }
+    }
+
+    fn source(windows: Vec<RawWindow>, foreground: isize) -> FixtureWindowSource {
+        let processes = HashMap::from([
+            (10, process("notepad.exe", NOTEPAD)),
+            (20, process("Code.exe", CODE)),
+            (30, process("explorer.exe", "C:\\Windows\\explorer.exe")),
+        ]);
+        FixtureWindowSource::new(vec![FixtureFrame {
+            windows,
+            processes,
+            foreground,
+        }])
+    }
+
+    /// Boş bir Windows pinned klasörü ve verilen sırayla pinlenmiş bir ARUI store'u
+    fn pinned(dir: &TempDir, apps: &[(&str, &str)]) -> PinnedIndex {
+        let store_path = dir.path().join("pinned.json");
+        let mut store = PinnedStore::load(&store_path).unwrap();
+        for (path, name) in apps {
+            store.pin(path, Some(name.to_string()), None);
+        }
+        store.save().unwrap();
+        PinnedIndex::new(dir.path().join("TaskBar")).with_store(store_path)
+    }
+
+    fn titles(items: &[TaskbarItem]) -> Vec<&str> {
+        items.iter().map(|item| item.title.as_str()).collect()
+    }
+
+    #[test]
+    fn classifies_running_windows() {
+        let dir = TempDir::new().unwrap();
+        let source = source(
+            vec![
+                window(1, 10, "Notepad", "notes.txt - Notepad"),
+                window(2, 20, "Chrome_WidgetWin_1", "main.rs - Visual Studio Code"),
+            ],
+            2,
+        );
+
+        let items = collect_taskbar_items(&source, &pinned(&dir, &[]), &RuleSet::defaults());
+
+        assert_eq!(
+            titles(&items),
+            ["main.rs - Visual Studio Code", "notes.txt - Notepad"]
+        );
+        let code = &items[0];
+        assert_eq!(code.hwnd, WindowHandle::from_raw(2));
+        assert_eq!(code.executable_path, CODE);
+        assert!(code.is_focused && code.is_running && code.has_taskbar_button);
+        assert_eq!(code.display_location, "taskbar");
+        assert_eq!(code.item_type, "running");
+        assert_eq!(code.window_state, "normal");
+        assert!(!items[1].is_focused);
+    }
+
+    #[test]
+    fn skips_windows_that_never_reach_the_taskbar() {
+        let dir = TempDir::new().unwrap();
+        let mut child = window(3, 10, "Notepad", "child");
+        child.parent = 1;
+        let mut hidden = window(4, 10, "Hidden", "hidden");
+        hidden.style = 0;
+        let source = source(
+            vec![
+                window(1, 10, "Notepad", "notes.txt - Notepad"),
+                child,
+                hidden,
+                window(5, 30, "Shell_TrayWnd", ""),
+                window(6, 30, "Progman", "Program Manager"),
+                // Process'i bilinmeyen pencereler atlanır
+                window(7, 99, "Unknown", "unknown"),
+            ],
+            0,
+        );
+
+        let items = collect_taskbar_items(&source, &pinned(&dir, &[]), &RuleSet::defaults());
+
+        assert_eq!(titles(&items), ["notes.txt - Notepad"]);
+    }
+
+    #[test]
+    fn minimized_windows_stay_on_the_taskbar() {
+        let dir = TempDir::new().unwrap();
+        let mut minimized = window(1, 10, "Notepad", "notes.txt - Notepad");
+        minimized.is_visible = false;
+        minimized.is_minimized = true;
+        let source = source(vec![minimized], 0);
+
+        let items = collect_taskbar_items(&source, &pinned(&dir, &[]), &RuleSet::defaults());
+
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].window_state, "minimized");
+        assert_eq!(items[0].display_location, "taskbar");
+    }
+
+    #[test]
+    fn tool_windows_go_to_the_tray() {
+        let dir = TempDir::new().unwrap();
+        let mut tool = window(1, 20, "Chrome_WidgetWin_1", "Code helper");
+        tool.ex_style = WS_EX_TOOLWINDOW;
+        let source = source(vec![tool], 0);
+
+        let items = collect_taskbar_items(&source, &pinned(&dir, &[]), &RuleSet::defaults());
+
+        assert_eq!(items.len(), 1);
+        assert!(items[0].is_tray_icon && !items[0].has_taskbar_button);
+        assert_eq!(items[0].display_location, "tray");
+    }
+
+    #[test]
+    fn adds_pinned_apps_that_are_not_running() {
+        let dir = TempDir::new().unwrap();
+        let source = source(vec![window(1, 10, "Notepad", "notes.txt - Notepad")], 0);
+
+        // Çalışan Notepad path'i büyük/küçük harf farkıyla pinli
+        let pinned = pinned(
+            &dir,
+            &[("c:/windows/NOTEPAD.EXE", "Notepad"), (SPOTIFY, "Spotify")],
+        );
+        let items = collect_taskbar_items(&source, &pinned, &RuleSet::defaults());
+
+        assert_eq!(titles(&items), ["notes.txt - Notepad", "Spotify"]);
+        let notepad = &items[0];
+        assert!(notepad.is_pinned && notepad.is_running);
+        assert_eq!(notepad.item_type, "both");
+
+        let spotify = &items[1];
+        assert_eq!(spotify.hwnd, WindowHandle::NULL);
+        assert_eq!(spotify.executable_path, SPOTIFY);
+        assert_eq!(spotify.window_state, "pinned_only");
+        assert_eq!(spotify.item_type, "pinned");
+        assert!(spotify.is_pinned && !spotify.is_running);
+        assert_eq!(spotify.display_location, "taskbar");
+    }
+
+    #[test]
+    fn pinned_only_items_keep_the_store_order() {
+        let dir = TempDir::new().unwrap();
+        let source = source(vec![window(1, 10, "Notepad", "notes.txt - Notepad")], 0);
+        let pinned = pinned(
+            &dir,
+            &[
+                (SPOTIFY, "Spotify"),
+                (CODE, "Visual Studio Code"),
+                ("C:\\Tools\\Alacritty.exe", "Alacritty"),
+            ],
+        );
+
+        let items = collect_taskbar_items(&source, &pinned, &RuleSet::defaults());
+        assert_eq!(
+            titles(&items),
+            [
+                "notes.txt - Notepad",
+                "Spotify",
+                "Visual Studio Code",
+                "Alacritty"
+            ]
+        );
+
+        // Store'da Alacritty'yi başa taşı
+        let store_path = dir.path().join("pinned.json");
+        let mut store = PinnedStore::load(&store_path).unwrap();
+        store.reorder("C:\\Tools\\Alacritty.exe", 0).unwrap();
+        store.save().unwrap();
+        let pinned = PinnedIndex::new(dir.path().join("TaskBar")).with_store(store_path);
+
+        let items = collect_taskbar_items(&source, &pinned, &RuleSet::defaults());
+        assert_eq!(
+            titles(&items),
+            [
+                "notes.txt - Notepad",
+                "Alacritty",
+                "Spotify",
+                "Visual Studio Code"
+            ]
+        );
+    }
+
+    #[test]
+    fn follows_the_fixture_frames() {
+        let dir = TempDir::new().unwrap();
+        let processes = HashMap::from([(10, process("notepad.exe", NOTEPAD))]);
+        let source = FixtureWindowSource::new(vec![
+            FixtureFrame {
+                windows: vec![window(1, 10, "Notepad", "first")],
+                processes: processes.clone(),
+                foreground: 1,
+            },
+            FixtureFrame {
+                windows: vec![window(1, 10, "Notepad", "second")],
+                processes,
+                foreground: 0,
+            },
+        ]);
+        let pinned = pinned(&dir, &[]);
+
+        let first = collect_taskbar_items(&source, &pinned, &RuleSet::defaults());
+        assert!(source.advance());
+        let second = collect_taskbar_items(&source, &pinned, &RuleSet::defaults());
+        assert!(!source.advance());
+
+        assert_eq!(titles(&first), ["first"]);
+        assert!(first[0].is_focused);
+        assert_eq!(titles(&second), ["second"]);
+        assert!(!second[0].is_focused);
+    }
+}
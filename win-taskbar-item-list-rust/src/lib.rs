@@ -0,0 +1,591 @@
+//! Taskbar'daki pencereleri ve pinned uygulamaları izleyen yardımcı.
+//! Tek başına `win-taskbar-item-list` binary'si olarak ya da arui-native
+//! içinde `taskbar_items` alt sistemi olarak çalışır.
+
+mod diff;
+mod grouping;
+// Icon'ları sadece Win32 komutları üretir; diğer platformlarda bir kısmı kullanılmaz
+#[cfg_attr(not(windows), allow(dead_code))]
+mod icon_cache;
+#[cfg_attr(not(windows), allow(dead_code))]
+mod icon_encode;
+mod items;
+mod pinned;
+mod pinned_store;
+mod rules;
+#[cfg(windows)]
+mod win32_commands;
+#[cfg(windows)]
+mod win32_source;
+pub mod window_source;
+
+use arui_protocol::rpc::{self, RpcRequest};
+use arui_protocol::{
+    AruiError, Envelope, ErrorCode, Output, Result, TaskbarGroup, TaskbarGroupUpdate, TaskbarItem,
+    TaskbarUpdate, WindowHandle,
+};
+use clap::{ArgAction, Args, Parser, Subcommand};
+use diff::TaskbarDiffer;
+use grouping::TaskbarGrouper;
+use icon_cache::{executable_icon_id, icon_cache};
+use icon_encode::{default_icon_size, parse_icon_size, IconFormat, MaxSize};
+use items::collect_taskbar_items;
+use pinned::PinnedIndex;
+use pinned_store::{store_lock, PinnedStore};
+use rules::RuleSet;
+use serde::Deserialize;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::sync::mpsc;
+use tokio::time::{sleep, Duration};
+#[cfg(windows)]
+use win32_source::Win32WindowSource;
+use win_window_events::EventCoalescer;
+use window_source::{FixtureWindowSource, WindowSource};
+
+#[derive(Parser)]
+#[command(name = "win-taskbar-item-list")]
+#[command(about = "Windows Taskbar Item Monitor")]
+pub struct Cli {
+    #[command(subcommand)]
+    action: Option<Commands>,
+    /// Start ARUI's pinned list empty instead of copying the Windows taskbar pins
+    /// when it is first created
+    #[arg(long = "no-seed-from-windows", action = ArgAction::SetFalse, global = true)]
+    seed_from_windows: bool,
+}
+
+/// Serve modunda her komut aynı isimle bir JSON-RPC method'u olur
+/// (örn. `{"method": "minimize-window", "params": {"hwnd": 123}}`)
+#[derive(Subcommand, Deserialize)]
+#[serde(tag = "method", content = "params", rename_all = "kebab-case")]
+enum Commands {
+    /// Get icon for a specific HWND (base64 PNG by default)
+    GetHwndIcon {
+        /// Window handle (HWND), decimal or 0x-prefixed hex
+        #[arg(long)]
+        hwnd: WindowHandle,
+        /// Icon size in pixels (16, 24, 32, 48 or 256)
+        #[arg(long, default_value = "32", value_parser = parse_icon_size)]
+        #[serde(default = "default_icon_size")]
+        size: u32,
+        /// Output format
+        #[arg(long, value_enum, default_value_t = IconFormat::Png)]
+        #[serde(default)]
+        format: IconFormat,
+        /// Write the icon to this file instead of returning it as base64
+        #[arg(long)]
+        output: Option<String>,
+    },
+    /// Get icon for a specific executable path (base64 PNG by default)
+    GetExecutableIcon {
+        /// Path to the executable file
+        #[arg(long)]
+        path: String,
+        /// Icon size in pixels (16, 24, 32, 48 or 256)
+        #[arg(long, default_value = "32", value_parser = parse_icon_size)]
+        #[serde(default = "default_icon_size")]
+        size: u32,
+        /// Output format
+        #[arg(long, value_enum, default_value_t = IconFormat::Png)]
+        #[serde(default)]
+        format: IconFormat,
+        /// Write the icon to this file instead of returning it as base64
+        #[arg(long)]
+        output: Option<String>,
+    },
+    /// Get window screenshot as base64 PNG (max 256x256)
+    GetWindowScreenshot {
+        /// Window handle (HWND), decimal or 0x-prefixed hex
+        #[arg(long)]
+        hwnd: WindowHandle,
+        /// Maximum size as WIDTHxHEIGHT (e.g., 512x512)
+        #[arg(long, default_value = "256x256")]
+        #[serde(default)]
+        size: MaxSize,
+    },
+    /// Stream window thumbnails as newline-delimited JSON until stdin closes
+    #[serde(skip)]
+    StreamThumbnails {
+        /// Window handle (HWND); repeat to stream several windows
+        #[arg(long, required = true)]
+        hwnd: Vec<WindowHandle>,
+        /// Frames per second (1-30)
+        #[arg(long, default_value_t = 5, value_parser = clap::value_parser!(u32).range(1..=30))]
+        fps: u32,
+        /// Maximum size as WIDTHxHEIGHT (e.g., 512x512)
+        #[arg(long, default_value = "256x256")]
+        size: MaxSize,
+    },
+    /// Minimize a window by HWND
+    MinimizeWindow {
+        /// Window handle (HWND), decimal or 0x-prefixed hex
+        #[arg(long)]
+        hwnd: WindowHandle,
+    },
+    /// Maximize a window by HWND
+    MaximizeWindow {
+        /// Window handle (HWND), decimal or 0x-prefixed hex
+        #[arg(long)]
+        hwnd: WindowHandle,
+    },
+    /// Restore a window by HWND (from minimized/maximized state)
+    RestoreWindow {
+        /// Window handle (HWND), decimal or 0x-prefixed hex
+        #[arg(long)]
+        hwnd: WindowHandle,
+    },
+    /// Close a window by HWND
+    CloseWindow {
+        /// Window handle (HWND), decimal or 0x-prefixed hex
+        #[arg(long)]
+        hwnd: WindowHandle,
+    },
+    /// Focus a window by HWND (bring to foreground)
+    FocusWindow {
+        /// Window handle (HWND), decimal or 0x-prefixed hex
+        #[arg(long)]
+        hwnd: WindowHandle,
+    },
+    /// Unfocus a window by HWND (send to background)
+    UnfocusWindow {
+        /// Window handle (HWND), decimal or 0x-prefixed hex
+        #[arg(long)]
+        hwnd: WindowHandle,
+    },
+    /// Toggle focus state of a window by HWND
+    ToggleFocusWindow {
+        /// Window handle (HWND), decimal or 0x-prefixed hex
+        #[arg(long)]
+        hwnd: WindowHandle,
+    },
+    /// Start an executable file
+    StartExecutable {
+        /// Path to the executable file to start
+        #[arg(long)]
+        path: String,
+    },
+    /// Open Windows Start Menu
+    OpenStartMenu,
+    /// Remove all cached icons from memory and disk
+    ClearIconCache,
+    /// Pin an executable to ARUI's pinned list
+    PinExecutable {
+        /// Path to the executable file to pin
+        #[arg(long)]
+        path: String,
+        /// Display name (defaults to the executable's file name)
+        #[arg(long)]
+        name: Option<String>,
+        /// Position in the pinned list (defaults to the end)
+        #[arg(long)]
+        index: Option<usize>,
+    },
+    /// Remove an executable from ARUI's pinned list
+    UnpinItem {
+        /// Path to the pinned executable file
+        #[arg(long)]
+        path: String,
+    },
+    /// Move a pinned executable to a new position in ARUI's pinned list
+    ReorderPinned {
+        /// Path to the pinned executable file
+        #[arg(long)]
+        path: String,
+        /// New position in the pinned list
+        #[arg(long)]
+        index: usize,
+    },
+    /// Monitor taskbar items (default action)
+    #[serde(skip)]
+    Monitor(MonitorOptions),
+    /// Monitor taskbar items and accept newline-delimited JSON-RPC requests on stdin
+    #[serde(skip)]
+    Serve(MonitorOptions),
+}
+
+#[cfg(windows)]
+impl Commands {
+    /// Komutun üzerinde çalıştığı pencere (varsa)
+    fn window_handle(&self) -> Option<WindowHandle> {
+        match self {
+            Commands::GetHwndIcon { hwnd, .. }
+            | Commands::GetWindowScreenshot { hwnd, .. }
+            | Commands::MinimizeWindow { hwnd }
+            | Commands::MaximizeWindow { hwnd }
+            | Commands::RestoreWindow { hwnd }
+            | Commands::CloseWindow { hwnd }
+            | Commands::FocusWindow { hwnd }
+            | Commands::UnfocusWindow { hwnd }
+            | Commands::ToggleFocusWindow { hwnd } => Some(*hwnd),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Args, Clone, Debug, Default)]
+pub struct MonitorOptions {
+    /// Replay a recorded window fixture (JSON) instead of live windows
+    #[arg(long)]
+    pub fixture: Option<String>,
+    /// Emit only added/removed/updated changes instead of the full list.
+    /// Write "resync" to stdin to receive a full snapshot again.
+    #[arg(long)]
+    pub diff: bool,
+    /// Classification rules file (TOML or JSON) evaluated before the defaults
+    #[arg(long)]
+    pub rules: Option<String>,
+    /// Also emit "groups" updates with windows grouped by application
+    #[arg(long)]
+    pub grouped: bool,
+}
+
+/// Monitor döngüsünün çıktı ayarları
+#[derive(Clone, Copy, Default)]
+struct MonitorMode {
+    diff: bool,
+    grouped: bool,
+    // Serve modunda RPC komutlarına verilecek seed_from_windows değeri
+    serve: Option<bool>,
+}
+
+// Event kaynağı olmayan (ya da hook'u kurulamayan) monitor'ün tarama aralığı
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+// Event'lerle çalışırken hook'un bildirmediği değişiklikler (ör. pinned
+// store) için yapılan tam taramanın aralığı
+const RESCAN_INTERVAL: Duration = Duration::from_secs(5);
+// Art arda gelen event'ler bu kadar sessizlikten sonra tek yenilemede işlenir
+const EVENT_DEBOUNCE: Duration = Duration::from_millis(50);
+// Event'ler hiç kesilmese bile yenileme en fazla bu kadar gecikir
+const EVENT_MAX_DELAY: Duration = Duration::from_millis(250);
+
+struct TaskbarMonitor {
+    source: Box<dyn WindowSource>,
+    rules: RuleSet,
+    pinned: PinnedIndex,
+}
+
+impl TaskbarMonitor {
+    fn with_source(source: Box<dyn WindowSource>, rules: RuleSet) -> Self {
+        Self {
+            source,
+            rules,
+            pinned: PinnedIndex::windows_default().with_store(PinnedStore::default_path()),
+        }
+    }
+
+    fn get_current_taskbar_items(&mut self) -> Vec<TaskbarItem> {
+        // Pinned klasörü sadece değiştiğinde yeniden okunur
+        self.pinned.refresh();
+        let mut items = collect_taskbar_items(self.source.as_ref(), &self.pinned, &self.rules);
+        for item in &mut items {
+            item.icon_id = executable_icon_id(&item.executable_path);
+        }
+        items
+    }
+
+    async fn monitor_loop(
+        &mut self,
+        mode: MonitorMode,
+        out: Output,
+        mut input: mpsc::UnboundedReceiver<String>,
+    ) {
+        let mut differ = TaskbarDiffer::new();
+        let mut grouper = TaskbarGrouper::new();
+        let mut previous_groups: Option<Vec<TaskbarGroup>> = None;
+
+        // Tüketici "resync" satırı göndererek tam listeyi yeniden isteyebilir
+        let mut input_open = true;
+
+        // RPC yanıtları da monitor event'leriyle aynı yerden yazılır,
+        // böylece stdout'ta satırlar birbirine karışmaz
+        let (responses_tx, mut responses_rx) = mpsc::unbounded_channel::<serde_json::Value>();
+
+        // Kaynak event bildirebiliyorsa liste değişiklik olduğunda yenilenir
+        let mut events =
+            EventCoalescer::new(self.source.subscribe(), EVENT_DEBOUNCE, EVENT_MAX_DELAY);
+
+        loop {
+            let current_items = self.get_current_taskbar_items();
+
+            if mode.grouped {
+                let groups = grouper.group(&current_items, self.pinned.entries());
+                // Diff modunda gruplar sadece değiştiğinde gönderilir
+                if !mode.diff || previous_groups.as_ref() != Some(&groups) {
+                    let update = TaskbarGroupUpdate::new(groups.clone());
+                    out.emit(&update);
+                    previous_groups = Some(groups);
+                }
+            }
+
+            if mode.diff {
+                // Sadece değişiklikleri gönder
+                for update in differ.diff(current_items) {
+                    out.emit(&update);
+                }
+            } else {
+                // Tüm mevcut taskbar öğelerini listele
+                let update = TaskbarUpdate::new("list", current_items);
+                out.emit(&update);
+            }
+
+            // Scripted kaynaklarda bir sonraki anlık görüntüye geç
+            self.source.advance();
+
+            let interval = if events.is_active() {
+                RESCAN_INTERVAL
+            } else {
+                POLL_INTERVAL
+            };
+            let tick = sleep(interval);
+            tokio::pin!(tick);
+
+            loop {
+                tokio::select! {
+                    _ = &mut tick => break,
+                    _ = events.next_batch() => break,
+                    Some(response) = responses_rx.recv() => out.emit(&response),
+                    line = input.recv(), if input_open => match line {
+                        Some(line) if line.trim() == "resync" => {
+                            differ.request_resync();
+                            previous_groups = None;
+                            break;
+                        }
+                        Some(line) if !line.trim().is_empty() => {
+                            let Some(seed_from_windows) = mode.serve else {
+                                continue;
+                            };
+                            match RpcRequest::parse(&line) {
+                                Ok(request) if request.method == "resync" => {
+                                    differ.request_resync();
+                                    previous_groups = None;
+                                    let result =
+                                        Envelope::ok(serde_json::json!({ "action": "resync" }));
+                                    out.emit(&rpc::response(request.id, result.to_value()));
+                                    break;
+                                }
+                                Ok(request) => match request.command::<Commands>() {
+                                    Ok(command) => {
+                                        // Win32 çağrıları bloklayıcı; monitor döngüsünü durdurmasın
+                                        let responses_tx = responses_tx.clone();
+                                        tokio::task::spawn_blocking(move || {
+                                            let result =
+                                                Envelope::from(execute(command, seed_from_windows));
+                                            let response =
+                                                rpc::response(request.id, result.to_value());
+                                            let _ = responses_tx.send(response);
+                                        });
+                                    }
+                                    Err(error) => out.emit(&error),
+                                },
+                                Err(error) => out.emit(&error),
+                            }
+                        }
+                        Some(_) => {}
+                        // Girdi kapandıysa sadece zamanlayıcıyı bekle
+                        None => input_open = false,
+                    },
+                }
+            }
+        }
+    }
+}
+
+/// ARUI pinned store'unu yükle, değiştir ve kaydet
+fn update_pinned_store(
+    seed_from_windows: bool,
+    update: impl FnOnce(&mut PinnedStore) -> Result<serde_json::Value>,
+) -> Result<serde_json::Value> {
+    // Paralel RPC'ler birbirinin değişikliğini ezmesin
+    let _guard = store_lock()
+        .lock()
+        .map_err(|_| AruiError::new(ErrorCode::Internal, "Pinned store is unavailable"))?;
+    let mut store = PinnedStore::load(PinnedStore::default_path())?;
+
+    // Store ilk kez oluşturuluyorsa Windows'un pinned item'larıyla doldur;
+    // aksi halde ilk pin Windows'un tüm pinned item'larını gizlerdi
+    if seed_from_windows && !store.exists() {
+        store.seed_from(PinnedIndex::windows_default().entries());
+    }
+
+    let mut data = update(&mut store)?;
+    store.save()?;
+    data["pinned"] = serde_json::json!(store.items);
+    Ok(data)
+}
+
+/// Tek bir komutu çalıştırır ve `{ok, data | error}` zarfının içeriğini döner.
+/// Hem tek seferlik komutlar hem de serve modundaki RPC istekleri bunu kullanır.
+fn execute(command: Commands, seed_from_windows: bool) -> Result<serde_json::Value> {
+    match command {
+        Commands::ClearIconCache => {
+            // Icon önbelleğini temizle
+            let mut cache = icon_cache()
+                .lock()
+                .map_err(|_| AruiError::new(ErrorCode::Internal, "Icon cache is unavailable"))?;
+            Ok(serde_json::json!({
+                "action": "clear_icon_cache",
+                "removed": cache.clear()
+            }))
+        }
+        Commands::PinExecutable { path, name, index } => {
+            // ARUI pinned listesine ekle
+            update_pinned_store(seed_from_windows, |store| {
+                let index = store.pin(&path, name, index);
+                Ok(serde_json::json!({
+                    "path": path,
+                    "action": "pin",
+                    "index": index
+                }))
+            })
+        }
+        Commands::UnpinItem { path } => {
+            // ARUI pinned listesinden çıkar
+            update_pinned_store(seed_from_windows, |store| match store.unpin(&path) {
+                Some(_) => Ok(serde_json::json!({
+                    "path": path,
+                    "action": "unpin"
+                })),
+                None => Err(not_pinned(&path)),
+            })
+        }
+        Commands::ReorderPinned { path, index } => {
+            // Pinned item'ı yeni konumuna taşı
+            update_pinned_store(seed_from_windows, |store| {
+                match store.reorder(&path, index) {
+                    Some(index) => Ok(serde_json::json!({
+                        "path": path,
+                        "action": "reorder",
+                        "index": index
+                    })),
+                    None => Err(not_pinned(&path)),
+                }
+            })
+        }
+        Commands::Monitor(_) | Commands::Serve(_) | Commands::StreamThumbnails { .. } => {
+            Err(AruiError::new(
+                ErrorCode::Unsupported,
+                "Streaming modes are only available from the command line",
+            ))
+        }
+        // Pencere, icon ve başlatma komutları Win32 gerektirir
+        #[cfg(windows)]
+        command => win32_commands::execute(command),
+        #[cfg(not(windows))]
+        _ => Err(AruiError::new(
+            ErrorCode::Unsupported,
+            "Window commands are only available on Windows",
+        )),
+    }
+}
+
+fn not_pinned(path: &str) -> AruiError {
+    AruiError::new(
+        ErrorCode::NotFound,
+        format!("Executable is not pinned: {}", path),
+    )
+}
+
+/// Tek başına çalışan binary'nin giriş noktası
+pub async fn run(cli: Cli) {
+    // Varsayılan monitoring modu
+    let action = cli
+        .action
+        .unwrap_or_else(|| Commands::Monitor(MonitorOptions::default()));
+
+    let result = match action {
+        Commands::Monitor(options) => {
+            run_monitor(options, None, Output::stdout(), stdin_lines()).await
+        }
+        Commands::Serve(options) => {
+            // Monitor event'lerinin yanında stdin'den JSON-RPC isteklerini kabul et
+            let serve = Some(cli.seed_from_windows);
+            run_monitor(options, serve, Output::stdout(), stdin_lines()).await
+        }
+        #[cfg(windows)]
+        Commands::StreamThumbnails { hwnd, fps, size } => {
+            win32_commands::stream_thumbnails(hwnd, fps, size, Output::stdout(), stdin_lines())
+                .await;
+            Ok(())
+        }
+        command => {
+            // Hata durumunda çıkış kodu hatanın koduna göre belirlenir
+            let code = Envelope::from(execute(command, cli.seed_from_windows)).print();
+            std::process::exit(code);
+        }
+    };
+
+    // Monitor başlatılamadıysa hata zarfını yaz ve çık
+    if let Err(error) = result {
+        std::process::exit(Envelope::<()>::error(error).print());
+    }
+}
+
+/// stdin satırlarını bir kanala aktarır; stdin kapandığında kanal da kapanır
+pub fn stdin_lines() -> mpsc::UnboundedReceiver<String> {
+    let (tx, rx) = mpsc::unbounded_channel();
+    tokio::spawn(async move {
+        let mut lines = BufReader::new(tokio::io::stdin()).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            if tx.send(line).is_err() {
+                break;
+            }
+        }
+    });
+    rx
+}
+
+/// Pencereleri izler ve güncellemeleri `out`'a yazar. `serve` verilmişse
+/// `input`'tan gelen satırlar JSON-RPC isteği olarak çalıştırılır; her
+/// durumda "resync" satırı tam listeyi yeniden gönderir.
+pub async fn run_monitor(
+    options: MonitorOptions,
+    serve: Option<bool>,
+    out: Output,
+    input: mpsc::UnboundedReceiver<String>,
+) -> Result<()> {
+    // Kayıtlı pencere fixture'ı verilmediyse gerçek pencereler izlenir
+    let source: Box<dyn WindowSource> = match &options.fixture {
+        Some(path) => Box::new(
+            FixtureWindowSource::from_file(path)
+                .map_err(|error| AruiError::new(ErrorCode::InvalidArgument, error))?,
+        ),
+        #[cfg(windows)]
+        None => Box::new(Win32WindowSource::new()),
+        #[cfg(not(windows))]
+        None => {
+            return Err(AruiError::new(
+                ErrorCode::Unsupported,
+                "Live windows are only available on Windows; use --fixture",
+            ))
+        }
+    };
+
+    run_monitor_with_source(source, options, serve, out, input).await
+}
+
+/// `run_monitor` gibi, ama pencereleri verilen kaynaktan okur;
+/// `options.fixture` kullanılmaz. Testler burada `EventInjector`'a bağlı
+/// bir `FixtureWindowSource` verebilir.
+pub async fn run_monitor_with_source(
+    source: Box<dyn WindowSource>,
+    options: MonitorOptions,
+    serve: Option<bool>,
+    out: Output,
+    input: mpsc::UnboundedReceiver<String>,
+) -> Result<()> {
+    // Kullanıcı kurallarını yükle
+    let rules = match &options.rules {
+        Some(path) => RuleSet::load(path),
+        None => Ok(RuleSet::defaults()),
+    }
+    .map_err(|error| AruiError::new(ErrorCode::InvalidArgument, error))?;
+
+    let mut monitor = TaskbarMonitor::with_source(source, rules);
+    let mode = MonitorMode {
+        diff: options.diff,
+        grouped: options.grouped,
+        serve,
+    };
+    monitor.monitor_loop(mode, out, input).await;
+    Ok(())
+}
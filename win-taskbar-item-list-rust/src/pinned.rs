@@ -0,0 +1,369 @@
+use crate::pinned_store::PinnedStore;
+use lnk::ShellLink;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PinnedEntry {
+    pub display_name: String,
+    pub executable_path: String,
+}
+
+/// Executable path'lerini karşılaştırılabilir hale getirir:
+/// küçük harf, ters slash, `\\?\` öneki ve sondaki ayraçlar olmadan
+pub fn normalize_path(path: &str) -> String {
+    let path = path.trim().replace('/', "\\");
+    let path = path.strip_prefix("\\\\?\\").unwrap_or(&path);
+    path.trim_end_matches('\\').to_lowercase()
+}
+
+/// Pinned klasörünün önbelleğe alınmış indeksi. Klasörün mtime'ı
+/// değişmediği sürece .lnk dosyaları tekrar okunmaz. ARUI pinned store'u
+/// verilmişse ve dosya mevcutsa klasör yerine store kullanılır.
+pub struct PinnedIndex {
+    dir: PathBuf,
+    store: Option<PathBuf>,
+    mtime: (Option<SystemTime>, Option<SystemTime>),
+    entries: Vec<PinnedEntry>,
+    paths: HashSet<String>,
+}
+
+impl PinnedIndex {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        let mut index = Self {
+            dir: dir.into(),
+            store: None,
+            mtime: (None, None),
+            entries: Vec::new(),
+            paths: HashSet::new(),
+        };
+        index.rebuild();
+        index
+    }
+
+    /// Windows'un taskbar pinned klasörü:
+    /// %APPDATA%\Microsoft\Internet Explorer\Quick Launch\User Pinned\TaskBar
+    pub fn windows_pinned_dir() -> PathBuf {
+        let appdata = std::env::var("APPDATA").unwrap_or_default();
+        Path::new(&appdata).join("Microsoft\\Internet Explorer\\Quick Launch\\User Pinned\\TaskBar")
+    }
+
+    pub fn windows_default() -> Self {
+        Self::new(Self::windows_pinned_dir())
+    }
+
+    /// ARUI pinned store'unu (JSON) klasörün önüne koy
+    pub fn with_store(mut self, store: impl Into<PathBuf>) -> Self {
+        self.store = Some(store.into());
+        self.rebuild();
+        self
+    }
+
+    /// Klasör veya store değiştiyse indeksi yeniden oluşturur; yeniden okunduysa true döner
+    pub fn refresh(&mut self) -> bool {
+        if self.current_mtime() == self.mtime {
+            return false;
+        }
+        self.rebuild();
+        true
+    }
+
+    pub fn entries(&self) -> &[PinnedEntry] {
+        &self.entries
+    }
+
+    pub fn is_pinned(&self, executable_path: &str) -> bool {
+        !executable_path.is_empty() && self.paths.contains(&normalize_path(executable_path))
+    }
+
+    fn path_mtime(path: &Path) -> Option<SystemTime> {
+        std::fs::metadata(path).and_then(|m| m.modified()).ok()
+    }
+
+    fn current_mtime(&self) -> (Option<SystemTime>, Option<SystemTime>) {
+        (
+            Self::path_mtime(&self.dir),
+            self.store.as_deref().and_then(Self::path_mtime),
+        )
+    }
+
+    fn rebuild(&mut self) {
+        self.mtime = self.current_mtime();
+        self.entries = match self.store.as_deref().map(PinnedStore::load) {
+            Some(Ok(store)) if store.exists() => store
+                .items
+                .iter()
+                .map(|item| PinnedEntry {
+                    display_name: item.name.clone(),
+                    executable_path: item.executable_path.clone(),
+                })
+                .collect(),
+            _ => Self::read_entries(&self.dir),
+        };
+        self.paths = self
+            .entries
+            .iter()
+            .filter(|entry| !entry.executable_path.is_empty())
+            .map(|entry| normalize_path(&entry.executable_path))
+            .collect();
+    }
+
+    fn read_entries(dir: &Path) -> Vec<PinnedEntry> {
+        let mut entries = Vec::new();
+
+        let Ok(dir_entries) = std::fs::read_dir(dir) else {
+            return entries;
+        };
+
+        let mut paths: Vec<PathBuf> = dir_entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "lnk"))
+            .collect();
+        // Dizin sırası dosya sistemine bağlı, sabit bir sıra kullan
+        paths.sort();
+
+        // Aynı executable'a giden birden fazla shortcut tek item olur; isim sırasında ilki kalır
+        let mut seen = HashSet::new();
+
+        for path in paths {
+            let Some(name) = path.file_stem() else {
+                continue;
+            };
+
+            // Shortcut dosyasını oku ve target path'ini al
+            let executable_path = match ShellLink::open(&path) {
+                Ok(shell_link) => {
+                    if let Some(link_info) = shell_link.link_info() {
+                        if let Some(path) = link_info.local_base_path() {
+                            path.clone()
+                        } else {
+                            String::new()
+                        }
+                    } else {
+                        String::new()
+                    }
+                }
+                Err(_) => String::new(),
+            };
+
+            if !executable_path.is_empty() && !seen.insert(normalize_path(&executable_path)) {
+                continue;
+            }
+
+            entries.push(PinnedEntry {
+                display_name: name.to_string_lossy().to_string(),
+                executable_path,
+            });
+        }
+
+        entries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// Hedefi `LinkInfo.LocalBasePath` içinde olan minimal bir .lnk (MS-SHLLINK).
+    /// `target` yoksa shortcut bir shell öğesini (ör. Store uygulaması) gösterir.
+    fn shortcut(target: Option<&str>) -> Vec<u8> {
+        const HAS_LINK_INFO: u32 = 0x02;
+        const IS_UNICODE: u32 = 0x80;
+        const CLSID: u128 = 0x4600_0000_0000_00c0_0000_0000_0002_1401;
+
+        let mut data = Vec::new();
+        data.extend(0x4cu32.to_le_bytes());
+        data.extend(CLSID.to_le_bytes());
+        let flags = if target.is_some() { HAS_LINK_INFO } else { 0 } | IS_UNICODE;
+        data.extend(flags.to_le_bytes());
+        // Dosya öznitelikleri, zamanlar, boyut ve icon index
+        data.extend([0u8; 36]);
+        // SW_SHOWNORMAL, hotkey ve ayrılmış alanlar
+        data.extend(1u32.to_le_bytes());
+        data.extend([0u8; 12]);
+        assert_eq!(data.len(), 0x4c);
+
+        if let Some(target) = target {
+            // VolumeID: boyut, DRIVE_FIXED, seri no, etiket offset'i ve boş etiket
+            let mut volume_id = Vec::new();
+            for value in [17u32, 3, 0x1234_5678, 16] {
+                volume_id.extend(value.to_le_bytes());
+            }
+            volume_id.push(0);
+
+            let header_size = 0x1cu32;
+            let local_base_path = header_size + volume_id.len() as u32;
+            let common_path_suffix = local_base_path + target.len() as u32 + 1;
+            let size = common_path_suffix + 1;
+            for value in [
+                size,
+                header_size,
+                1, // VolumeIDAndLocalBasePath
+                header_size,
+                local_base_path,
+                0,
+                common_path_suffix,
+            ] {
+                data.extend(value.to_le_bytes());
+            }
+            data.extend(volume_id);
+            data.extend(target.as_bytes());
+            data.push(0);
+            data.push(0);
+        }
+
+        // TerminalBlock
+        data.extend(0u32.to_le_bytes());
+        data
+    }
+
+    fn pinned_dir(shortcuts: &[(&str, Option<&str>)]) -> TempDir {
+        let dir = TempDir::new().unwrap();
+        for (name, target) in shortcuts {
+            std::fs::write(dir.path().join(format!("{}.lnk", name)), shortcut(*target)).unwrap();
+        }
+        dir
+    }
+
+    fn entry(name: &str, path: &str) -> PinnedEntry {
+        PinnedEntry {
+            display_name: name.to_string(),
+            executable_path: path.to_string(),
+        }
+    }
+
+    #[test]
+    fn normalize_path_ignores_case_separators_and_prefixes() {
+        let expected = "c:\\program files\\app\\app.exe";
+        for path in [
+            "C:\\Program Files\\App\\App.exe",
+            "c:/program files/app/APP.EXE",
+            "\\\\?\\C:\\Program Files\\App\\App.exe",
+            "  C:\\Program Files\\App\\App.exe  ",
+        ] {
+            assert_eq!(normalize_path(path), expected, "{}", path);
+        }
+        assert_eq!(normalize_path("C:\\Tools\\"), "c:\\tools");
+        assert_eq!(normalize_path("C:/Tools//"), "c:\\tools");
+    }
+
+    #[test]
+    fn reads_shortcut_targets_in_name_order() {
+        let dir = pinned_dir(&[
+            (
+                "Spotify",
+                Some("C:\\Users\\arui\\AppData\\Roaming\\Spotify\\Spotify.exe"),
+            ),
+            ("Notepad", Some("C:\\Windows\\notepad.exe")),
+        ]);
+        std::fs::write(dir.path().join("desktop.ini"), "[.ShellClassInfo]").unwrap();
+
+        let index = PinnedIndex::new(dir.path());
+
+        assert_eq!(
+            index.entries(),
+            [
+                entry("Notepad", "C:\\Windows\\notepad.exe"),
+                entry(
+                    "Spotify",
+                    "C:\\Users\\arui\\AppData\\Roaming\\Spotify\\Spotify.exe"
+                ),
+            ]
+        );
+        assert!(index.is_pinned("c:/windows/NOTEPAD.EXE"));
+        assert!(!index.is_pinned("C:\\Windows\\explorer.exe"));
+        assert!(!index.is_pinned(""));
+    }
+
+    #[test]
+    fn shortcuts_without_a_target_are_kept_by_name() {
+        let dir = pinned_dir(&[("Microsoft Store", None)]);
+        std::fs::write(dir.path().join("Broken.lnk"), b"not a shortcut").unwrap();
+
+        let index = PinnedIndex::new(dir.path());
+
+        assert_eq!(
+            index.entries(),
+            [entry("Broken", ""), entry("Microsoft Store", "")]
+        );
+        assert!(!index.is_pinned(""));
+    }
+
+    #[test]
+    fn duplicate_targets_are_listed_once() {
+        let dir = pinned_dir(&[
+            ("Notepad", Some("C:\\Windows\\notepad.exe")),
+            ("Text Editor", Some("C:\\WINDOWS\\NOTEPAD.EXE")),
+            ("Store A", None),
+            ("Store B", None),
+        ]);
+
+        let index = PinnedIndex::new(dir.path());
+
+        assert_eq!(
+            index.entries(),
+            [
+                entry("Notepad", "C:\\Windows\\notepad.exe"),
+                entry("Store A", ""),
+                entry("Store B", ""),
+            ]
+        );
+    }
+
+    #[test]
+    fn missing_folder_has_no_entries() {
+        let dir = TempDir::new().unwrap();
+
+        let index = PinnedIndex::new(dir.path().join("TaskBar"));
+
+        assert!(index.entries().is_empty());
+    }
+
+    #[test]
+    fn store_replaces_the_folder_once_it_exists() {
+        let dir = pinned_dir(&[("Notepad", Some("C:\\Windows\\notepad.exe"))]);
+        let store_path = dir.path().join("store").join("pinned.json");
+
+        let mut index = PinnedIndex::new(dir.path()).with_store(&store_path);
+        assert_eq!(
+            index.entries(),
+            [entry("Notepad", "C:\\Windows\\notepad.exe")]
+        );
+
+        let mut store = PinnedStore::load(&store_path).unwrap();
+        store.pin(
+            "C:\\Tools\\Alacritty.exe",
+            Some("Alacritty".to_string()),
+            None,
+        );
+        store.save().unwrap();
+
+        assert!(index.refresh());
+        assert_eq!(
+            index.entries(),
+            [entry("Alacritty", "C:\\Tools\\Alacritty.exe")]
+        );
+        assert!(!index.is_pinned("C:\\Windows\\notepad.exe"));
+        assert!(!index.refresh());
+    }
+
+    #[test]
+    fn refresh_rereads_a_changed_folder() {
+        let dir = pinned_dir(&[("Notepad", Some("C:\\Windows\\notepad.exe"))]);
+        let mut index = PinnedIndex::new(dir.path());
+        assert!(!index.refresh());
+
+        std::fs::write(
+            dir.path().join("Terminal.lnk"),
+            shortcut(Some("C:\\Tools\\wt.exe")),
+        )
+        .unwrap();
+
+        assert!(index.refresh());
+        assert!(index.is_pinned("C:\\Tools\\wt.exe"));
+        assert_eq!(index.entries().len(), 2);
+    }
+}
@@ -0,0 +1,269 @@
+use crate::pinned::{normalize_path, PinnedEntry};
+use arui_protocol::{AruiError, ErrorCode, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+// Her kaydın kendi geçici dosyası olsun diye artan sayaç
+static SAVE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct StoredPin {
+    pub name: String,
+    pub executable_path: String,
+}
+
+impl From<&PinnedEntry> for StoredPin {
+    fn from(entry: &PinnedEntry) -> Self {
+        Self {
+            name: entry.display_name.clone(),
+            executable_path: entry.executable_path.clone(),
+        }
+    }
+}
+
+/// ARUI'nin kendi pinned listesi. Sıralama explorer'dan bağımsız olarak
+/// JSON dosyasında saklanır.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct PinnedStore {
+    #[serde(skip)]
+    path: PathBuf,
+    pub items: Vec<StoredPin>,
+}
+
+/// Store'un yükle-değiştir-kaydet adımlarını sıralayan process genelindeki
+/// kilit. Serve modunda RPC'ler blocking thread'lerde paralel çalışır.
+pub fn store_lock() -> &'static Mutex<()> {
+    static LOCK: OnceLock<Mutex<()>> = OnceLock::new();
+    LOCK.get_or_init(|| Mutex::new(()))
+}
+
+impl PinnedStore {
+    /// %APPDATA%\ARUI\pinned.json
+    pub fn default_path() -> PathBuf {
+        let appdata = std::env::var("APPDATA").unwrap_or_default();
+        Path::new(&appdata).join("ARUI").join("pinned.json")
+    }
+
+    /// Store'u yükle; dosya yoksa boş bir store döner
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+
+        let mut store = if path.exists() {
+            let json = std::fs::read_to_string(&path).map_err(|e| {
+                AruiError::io(e, format!("Could not read pinned store {}", path.display()))
+            })?;
+            serde_json::from_str::<PinnedStore>(&json).map_err(|e| {
+                AruiError::new(
+                    ErrorCode::Io,
+                    format!("Invalid pinned store {}: {}", path.display(), e),
+                )
+            })?
+        } else {
+            PinnedStore::default()
+        };
+
+        store.path = path;
+        Ok(store)
+    }
+
+    pub fn exists(&self) -> bool {
+        self.path.exists()
+    }
+
+    /// Store henüz oluşturulmadıysa Windows pinned item'larıyla doldur
+    pub fn seed_from(&mut self, entries: &[PinnedEntry]) {
+        for entry in entries {
+            if entry.executable_path.is_empty() || self.position(&entry.executable_path).is_some() {
+                continue;
+            }
+            self.items.push(StoredPin::from(entry));
+        }
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| AruiError::io(e, format!("Could not create {}", parent.display())))?;
+        }
+
+        // Önce geçici dosyaya yaz, sonra taşı (yarım yazılmış dosya kalmasın)
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| AruiError::new(ErrorCode::Internal, e.to_string()))?;
+        let tmp_path = self.path.with_extension(format!(
+            "json.{}.{}.tmp",
+            std::process::id(),
+            SAVE_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        std::fs::write(&tmp_path, json)
+            .map_err(|e| AruiError::io(e, format!("Could not write {}", tmp_path.display())))?;
+        std::fs::rename(&tmp_path, &self.path).map_err(|e| {
+            let _ = std::fs::remove_file(&tmp_path);
+            AruiError::io(e, format!("Could not write {}", self.path.display()))
+        })
+    }
+
+    pub fn position(&self, executable_path: &str) -> Option<usize> {
+        let normalized = normalize_path(executable_path);
+        self.items
+            .iter()
+            .position(|item| normalize_path(&item.executable_path) == normalized)
+    }
+
+    /// Executable'ı pinle; zaten pinliyse mevcut konumunu döner
+    pub fn pin(
+        &mut self,
+        executable_path: &str,
+        name: Option<String>,
+        index: Option<usize>,
+    ) -> usize {
+        if let Some(position) = self.position(executable_path) {
+            return position;
+        }
+
+        let name = name.unwrap_or_else(|| {
+            Path::new(executable_path)
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_else(|| executable_path.to_string())
+        });
+
+        let index = index.unwrap_or(self.items.len()).min(self.items.len());
+        self.items.insert(
+            index,
+            StoredPin {
+                name,
+                executable_path: executable_path.to_string(),
+            },
+        );
+        index
+    }
+
+    pub fn unpin(&mut self, executable_path: &str) -> Option<StoredPin> {
+        self.position(executable_path)
+            .map(|position| self.items.remove(position))
+    }
+
+    /// Pinli item'ı yeni konumuna taşı; konum listenin sonuna sınırlanır
+    pub fn reorder(&mut self, executable_path: &str, index: usize) -> Option<usize> {
+        let position = self.position(executable_path)?;
+        let item = self.items.remove(position);
+        let index = index.min(self.items.len());
+        self.items.insert(index, item);
+        Some(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store_in(dir: &tempfile::TempDir) -> PinnedStore {
+        PinnedStore::load(dir.path().join("ARUI").join("pinned.json")).unwrap()
+    }
+
+    fn paths(store: &PinnedStore) -> Vec<&str> {
+        store
+            .items
+            .iter()
+            .map(|item| item.executable_path.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn pin_appends_inserts_and_ignores_duplicates() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = store_in(&dir);
+
+        assert_eq!(store.pin(r"C:\a.exe", Some("A".into()), None), 0);
+        assert_eq!(store.pin(r"C:\b.exe", Some("B".into()), None), 1);
+        assert_eq!(store.pin(r"C:\c.exe", Some("C".into()), Some(0)), 0);
+        // Listenin dışındaki konum sona sınırlanır
+        assert_eq!(store.pin(r"C:\d.exe", Some("D".into()), Some(99)), 3);
+
+        // Aynı executable (büyük/küçük harf farkıyla) tekrar eklenmez
+        assert_eq!(store.pin(r"c:\B.EXE", Some("Other".into()), Some(0)), 2);
+        assert_eq!(
+            paths(&store),
+            [r"C:\c.exe", r"C:\a.exe", r"C:\b.exe", r"C:\d.exe"]
+        );
+        assert_eq!(store.items[2].name, "B");
+    }
+
+    #[test]
+    fn unpin_removes_only_pinned_items() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = store_in(&dir);
+        store.pin(r"C:\a.exe", Some("A".into()), None);
+
+        assert_eq!(store.unpin(r"C:\missing.exe"), None);
+        assert_eq!(store.items.len(), 1);
+
+        let removed = store.unpin(r"C:\A.exe").unwrap();
+        assert_eq!(removed.executable_path, r"C:\a.exe");
+        assert!(store.items.is_empty());
+        assert_eq!(store.unpin(r"C:\a.exe"), None);
+    }
+
+    #[test]
+    fn reorder_clamps_out_of_range_index() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = store_in(&dir);
+        for path in [r"C:\a.exe", r"C:\b.exe", r"C:\c.exe"] {
+            store.pin(path, Some(path.into()), None);
+        }
+
+        assert_eq!(store.reorder(r"C:\c.exe", 0), Some(0));
+        assert_eq!(paths(&store), [r"C:\c.exe", r"C:\a.exe", r"C:\b.exe"]);
+
+        assert_eq!(store.reorder(r"C:\c.exe", 99), Some(2));
+        assert_eq!(paths(&store), [r"C:\a.exe", r"C:\b.exe", r"C:\c.exe"]);
+
+        assert_eq!(store.reorder(r"C:\missing.exe", 0), None);
+        assert_eq!(paths(&store), [r"C:\a.exe", r"C:\b.exe", r"C:\c.exe"]);
+    }
+
+    #[test]
+    fn changes_persist_across_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = store_in(&dir);
+        assert!(!store.exists());
+
+        store.pin(r"C:\a.exe", Some("A".into()), None);
+        store.pin(r"C:\b.exe", Some("B".into()), None);
+        store.reorder(r"C:\b.exe", 0);
+        store.save().unwrap();
+        assert!(store.exists());
+
+        let mut loaded = PinnedStore::load(dir.path().join("ARUI").join("pinned.json")).unwrap();
+        assert_eq!(loaded, store);
+
+        loaded.unpin(r"C:\a.exe");
+        loaded.save().unwrap();
+        assert_eq!(paths(&store_in(&dir)), [r"C:\b.exe"]);
+
+        // Geçici dosya arkada kalmaz
+        let files = std::fs::read_dir(dir.path().join("ARUI")).unwrap().count();
+        assert_eq!(files, 1);
+    }
+
+    #[test]
+    fn seed_skips_existing_and_empty_targets() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = store_in(&dir);
+        store.pin(r"C:\a.exe", Some("A".into()), None);
+
+        let entry = |name: &str, path: &str| PinnedEntry {
+            display_name: name.to_string(),
+            executable_path: path.to_string(),
+        };
+        store.seed_from(&[
+            entry("A again", r"C:\A.exe"),
+            entry("Folder", ""),
+            entry("B", r"C:\b.exe"),
+        ]);
+
+        assert_eq!(paths(&store), [r"C:\a.exe", r"C:\b.exe"]);
+    }
+}
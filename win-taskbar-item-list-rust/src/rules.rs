@@ -0,0 +1,532 @@
+use crate::window_source::RawWindow;
+use arui_protocol::MatchedRules;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+// Varsayılan kurallar; kullanıcı kuralları bunlardan önce değerlendirilir
+const DEFAULT_RULES: &str = include_str!("../rules/default.toml");
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleTarget {
+    /// Pencere listeye alınır mı; process'e bakılmadan, diğerlerinden önce karar verilir
+    Window,
+    Taskbar,
+    Tray,
+    /// Item'ın `is_tray_icon` alanı
+    TrayIcon,
+    System,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleEffect {
+    Include,
+    Exclude,
+}
+
+/// Style bitleri sayı olarak (0x80) ya da isim listesi olarak (["WS_EX_TOOLWINDOW"]) yazılabilir
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(untagged)]
+pub enum StyleBits {
+    Bits(u32),
+    Names(Vec<String>),
+}
+
+impl StyleBits {
+    fn resolve(&self) -> Result<u32, String> {
+        match self {
+            StyleBits::Bits(bits) => Ok(*bits),
+            StyleBits::Names(names) => names.iter().try_fold(0, |bits, name| {
+                style_bit(name)
+                    .map(|bit| bits | bit)
+                    .ok_or_else(|| format!("Unknown style bit: {}", name))
+            }),
+        }
+    }
+}
+
+fn style_bit(name: &str) -> Option<u32> {
+    let bit = match name {
+        "WS_POPUP" => 0x8000_0000,
+        "WS_CHILD" => 0x4000_0000,
+        "WS_MINIMIZE" => 0x2000_0000,
+        "WS_VISIBLE" => 0x1000_0000,
+        "WS_DISABLED" => 0x0800_0000,
+        "WS_MAXIMIZE" => 0x0100_0000,
+        "WS_CAPTION" => 0x00C0_0000,
+        "WS_BORDER" => 0x0080_0000,
+        "WS_DLGFRAME" => 0x0040_0000,
+        "WS_SYSMENU" => 0x0008_0000,
+        "WS_THICKFRAME" => 0x0004_0000,
+        "WS_MINIMIZEBOX" => 0x0002_0000,
+        "WS_MAXIMIZEBOX" => 0x0001_0000,
+        "WS_EX_TOPMOST" => 0x0000_0008,
+        "WS_EX_TRANSPARENT" => 0x0000_0020,
+        "WS_EX_TOOLWINDOW" => 0x0000_0080,
+        "WS_EX_WINDOWEDGE" => 0x0000_0100,
+        "WS_EX_APPWINDOW" => 0x0004_0000,
+        "WS_EX_LAYERED" => 0x0008_0000,
+        "WS_EX_NOREDIRECTIONBITMAP" => 0x0020_0000,
+        "WS_EX_NOACTIVATE" => 0x0800_0000,
+        _ => return None,
+    };
+    Some(bit)
+}
+
+/// Tek bir kural: tüm belirtilen koşullar sağlanırsa `effect` uygulanır
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Rule {
+    pub name: String,
+    pub target: RuleTarget,
+    pub effect: RuleEffect,
+    /// Class adı bunlardan biriyle birebir eşleşmeli
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub class: Vec<String>,
+    /// Class adı bunlardan birini içermeli
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub class_contains: Vec<String>,
+    /// Process adı bunlardan biriyle eşleşmeli (büyük/küçük harf duyarsız)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub process: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title_regex: Option<String>,
+    /// Pencerenin parent'ı (child ya da owned pencere) var mı
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub has_parent: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title_empty: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub style_all: Option<StyleBits>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub style_any: Option<StyleBits>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub style_none: Option<StyleBits>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ex_style_all: Option<StyleBits>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ex_style_any: Option<StyleBits>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ex_style_none: Option<StyleBits>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub visible: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub minimized: Option<bool>,
+}
+
+/// Kural dosyasının içeriği (TOML veya JSON)
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct RuleFile {
+    /// true ise varsayılan kurallar tamamen devre dışı kalır
+    #[serde(default)]
+    pub replace_defaults: bool,
+    #[serde(default)]
+    pub rules: Vec<Rule>,
+}
+
+impl RuleFile {
+    pub fn parse(contents: &str, is_json: bool) -> Result<Self, String> {
+        if is_json {
+            serde_json::from_str(contents).map_err(|e| format!("Invalid rules JSON: {}", e))
+        } else {
+            toml::from_str(contents).map_err(|e| format!("Invalid rules TOML: {}", e))
+        }
+    }
+}
+
+/// Derlenmiş kural: regex ve style maskeleri önceden çözümlenmiş
+struct CompiledRule {
+    rule: Rule,
+    title_regex: Option<Regex>,
+    process: Vec<String>,
+    style_all: u32,
+    style_any: u32,
+    style_none: u32,
+    ex_style_all: u32,
+    ex_style_any: u32,
+    ex_style_none: u32,
+}
+
+impl CompiledRule {
+    fn compile(rule: Rule) -> Result<Self, String> {
+        let resolve = |bits: &Option<StyleBits>| -> Result<u32, String> {
+            bits.as_ref()
+                .map(StyleBits::resolve)
+                .transpose()
+                .map(|bits| bits.unwrap_or(0))
+                .map_err(|e| format!("Rule '{}': {}", rule.name, e))
+        };
+
+        if rule.target == RuleTarget::Window && !rule.process.is_empty() {
+            return Err(format!(
+                "Rule '{}': window rules are decided before the process is known and cannot match on process",
+                rule.name
+            ));
+        }
+
+        let title_regex = match &rule.title_regex {
+            Some(pattern) => Some(
+                Regex::new(pattern)
+                    .map_err(|e| format!("Rule '{}': invalid title_regex: {}", rule.name, e))?,
+            ),
+            None => None,
+        };
+
+        Ok(Self {
+            title_regex,
+            process: rule.process.iter().map(|p| p.to_lowercase()).collect(),
+            style_all: resolve(&rule.style_all)?,
+            style_any: resolve(&rule.style_any)?,
+            style_none: resolve(&rule.style_none)?,
+            ex_style_all: resolve(&rule.ex_style_all)?,
+            ex_style_any: resolve(&rule.ex_style_any)?,
+            ex_style_none: resolve(&rule.ex_style_none)?,
+            rule,
+        })
+    }
+
+    fn matches(&self, window: &RawWindow, process_name: &str) -> bool {
+        let rule = &self.rule;
+
+        if !rule.class.is_empty() && !rule.class.contains(&window.class_name) {
+            return false;
+        }
+        if !rule.class_contains.is_empty()
+            && !rule
+                .class_contains
+                .iter()
+                .any(|c| window.class_name.contains(c.as_str()))
+        {
+            return false;
+        }
+        if !self.process.is_empty() && !self.process.contains(&process_name.to_lowercase()) {
+            return false;
+        }
+        if let Some(regex) = &self.title_regex {
+            if !regex.is_match(&window.title) {
+                return false;
+            }
+        }
+        if let Some(has_parent) = rule.has_parent {
+            if (window.parent != 0) != has_parent {
+                return false;
+            }
+        }
+        if let Some(title_empty) = rule.title_empty {
+            if window.title.is_empty() != title_empty {
+                return false;
+            }
+        }
+        if let Some(visible) = rule.visible {
+            if window.is_visible != visible {
+                return false;
+            }
+        }
+        if let Some(minimized) = rule.minimized {
+            if window.is_minimized != minimized {
+                return false;
+            }
+        }
+
+        bits_match(
+            window.style,
+            self.style_all,
+            self.style_any,
+            self.style_none,
+        ) && bits_match(
+            window.ex_style,
+            self.ex_style_all,
+            self.ex_style_any,
+            self.ex_style_none,
+        )
+    }
+}
+
+fn bits_match(value: u32, all: u32, any: u32, none: u32) -> bool {
+    (value & all) == all && (any == 0 || (value & any) != 0) && (value & none) == 0
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Classification {
+    pub is_definitely_taskbar: bool,
+    pub is_definitely_tray: bool,
+    pub is_tray_icon: bool,
+    pub is_system_window: bool,
+    pub matched_rules: MatchedRules,
+}
+
+/// Sıralı kural listesi. Her hedef (window, taskbar, tray, tray_icon, system) için eşleşen ilk
+/// kural sonucu belirler; hiçbir kural eşleşmezse sonuç false olur.
+pub struct RuleSet {
+    rules: Vec<CompiledRule>,
+}
+
+impl RuleSet {
+    pub fn defaults() -> Self {
+        Self::from_file(RuleFile::parse(DEFAULT_RULES, false).expect("default rules must parse"))
+            .expect("default rules must compile")
+    }
+
+    pub fn from_file(file: RuleFile) -> Result<Self, String> {
+        let rules = file
+            .rules
+            .into_iter()
+            .map(CompiledRule::compile)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { rules })
+    }
+
+    /// Kullanıcı kurallarını yükle; varsayılan kurallar (replace_defaults
+    /// verilmediyse) kullanıcı kurallarından sonra değerlendirilir
+    pub fn load(path: &str) -> Result<Self, String> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| format!("Could not read rules file {}: {}", path, e))?;
+        let is_json = path.to_lowercase().ends_with(".json");
+        let mut file = RuleFile::parse(&contents, is_json)?;
+
+        if !file.replace_defaults {
+            let defaults = RuleFile::parse(DEFAULT_RULES, false)?;
+            file.rules.extend(defaults.rules);
+        }
+
+        Self::from_file(file)
+    }
+
+    fn decide(
+        &self,
+        target: RuleTarget,
+        window: &RawWindow,
+        process_name: &str,
+    ) -> (bool, Option<String>) {
+        self.rules
+            .iter()
+            .filter(|compiled| compiled.rule.target == target)
+            .find(|compiled| compiled.matches(window, process_name))
+            .map(|compiled| {
+                (
+                    compiled.rule.effect == RuleEffect::Include,
+                    Some(compiled.rule.name.clone()),
+                )
+            })
+            .unwrap_or((false, None))
+    }
+
+    /// Pencerenin taskbar item listesine alınıp alınmayacağı
+    pub fn lists(&self, window: &RawWindow) -> bool {
+        self.decide(RuleTarget::Window, window, "").0
+    }
+
+    pub fn classify(&self, window: &RawWindow, process_name: &str) -> Classification {
+        let (is_definitely_taskbar, taskbar) =
+            self.decide(RuleTarget::Taskbar, window, process_name);
+        let (is_definitely_tray, tray) = self.decide(RuleTarget::Tray, window, process_name);
+        let (is_tray_icon, _) = self.decide(RuleTarget::TrayIcon, window, process_name);
+        let (is_system_window, system) = self.decide(RuleTarget::System, window, process_name);
+
+        Classification {
+            is_definitely_taskbar,
+            is_definitely_tray,
+            is_tray_icon,
+            is_system_window,
+            matched_rules: MatchedRules {
+                taskbar,
+                tray,
+                system,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::items::classify_window;
+    use crate::pinned::PinnedIndex;
+    use crate::window_source::{FixtureFrame, FixtureWindowSource, ProcessInfo};
+    use std::collections::HashMap;
+
+    const GOLDEN: &str = include_str!("../rules/golden.toml");
+
+    #[derive(Deserialize)]
+    struct GoldenFile {
+        cases: Vec<GoldenCase>,
+    }
+
+    #[derive(Deserialize)]
+    struct GoldenCase {
+        name: String,
+        class: String,
+        title: String,
+        process: String,
+        #[serde(default)]
+        style: Option<StyleBits>,
+        #[serde(default)]
+        ex_style: Option<StyleBits>,
+        #[serde(default)]
+        visible: bool,
+        #[serde(default)]
+        minimized: bool,
+        #[serde(default)]
+        has_parent: bool,
+        display_location: String,
+        #[serde(default)]
+        tray_icon: Option<bool>,
+        #[serde(default)]
+        system: bool,
+    }
+
+    fn bits(bits: &Option<StyleBits>) -> u32 {
+        bits.as_ref().map_or(0, |bits| bits.resolve().unwrap())
+    }
+
+    #[test]
+    fn default_rules_match_the_golden_corpus() {
+        let golden: GoldenFile = toml::from_str(GOLDEN).unwrap();
+        let rules = RuleSet::defaults();
+        let pinned_dir = tempfile::tempdir().unwrap();
+        let pinned = PinnedIndex::new(pinned_dir.path());
+
+        let mut failures = Vec::new();
+        for case in &golden.cases {
+            let window = RawWindow {
+                hwnd: 1,
+                process_id: 100,
+                class_name: case.class.clone(),
+                title: case.title.clone(),
+                style: bits(&case.style),
+                ex_style: bits(&case.ex_style),
+                is_visible: case.visible,
+                is_minimized: case.minimized,
+                parent: if case.has_parent { 2 } else { 0 },
+                ..Default::default()
+            };
+            if !rules.lists(&window) {
+                if case.display_location != "unlisted" {
+                    failures.push(format!(
+                        "{}: expected {}, got unlisted",
+                        case.name, case.display_location
+                    ));
+                }
+                continue;
+            }
+
+            let source = FixtureWindowSource::new(vec![FixtureFrame {
+                windows: vec![window.clone()],
+                processes: HashMap::from([(
+                    100,
+                    ProcessInfo {
+                        name: case.process.clone(),
+                        executable_path: String::new(),
+                    },
+                )]),
+                foreground: 0,
+            }]);
+
+            let item = classify_window(&window, &source, &pinned, &rules, 0).unwrap();
+            if item.display_location != case.display_location
+                || item.is_system_window != case.system
+                || case
+                    .tray_icon
+                    .is_some_and(|tray_icon| item.is_tray_icon != tray_icon)
+            {
+                failures.push(format!(
+                    "{}: expected {} (system: {}, tray icon: {:?}), got {} (system: {}, tray icon: {}) via {:?}",
+                    case.name,
+                    case.display_location,
+                    case.system,
+                    case.tray_icon,
+                    item.display_location,
+                    item.is_system_window,
+                    item.is_tray_icon,
+                    item.matched_rules
+                ));
+            }
+        }
+
+        assert!(failures.is_empty(), "{}", failures.join("\n"));
+    }
+
+    fn spotify_window() -> RawWindow {
+        RawWindow {
+            class_name: "Chrome_WidgetWin_0".to_string(),
+            title: "Spotify Premium".to_string(),
+            style: 0x00C0_0000 | 0x1000_0000,
+            is_visible: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn user_rules_take_precedence_over_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("rules.toml");
+        std::fs::write(
+            &path,
+            r#"
+            [[rules]]
+            name = "hide-spotify"
+            target = "taskbar"
+            effect = "exclude"
+            process = ["spotify.exe"]
+            "#,
+        )
+        .unwrap();
+        let rules = RuleSet::load(path.to_str().unwrap()).unwrap();
+
+        let classification = rules.classify(&spotify_window(), "Spotify.exe");
+        assert!(!classification.is_definitely_taskbar);
+        assert_eq!(
+            classification.matched_rules.taskbar.as_deref(),
+            Some("hide-spotify")
+        );
+
+        // Kullanıcı kuralı eşleşmeyen pencerelerde varsayılanlar devam eder
+        let classification = rules.classify(&spotify_window(), "chrome.exe");
+        assert!(classification.is_definitely_taskbar);
+        assert!(rules.lists(&spotify_window()));
+    }
+
+    #[test]
+    fn replace_defaults_drops_the_default_rules() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("rules.json");
+        std::fs::write(
+            &path,
+            r#"{
+                "replace_defaults": true,
+                "rules": [
+                    { "name": "list-all", "target": "window", "effect": "include" }
+                ]
+            }"#,
+        )
+        .unwrap();
+        let rules = RuleSet::load(path.to_str().unwrap()).unwrap();
+
+        let classification = rules.classify(&spotify_window(), "chrome.exe");
+        assert!(!classification.is_definitely_taskbar);
+        assert_eq!(classification.matched_rules.taskbar, None);
+        assert!(rules.lists(&RawWindow {
+            class_name: "Shell_TrayWnd".to_string(),
+            ..Default::default()
+        }));
+    }
+
+    #[test]
+    fn window_rules_cannot_match_on_process() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("rules.toml");
+        std::fs::write(
+            &path,
+            r#"
+            [[rules]]
+            name = "list-spotify"
+            target = "window"
+            effect = "include"
+            process = ["spotify.exe"]
+            "#,
+        )
+        .unwrap();
+
+        assert!(RuleSet::load(path.to_str().unwrap()).is_err());
+    }
+}
@@ -0,0 +1,734 @@
+//! Pencereler, icon'lar ve uygulama başlatma üzerinde çalışan Win32 komutları
+
+use crate::icon_cache::{
+    cache_key, cached_icon, content_icon_id, executable_icon_id, fnv1a64, store_icon,
+};
+use crate::icon_encode::{encode_icon, encode_rgba, validate_icon_size, IconFormat, MaxSize};
+use crate::Commands;
+use arui_protocol::{AruiError, ErrorCode, Output, Result, WindowHandle};
+use base64::{engine::general_purpose, Engine as _};
+use std::collections::HashMap;
+use std::ptr::null_mut;
+use tokio::sync::mpsc;
+use tokio::time::Duration;
+use winapi::shared::windef::HWND;
+use winapi::um::shellapi::{
+    ExtractIconW, SHGetFileInfoW, ShellExecuteW, SHFILEINFOW, SHGFI_ICON, SHGFI_LARGEICON,
+};
+use winapi::um::wingdi::*;
+use winapi::um::winuser::*;
+
+// Icon boyutu okunamazsa kullanılacak boyut
+const ICON_SIZE: i32 = 32;
+
+fn get_window_icon(hwnd: WindowHandle, size: u32, format: IconFormat) -> Option<(String, Vec<u8>)> {
+    unsafe {
+        let hwnd = hwnd.as_raw() as HWND;
+
+        // Büyük icon'u al
+        let mut hicon =
+            SendMessageW(hwnd, WM_GETICON, ICON_BIG as usize, 0) as winapi::shared::windef::HICON;
+
+        // Eğer büyük icon yoksa küçük icon'u dene
+        if hicon.is_null() {
+            hicon = SendMessageW(hwnd, WM_GETICON, ICON_SMALL as usize, 0)
+                as winapi::shared::windef::HICON;
+        }
+
+        // Hala icon yoksa class icon'unu dene
+        if hicon.is_null() {
+            hicon = GetClassLongPtrW(hwnd, GCLP_HICON) as winapi::shared::windef::HICON;
+        }
+
+        // Son çare olarak küçük class icon'unu dene
+        if hicon.is_null() {
+            hicon = GetClassLongPtrW(hwnd, GCLP_HICONSM) as winapi::shared::windef::HICON;
+        }
+
+        if hicon.is_null() {
+            return None;
+        }
+
+        // Aynı icon'a sahip pencereler aynı id'yi paylaşır
+        let (pixels, width, height) = render_icon_rgba(hicon)?;
+        let icon_id = content_icon_id(&pixels);
+        let key = cache_key(&icon_id, size, format.extension());
+        let data = match cached_icon(&key) {
+            Some(data) => data,
+            None => {
+                let data = encode_icon(pixels, width, height, size, format)?;
+                store_icon(&key, data.clone());
+                data
+            }
+        };
+
+        Some((icon_id, data))
+    }
+}
+
+fn get_window_screenshot_as_base64(
+    hwnd: WindowHandle,
+    size: MaxSize,
+) -> Option<(String, i32, i32)> {
+    let (pixels, width, height) = capture_window_rgba(hwnd, size)?;
+    let png_data = encode_rgba(pixels, width as u32, height as u32, IconFormat::Png)?;
+    Some((general_purpose::STANDARD.encode(&png_data), width, height))
+}
+
+/// Pencereyi `size` sınırları içinde oranını koruyarak RGBA piksellere çizer
+fn capture_window_rgba(hwnd: WindowHandle, size: MaxSize) -> Option<(Vec<u8>, i32, i32)> {
+    unsafe {
+        let hwnd = hwnd.as_raw() as HWND;
+        let max_width = size.width.min(i32::MAX as u32) as i32;
+        let max_height = size.height.min(i32::MAX as u32) as i32;
+
+        // Pencere boyutlarını al
+        let mut rect = std::mem::zeroed::<winapi::shared::windef::RECT>();
+        if GetWindowRect(hwnd, &mut rect) == 0 {
+            return None;
+        }
+
+        let window_width = rect.right - rect.left;
+        let window_height = rect.bottom - rect.top;
+
+        if window_width <= 0 || window_height <= 0 {
+            return None;
+        }
+
+        // Maksimum boyutları belirle
+        let (target_width, target_height) = if window_width > window_height {
+            let ratio = max_width as f32 / window_width as f32;
+            let new_height = (window_height as f32 * ratio) as i32;
+            if new_height > max_height {
+                let ratio = max_height as f32 / window_height as f32;
+                ((window_width as f32 * ratio) as i32, max_height)
+            } else {
+                (max_width, new_height)
+            }
+        } else {
+            let ratio = max_height as f32 / window_height as f32;
+            let new_width = (window_width as f32 * ratio) as i32;
+            if new_width > max_width {
+                let ratio = max_width as f32 / window_width as f32;
+                (max_width, (window_height as f32 * ratio) as i32)
+            } else {
+                (new_width, max_height)
+            }
+        };
+
+        // Device context'ler oluştur
+        let hdc_screen = GetDC(null_mut());
+        let hdc_window = GetDC(hwnd);
+        let hdc_mem = CreateCompatibleDC(hdc_screen);
+        let hdc_scaled = CreateCompatibleDC(hdc_screen);
+
+        // Bitmap'ler oluştur
+        let hbitmap = CreateCompatibleBitmap(hdc_screen, window_width, window_height);
+        let hbitmap_scaled = CreateCompatibleBitmap(hdc_screen, target_width, target_height);
+
+        let old_bitmap = SelectObject(hdc_mem, hbitmap as *mut _);
+        let old_bitmap_scaled = SelectObject(hdc_scaled, hbitmap_scaled as *mut _);
+
+        // Pencereyi çiz
+        if PrintWindow(hwnd, hdc_mem, 0x00000002) != 0 {
+            // PW_RENDERFULLCONTENT
+            // Boyutlandır
+            SetStretchBltMode(hdc_scaled, 4); // HALFTONE
+            StretchBlt(
+                hdc_scaled,
+                0,
+                0,
+                target_width,
+                target_height,
+                hdc_mem,
+                0,
+                0,
+                window_width,
+                window_height,
+                0x00CC0020, // SRCCOPY
+            );
+
+            // Bitmap verisini al
+            let mut bmi: BITMAPINFOHEADER = std::mem::zeroed();
+            bmi.biSize = std::mem::size_of::<BITMAPINFOHEADER>() as u32;
+            bmi.biWidth = target_width;
+            bmi.biHeight = -target_height; // Top-down DIB
+            bmi.biPlanes = 1;
+            bmi.biBitCount = 32;
+            bmi.biCompression = BI_RGB;
+
+            let mut buffer: Vec<u8> = vec![0; (target_width * target_height * 4) as usize];
+
+            if GetDIBits(
+                hdc_scaled,
+                hbitmap_scaled,
+                0,
+                target_height as u32,
+                buffer.as_mut_ptr() as *mut _,
+                &bmi as *const _ as *mut _,
+                DIB_RGB_COLORS,
+            ) != 0
+            {
+                // BGRA'dan RGBA'ya çevir
+                for i in (0..buffer.len()).step_by(4) {
+                    buffer.swap(i, i + 2); // B ve R'yi swap et
+                    buffer[i + 3] = 255; // Alpha kanalını opaque yap
+                }
+
+                // Cleanup
+                SelectObject(hdc_mem, old_bitmap);
+                SelectObject(hdc_scaled, old_bitmap_scaled);
+                DeleteObject(hbitmap as *mut _);
+                DeleteObject(hbitmap_scaled as *mut _);
+                DeleteDC(hdc_mem);
+                DeleteDC(hdc_scaled);
+                ReleaseDC(hwnd, hdc_window);
+                ReleaseDC(null_mut(), hdc_screen);
+
+                return Some((buffer, target_width, target_height));
+            }
+        }
+
+        // Cleanup on failure
+        SelectObject(hdc_mem, old_bitmap);
+        SelectObject(hdc_scaled, old_bitmap_scaled);
+        DeleteObject(hbitmap as *mut _);
+        DeleteObject(hbitmap_scaled as *mut _);
+        DeleteDC(hdc_mem);
+        DeleteDC(hdc_scaled);
+        ReleaseDC(hwnd, hdc_window);
+        ReleaseDC(null_mut(), hdc_screen);
+
+        None
+    }
+}
+
+fn get_executable_icon(
+    executable_path: &str,
+    size: u32,
+    format: IconFormat,
+) -> Option<(String, Vec<u8>)> {
+    // Executable değişmediği sürece icon önbellekten gelir
+    let icon_id = executable_icon_id(executable_path);
+    let key = cache_key(&icon_id, size, format.extension());
+    if let Some(data) = cached_icon(&key) {
+        return Some((icon_id, data));
+    }
+
+    let (pixels, width, height) = extract_executable_icon_rgba(executable_path)?;
+    let data = encode_icon(pixels, width, height, size, format)?;
+    store_icon(&key, data.clone());
+    Some((icon_id, data))
+}
+
+fn extract_executable_icon_rgba(executable_path: &str) -> Option<(Vec<u8>, u32, u32)> {
+    unsafe {
+        // Executable dosyasından icon çıkar
+        let path_wide: Vec<u16> = executable_path
+            .encode_utf16()
+            .chain(std::iter::once(0))
+            .collect();
+
+        // İlk olarak SHGetFileInfoW ile dene (daha güvenilir)
+        let mut shfi: SHFILEINFOW = std::mem::zeroed();
+        let result = SHGetFileInfoW(
+            path_wide.as_ptr(),
+            0,
+            &mut shfi,
+            std::mem::size_of::<SHFILEINFOW>() as u32,
+            SHGFI_ICON | SHGFI_LARGEICON,
+        );
+
+        let hicon = if result != 0 && !shfi.hIcon.is_null() {
+            shfi.hIcon
+        } else {
+            // SHGetFileInfoW başarısız olduysa ExtractIconW dene (UWP uygulamaları için daha iyi)
+            let hicon = ExtractIconW(
+                null_mut(),
+                path_wide.as_ptr(),
+                0, // İlk icon'u al
+            );
+
+            if hicon.is_null() || hicon as isize == 1 {
+                // ExtractIconW başarısız olduysa LoadImageW dene
+                let hicon = LoadImageW(
+                    null_mut(),
+                    path_wide.as_ptr(),
+                    IMAGE_ICON,
+                    32,
+                    32,
+                    LR_LOADFROMFILE,
+                ) as winapi::shared::windef::HICON;
+
+                if hicon.is_null() {
+                    return None;
+                }
+                hicon
+            } else {
+                hicon
+            }
+        };
+
+        let pixels = render_icon_rgba(hicon);
+        DestroyIcon(hicon);
+        pixels
+    }
+}
+
+/// Icon'u kendi boyutunda RGBA piksellere çizer (HICON'u yok etmez).
+/// Piksellerle birlikte genişlik ve yüksekliği döner.
+unsafe fn render_icon_rgba(hicon: winapi::shared::windef::HICON) -> Option<(Vec<u8>, u32, u32)> {
+    // Icon bilgilerini al
+    let mut icon_info: ICONINFO = std::mem::zeroed();
+    if GetIconInfo(hicon, &mut icon_info) == 0 {
+        return None;
+    }
+
+    // Icon'un gerçek boyutunu bitmap'ten al; monochrome icon'larda
+    // mask bitmap'i AND ve XOR maskelerini üst üste tutar
+    let mut bitmap: BITMAP = std::mem::zeroed();
+    let (source_bitmap, mask_only) = if icon_info.hbmColor.is_null() {
+        (icon_info.hbmMask, true)
+    } else {
+        (icon_info.hbmColor, false)
+    };
+    GetObjectW(
+        source_bitmap as *mut _,
+        std::mem::size_of::<BITMAP>() as i32,
+        &mut bitmap as *mut _ as *mut _,
+    );
+    let width = if bitmap.bmWidth > 0 {
+        bitmap.bmWidth
+    } else {
+        ICON_SIZE
+    };
+    let height = match (bitmap.bmHeight > 0, mask_only) {
+        (true, true) => bitmap.bmHeight / 2,
+        (true, false) => bitmap.bmHeight,
+        (false, _) => ICON_SIZE,
+    };
+
+    let hdc = GetDC(null_mut());
+    let hdc_target = CreateCompatibleDC(hdc);
+    let hbitmap_target = CreateCompatibleBitmap(hdc, width, height);
+    let old_bitmap = SelectObject(hdc_target, hbitmap_target as *mut _);
+
+    // Icon'u çiz
+    DrawIconEx(
+        hdc_target,
+        0,
+        0,
+        hicon,
+        width,
+        height,
+        0,
+        null_mut(),
+        0x0003,
+    ); // DI_NORMAL | DI_COMPAT
+
+    // Bitmap verisini al
+    let mut bmi: BITMAPINFOHEADER = std::mem::zeroed();
+    bmi.biSize = std::mem::size_of::<BITMAPINFOHEADER>() as u32;
+    bmi.biWidth = width;
+    bmi.biHeight = -height; // Top-down DIB
+    bmi.biPlanes = 1;
+    bmi.biBitCount = 32;
+    bmi.biCompression = BI_RGB;
+
+    let mut buffer: Vec<u8> = vec![0; (width * height * 4) as usize];
+
+    let copied = GetDIBits(
+        hdc_target,
+        hbitmap_target,
+        0,
+        height as u32,
+        buffer.as_mut_ptr() as *mut _,
+        &bmi as *const _ as *mut _,
+        DIB_RGB_COLORS,
+    ) != 0;
+
+    // Cleanup
+    SelectObject(hdc_target, old_bitmap);
+    DeleteObject(hbitmap_target as *mut _);
+    DeleteDC(hdc_target);
+    ReleaseDC(null_mut(), hdc);
+    if !icon_info.hbmColor.is_null() {
+        DeleteObject(icon_info.hbmColor as *mut _);
+    }
+    DeleteObject(icon_info.hbmMask as *mut _);
+
+    if !copied {
+        return None;
+    }
+
+    // BGRA'dan RGBA'ya çevir
+    for i in (0..buffer.len()).step_by(4) {
+        buffer.swap(i, i + 2); // B ve R'yi swap et
+    }
+    Some((buffer, width as u32, height as u32))
+}
+
+// ShowWindow'un dönüş değeri pencerenin önceki görünürlüğüdür, hata
+// değil; handle execute'ta doğrulandığı için bu çağrılar başarısız olmaz
+fn minimize_window(hwnd: WindowHandle) -> Result<()> {
+    unsafe {
+        ShowWindow(hwnd.as_raw() as HWND, SW_MINIMIZE);
+    }
+    Ok(())
+}
+
+fn maximize_window(hwnd: WindowHandle) -> Result<()> {
+    unsafe {
+        ShowWindow(hwnd.as_raw() as HWND, SW_MAXIMIZE);
+    }
+    Ok(())
+}
+
+fn restore_window(hwnd: WindowHandle) -> Result<()> {
+    unsafe {
+        ShowWindow(hwnd.as_raw() as HWND, SW_RESTORE);
+    }
+    Ok(())
+}
+
+fn close_window(hwnd: WindowHandle) -> Result<()> {
+    unsafe {
+        let hwnd = hwnd.as_raw() as HWND;
+        // WM_CLOSE mesajı gönder (graceful close)
+        if PostMessageW(hwnd, WM_CLOSE, 0, 0) == 0 {
+            return Err(AruiError::last_os_error(
+                ErrorCode::Failed,
+                "Could not send close message to window",
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn focus_window(hwnd: WindowHandle) -> Result<()> {
+    unsafe {
+        let hwnd = hwnd.as_raw() as HWND;
+        // Pencereyi önplana getir; Windows'un foreground kilidi buna izin vermeyebilir
+        if SetForegroundWindow(hwnd) == 0 {
+            return Err(AruiError::new(
+                ErrorCode::AccessDenied,
+                "Windows refused to bring the window to the foreground",
+            ));
+        }
+        // Eğer minimize edilmişse restore et
+        if IsIconic(hwnd) != 0 {
+            ShowWindow(hwnd, SW_RESTORE);
+        }
+        Ok(())
+    }
+}
+
+fn unfocus_window(hwnd: WindowHandle) -> Result<()> {
+    unsafe {
+        let hwnd = hwnd.as_raw() as HWND;
+        let current_foreground = GetForegroundWindow();
+
+        // Eğer bu pencere şu anda focus'taysa, bir sonraki pencereye geç;
+        // değilse zaten unfocus durumdadır
+        if hwnd == current_foreground {
+            // Alt+Tab benzeri davranış için
+            keybd_event(0x12, 0, 0, 0); // Alt tuşunu bas
+            keybd_event(0x09, 0, 0, 0); // Tab tuşunu bas
+            keybd_event(0x09, 0, 0x02, 0); // Tab tuşunu bırak
+            keybd_event(0x12, 0, 0x02, 0); // Alt tuşunu bırak
+        }
+        Ok(())
+    }
+}
+
+fn toggle_focus_window(hwnd: WindowHandle) -> Result<()> {
+    unsafe {
+        let current_foreground = GetForegroundWindow();
+
+        if hwnd.as_raw() as HWND == current_foreground {
+            // Şu anda focus'ta, unfocus et
+            unfocus_window(hwnd)
+        } else {
+            // Focus'ta değil, focus et
+            focus_window(hwnd)
+        }
+    }
+}
+
+/// Handle hâlâ var olan bir pencereye mi ait
+fn is_valid_window(hwnd: WindowHandle) -> bool {
+    !hwnd.is_null() && unsafe { IsWindow(hwnd.as_raw() as HWND) != 0 }
+}
+
+fn start_executable(executable_path: &str) -> Result<()> {
+    unsafe {
+        // Executable path'i wide string'e çevir
+        let path_wide: Vec<u16> = executable_path
+            .encode_utf16()
+            .chain(std::iter::once(0))
+            .collect();
+
+        // ShellExecuteW kullanarak executable'ı başlat
+        let result = ShellExecuteW(
+            null_mut(),         // hwnd
+            std::ptr::null(),   // lpOperation (default: "open")
+            path_wide.as_ptr(), // lpFile
+            std::ptr::null(),   // lpParameters
+            std::ptr::null(),   // lpDirectory
+            1,                  // nShowCmd (SW_SHOWNORMAL)
+        );
+
+        // ShellExecuteW 32'den büyük bir değer döndürürse başarılı;
+        // değilse dönen değer hata kodudur
+        let result = result as isize;
+        if result > 32 {
+            return Ok(());
+        }
+        let message = format!("Could not start executable: {}", executable_path);
+        match result {
+            // SE_ERR_NOASSOC, SE_ERR_ASSOCINCOMPLETE
+            31 | 27 => {
+                Err(AruiError::new(ErrorCode::Unsupported, message).with_win32(result as u32))
+            }
+            // ERROR_FILE_NOT_FOUND, ERROR_PATH_NOT_FOUND, ERROR_ACCESS_DENIED ...
+            _ => Err(AruiError::win32(result as u32, message)),
+        }
+    }
+}
+
+fn open_start_menu() -> Result<()> {
+    unsafe {
+        // Windows Start Menu'yu açmak için Windows tuşuna basma simülasyonu
+        // VK_LWIN (Left Windows key) = 0x5B
+        const VK_LWIN: u8 = 0x5B;
+
+        // Tuşa bas
+        keybd_event(VK_LWIN, 0, 0, 0);
+        // Tuşu bırak
+        keybd_event(VK_LWIN, 0, 2, 0); // KEYEVENTF_KEYUP = 2
+    }
+    Ok(())
+}
+
+/// Icon verisini base64 olarak ya da `--output` verildiyse dosyaya yazarak döner
+fn icon_response(
+    mut data: serde_json::Value,
+    icon_id: String,
+    icon: Vec<u8>,
+    size: u32,
+    format: IconFormat,
+    output: Option<String>,
+) -> Result<serde_json::Value> {
+    if let Some(output) = output {
+        std::fs::write(&output, &icon)
+            .map_err(|e| AruiError::io(e, format!("Could not write {}", output)))?;
+        data["output"] = serde_json::json!(output);
+    } else {
+        data["icon_base64"] = serde_json::json!(general_purpose::STANDARD.encode(&icon));
+    }
+
+    data["icon_id"] = serde_json::json!(icon_id);
+    data["format"] = serde_json::json!(format.name());
+    data["width"] = serde_json::json!(size);
+    data["height"] = serde_json::json!(size);
+    Ok(data)
+}
+
+/// Pencere üzerinde çalışan basit komutların ortak yanıtı
+fn window_action(
+    hwnd: WindowHandle,
+    action: &str,
+    result: Result<()>,
+) -> Result<serde_json::Value> {
+    result.map(|_| serde_json::json!({ "hwnd": hwnd, "action": action }))
+}
+
+/// Pencere, icon ve başlatma komutlarını çalıştırır; diğer komutlar
+/// `crate::execute`'ta işlenir
+pub fn execute(command: Commands) -> Result<serde_json::Value> {
+    // Kapanmış veya geçersiz handle'lar başka bir pencereye gitmesin
+    if let Some(hwnd) = command.window_handle() {
+        if !is_valid_window(hwnd) {
+            // ERROR_INVALID_WINDOW_HANDLE
+            return Err(AruiError::win32(
+                1400,
+                format!(
+                    "Window handle {} does not refer to an existing window",
+                    hwnd
+                ),
+            ));
+        }
+    }
+
+    match command {
+        Commands::GetHwndIcon {
+            hwnd,
+            size,
+            format,
+            output,
+        } => {
+            // Icon alma modu
+            let size = validate_icon_size(size)
+                .map_err(|e| AruiError::new(ErrorCode::InvalidArgument, e))?;
+            let (icon_id, icon) = get_window_icon(hwnd, size, format).ok_or_else(|| {
+                AruiError::new(
+                    ErrorCode::NotFound,
+                    "Could not retrieve icon for the specified HWND",
+                )
+            })?;
+            let data = serde_json::json!({ "hwnd": hwnd });
+            icon_response(data, icon_id, icon, size, format, output)
+        }
+        Commands::GetExecutableIcon {
+            path,
+            size,
+            format,
+            output,
+        } => {
+            // Executable icon alma modu
+            let size = validate_icon_size(size)
+                .map_err(|e| AruiError::new(ErrorCode::InvalidArgument, e))?;
+            let (icon_id, icon) = get_executable_icon(&path, size, format).ok_or_else(|| {
+                AruiError::new(
+                    ErrorCode::NotFound,
+                    "Could not retrieve icon for the specified executable path",
+                )
+            })?;
+            let data = serde_json::json!({ "path": path });
+            icon_response(data, icon_id, icon, size, format, output)
+        }
+        Commands::GetWindowScreenshot { hwnd, size } => {
+            // Pencere screenshot alma modu
+            let (base64_screenshot, width, height) = get_window_screenshot_as_base64(hwnd, size)
+                .ok_or_else(|| {
+                    AruiError::new(
+                        ErrorCode::Failed,
+                        "Could not capture screenshot for the specified HWND",
+                    )
+                })?;
+            Ok(serde_json::json!({
+                "hwnd": hwnd,
+                "screenshot_base64": base64_screenshot,
+                "format": "PNG",
+                "max_size": size.to_string(),
+                "width": width,
+                "height": height
+            }))
+        }
+        Commands::MinimizeWindow { hwnd } => window_action(hwnd, "minimize", minimize_window(hwnd)),
+        Commands::MaximizeWindow { hwnd } => window_action(hwnd, "maximize", maximize_window(hwnd)),
+        Commands::RestoreWindow { hwnd } => window_action(hwnd, "restore", restore_window(hwnd)),
+        Commands::CloseWindow { hwnd } => window_action(hwnd, "close", close_window(hwnd)),
+        Commands::FocusWindow { hwnd } => window_action(hwnd, "focus", focus_window(hwnd)),
+        Commands::UnfocusWindow { hwnd } => window_action(hwnd, "unfocus", unfocus_window(hwnd)),
+        Commands::ToggleFocusWindow { hwnd } => {
+            window_action(hwnd, "toggle_focus", toggle_focus_window(hwnd))
+        }
+        Commands::StartExecutable { path } => {
+            // Executable'ı başlat
+            start_executable(&path)?;
+            Ok(serde_json::json!({
+                "path": path,
+                "action": "start"
+            }))
+        }
+        Commands::OpenStartMenu => {
+            // Windows Start Menu'yu aç
+            open_start_menu()?;
+            Ok(serde_json::json!({ "action": "open_start_menu" }))
+        }
+        _ => Err(AruiError::new(
+            ErrorCode::InvalidArgument,
+            "Not a window command",
+        )),
+    }
+}
+
+/// Pencerelerin küçük resimlerini `fps` hızında yakalayıp sadece değişen
+/// frame'leri gönderir. Girdi kapandığında veya tüm pencereler
+/// kapandığında durur.
+pub async fn stream_thumbnails(
+    hwnds: Vec<WindowHandle>,
+    fps: u32,
+    size: MaxSize,
+    out: Output,
+    mut input: mpsc::UnboundedReceiver<String>,
+) {
+    // Her pencere için son gönderilen frame'in hash'i
+    let mut last_hashes: HashMap<WindowHandle, u64> = hwnds.iter().map(|hwnd| (*hwnd, 0)).collect();
+    let mut sequence: u64 = 0;
+
+    let mut ticker = tokio::time::interval(Duration::from_millis(1000 / fps as u64));
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+    loop {
+        tokio::select! {
+            _ = ticker.tick() => {}
+            line = input.recv() => match line {
+                Some(_) => continue,
+                // Girdi kapandı, tüketici gitti
+                None => break,
+            },
+        }
+
+        for hwnd in &hwnds {
+            let Some(last_hash) = last_hashes.get_mut(hwnd) else {
+                continue;
+            };
+
+            if !is_valid_window(*hwnd) {
+                // Pencere kapandı; bir kez bildir ve listeden çıkar
+                let response = serde_json::json!({
+                    "action": "closed",
+                    "hwnd": hwnd
+                });
+                out.emit(&response);
+                last_hashes.remove(hwnd);
+                continue;
+            }
+
+            // Yakalama GDI çağrılarıyla bloklar; runtime'ın thread'ini tutmasın
+            let window = *hwnd;
+            let capture = tokio::task::spawn_blocking(move || capture_window_rgba(window, size))
+                .await
+                .ok()
+                .flatten();
+            // Pencere şu an yakalanamıyor (ör. küçültülmüş); bu frame'i atla
+            let Some((pixels, width, height)) = capture else {
+                continue;
+            };
+
+            // Piksel verisi değişmediyse frame'i atla
+            let hash = fnv1a64(&pixels);
+            if hash == *last_hash {
+                continue;
+            }
+            *last_hash = hash;
+
+            let Some(png_data) = encode_rgba(pixels, width as u32, height as u32, IconFormat::Png)
+            else {
+                continue;
+            };
+
+            sequence += 1;
+            let response = serde_json::json!({
+                "action": "frame",
+                "hwnd": hwnd,
+                "sequence": sequence,
+                "screenshot_base64": general_purpose::STANDARD.encode(&png_data),
+                "format": "PNG",
+                "width": width,
+                "height": height,
+                "timestamp": std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap()
+                    .as_millis() as u64
+            });
+            out.emit(&response);
+        }
+
+        if last_hashes.is_empty() {
+            break;
+        }
+    }
+}
@@ -0,0 +1,173 @@
+use crate::window_source::{ProcessInfo, RawWindow, WindowPlacement, WindowRect, WindowSource};
+use std::ffi::OsString;
+use std::os::windows::ffi::OsStringExt;
+use std::ptr::null_mut;
+use win_window_events::{WinEventHook, WindowEvents};
+use winapi::shared::windef::HWND;
+use winapi::um::handleapi::CloseHandle;
+use winapi::um::processthreadsapi::OpenProcess;
+use winapi::um::psapi::{GetModuleBaseNameW, GetModuleFileNameExW};
+use winapi::um::winuser::*;
+
+/// EnumWindows tabanlı gerçek pencere kaynağı
+pub struct Win32WindowSource {
+    // Abone olunduğunda kurulan hook; kaynakla birlikte kaldırılır
+    hook: Option<WinEventHook>,
+}
+
+impl Win32WindowSource {
+    pub fn new() -> Self {
+        Self { hook: None }
+    }
+
+    pub fn get_window_text(hwnd: HWND) -> String {
+        unsafe {
+            let length = GetWindowTextLengthW(hwnd);
+            if length == 0 {
+                return String::new();
+            }
+
+            let mut buffer: Vec<u16> = vec![0; (length + 1) as usize];
+            let result = GetWindowTextW(hwnd, buffer.as_mut_ptr(), buffer.len() as i32);
+            if result > 0 {
+                buffer.truncate(result as usize);
+                OsString::from_wide(&buffer).to_string_lossy().into_owned()
+            } else {
+                String::new()
+            }
+        }
+    }
+
+    pub fn get_class_name(hwnd: HWND) -> String {
+        unsafe {
+            let mut buffer: [u16; 256] = [0; 256];
+            let result = GetClassNameW(hwnd, buffer.as_mut_ptr(), buffer.len() as i32);
+            if result > 0 {
+                let slice = &buffer[..result as usize];
+                OsString::from_wide(slice).to_string_lossy().into_owned()
+            } else {
+                String::new()
+            }
+        }
+    }
+
+    pub fn get_process_name(process_id: u32) -> String {
+        unsafe {
+            let handle = OpenProcess(0x0400 | 0x0010, 0, process_id); // PROCESS_QUERY_INFORMATION | PROCESS_VM_READ
+            if handle.is_null() {
+                return String::new();
+            }
+
+            let mut buffer: [u16; 260] = [0; 260];
+
+            if GetModuleBaseNameW(handle, null_mut(), buffer.as_mut_ptr(), buffer.len() as u32) > 0
+            {
+                let slice = &buffer[..buffer.iter().position(|&x| x == 0).unwrap_or(buffer.len())];
+                let result = OsString::from_wide(slice).to_string_lossy().into_owned();
+                CloseHandle(handle);
+                result
+            } else {
+                CloseHandle(handle);
+                String::new()
+            }
+        }
+    }
+
+    pub fn get_executable_path(process_id: u32) -> String {
+        unsafe {
+            let handle = OpenProcess(0x0400 | 0x1000, 0, process_id); // PROCESS_QUERY_INFORMATION | PROCESS_QUERY_LIMITED_INFORMATION
+            if handle.is_null() {
+                return String::new();
+            }
+
+            let mut buffer: [u16; 260] = [0; 260];
+
+            if GetModuleFileNameExW(handle, null_mut(), buffer.as_mut_ptr(), buffer.len() as u32)
+                > 0
+            {
+                let slice = &buffer[..buffer.iter().position(|&x| x == 0).unwrap_or(buffer.len())];
+                let result = OsString::from_wide(slice).to_string_lossy().into_owned();
+                CloseHandle(handle);
+                result
+            } else {
+                CloseHandle(handle);
+                String::new()
+            }
+        }
+    }
+
+    fn read_window(hwnd: HWND) -> RawWindow {
+        unsafe {
+            let mut process_id = 0u32;
+            GetWindowThreadProcessId(hwnd, &mut process_id);
+
+            // Window placement bilgisi al
+            let mut placement = std::mem::zeroed::<WINDOWPLACEMENT>();
+            placement.length = std::mem::size_of::<WINDOWPLACEMENT>() as u32;
+            GetWindowPlacement(hwnd, &mut placement);
+
+            RawWindow {
+                hwnd: hwnd as isize,
+                parent: GetParent(hwnd) as isize,
+                process_id,
+                class_name: Self::get_class_name(hwnd),
+                title: Self::get_window_text(hwnd),
+                style: GetWindowLongW(hwnd, GWL_STYLE) as u32,
+                ex_style: GetWindowLongW(hwnd, GWL_EXSTYLE) as u32,
+                is_visible: IsWindowVisible(hwnd) != 0,
+                is_minimized: IsIconic(hwnd) != 0,
+                is_maximized: IsZoomed(hwnd) != 0,
+                placement: WindowPlacement {
+                    show_cmd: placement.showCmd,
+                    normal_rect: WindowRect {
+                        left: placement.rcNormalPosition.left,
+                        top: placement.rcNormalPosition.top,
+                        right: placement.rcNormalPosition.right,
+                        bottom: placement.rcNormalPosition.bottom,
+                    },
+                },
+            }
+        }
+    }
+}
+
+impl WindowSource for Win32WindowSource {
+    fn windows(&self) -> Vec<RawWindow> {
+        let mut windows = Vec::new();
+        unsafe {
+            EnumWindows(
+                Some(enum_windows_proc),
+                &mut windows as *mut Vec<RawWindow> as isize,
+            );
+        }
+        windows
+    }
+
+    fn process_info(&self, process_id: u32) -> ProcessInfo {
+        ProcessInfo {
+            name: Self::get_process_name(process_id),
+            executable_path: Self::get_executable_path(process_id),
+        }
+    }
+
+    fn foreground_window(&self) -> isize {
+        unsafe { GetForegroundWindow() as isize }
+    }
+
+    /// SetWinEventHook kurulamazsa monitor polling'e döner
+    fn subscribe(&mut self) -> Option<WindowEvents> {
+        if self.hook.is_some() {
+            return None;
+        }
+        let (hook, events) = WinEventHook::start().ok()?;
+        self.hook = Some(hook);
+        Some(events)
+    }
+}
+
+unsafe extern "system" fn enum_windows_proc(hwnd: HWND, lparam: isize) -> i32 {
+    let windows = &mut *(lparam as *mut Vec<RawWindow>);
+    windows.push(Win32WindowSource::read_window(hwnd));
+
+    1 // TRUE
+}
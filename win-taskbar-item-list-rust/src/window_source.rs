@@ -0,0 +1,164 @@
+use serde::{Deserialize, Serialize};
+use std::cell::Cell;
+use std::collections::HashMap;
+use win_window_events::WindowEvents;
+
+// Sınıflandırma için gereken Win32 style bitleri (winapi'ye bağımlı olmadan)
+pub const WS_CAPTION: u32 = 0x00C0_0000;
+pub const WS_VISIBLE: u32 = 0x1000_0000;
+pub const WS_EX_TOOLWINDOW: u32 = 0x0000_0080;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct WindowRect {
+    pub left: i32,
+    pub top: i32,
+    pub right: i32,
+    pub bottom: i32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct WindowPlacement {
+    pub show_cmd: u32,
+    pub normal_rect: WindowRect,
+}
+
+/// Bir top-level pencere hakkında işlenmemiş bilgiler
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(default)]
+pub struct RawWindow {
+    pub hwnd: isize,
+    pub parent: isize,
+    pub process_id: u32,
+    pub class_name: String,
+    pub title: String,
+    pub style: u32,
+    pub ex_style: u32,
+    pub is_visible: bool,
+    pub is_minimized: bool,
+    pub is_maximized: bool,
+    pub placement: WindowPlacement,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(default)]
+pub struct ProcessInfo {
+    pub name: String,
+    pub executable_path: String,
+}
+
+/// Pencere bilgilerinin kaynağı (Win32 veya test fixture'ı). Monitor
+/// arui-native'de diğer alt sistemlerle aynı runtime'da çalıştığı için `Send`.
+pub trait WindowSource: Send {
+    /// Tüm top-level pencereleri Z-order sırasıyla döndürür
+    fn windows(&self) -> Vec<RawWindow>;
+
+    /// Process adı ve executable path'i; bilinmiyorsa boş alanlar döner
+    fn process_info(&self, process_id: u32) -> ProcessInfo;
+
+    /// Şu anda odakta olan pencere (yoksa 0)
+    fn foreground_window(&self) -> isize;
+
+    /// Scripted kaynaklar için bir sonraki anlık görüntüye geçer
+    fn advance(&self) -> bool {
+        false
+    }
+
+    /// Pencere değişikliklerinin bildirileceği kanal. Sadece bir kez
+    /// alınabilir; `None` dönen kaynaklar sabit aralıkla taranır.
+    fn subscribe(&mut self) -> Option<WindowEvents> {
+        None
+    }
+}
+
+/// Kayıtlı pencere anlık görüntüsü
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(default)]
+pub struct FixtureFrame {
+    pub windows: Vec<RawWindow>,
+    pub processes: HashMap<u32, ProcessInfo>,
+    pub foreground: isize,
+}
+
+/// Bellekte çalışan, frame frame ilerletilebilen fixture backend'i
+#[derive(Debug, Default)]
+pub struct FixtureWindowSource {
+    frames: Vec<FixtureFrame>,
+    current: Cell<usize>,
+    events: Option<WindowEvents>,
+}
+
+impl FixtureWindowSource {
+    pub fn new(frames: Vec<FixtureFrame>) -> Self {
+        Self {
+            frames,
+            current: Cell::new(0),
+            events: None,
+        }
+    }
+
+    /// Monitor'ü polling yerine `EventInjector`'dan gelen sentetik
+    /// event'lerle sürer; her event grubunda bir sonraki frame okunur
+    pub fn with_events(mut self, events: WindowEvents) -> Self {
+        self.events = Some(events);
+        self
+    }
+
+    /// JSON fixture dosyasını yükle: tek bir frame ya da frame listesi olabilir
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum FixtureFile {
+            Frames(Vec<FixtureFrame>),
+            Single(FixtureFrame),
+        }
+
+        match serde_json::from_str::<FixtureFile>(json) {
+            Ok(FixtureFile::Frames(frames)) => Ok(Self::new(frames)),
+            Ok(FixtureFile::Single(frame)) => Ok(Self::new(vec![frame])),
+            Err(e) => Err(format!("Invalid window fixture: {}", e)),
+        }
+    }
+
+    pub fn from_file(path: &str) -> Result<Self, String> {
+        let json = std::fs::read_to_string(path)
+            .map_err(|e| format!("Could not read window fixture {}: {}", path, e))?;
+        Self::from_json(&json)
+    }
+
+    fn frame(&self) -> Option<&FixtureFrame> {
+        self.frames.get(self.current.get())
+    }
+}
+
+impl WindowSource for FixtureWindowSource {
+    fn windows(&self) -> Vec<RawWindow> {
+        self.frame()
+            .map(|frame| frame.windows.clone())
+            .unwrap_or_default()
+    }
+
+    fn process_info(&self, process_id: u32) -> ProcessInfo {
+        self.frame()
+            .and_then(|frame| frame.processes.get(&process_id).cloned())
+            .unwrap_or_default()
+    }
+
+    fn foreground_window(&self) -> isize {
+        self.frame().map(|frame| frame.foreground).unwrap_or(0)
+    }
+
+    /// Bir sonraki frame'e geç; son frame'de kalır
+    fn advance(&self) -> bool {
+        let next = self.current.get() + 1;
+        if next < self.frames.len() {
+            self.current.set(next);
+            true
+        } else {
+            false
+        }
+    }
+
+    fn subscribe(&mut self) -> Option<WindowEvents> {
+        self.events.take()
+    }
+}
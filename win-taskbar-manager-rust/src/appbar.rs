@@ -0,0 +1,284 @@
+//! ARUI bar'ını Explorer'a AppBar olarak kaydeder. Böylece büyütülen
+//! pencereler bar'ın altına girmez ve tam ekran uygulamalarda bar gizlenir.
+//!
+//! Bar penceresi renderer process'ine ait olduğundan AppBar bildirimleri
+//! ona gönderilemez. Bunun yerine burada açılan gizli bir pencere AppBar
+//! olarak kaydedilir ve Explorer'ın verdiği yer bar penceresine uygulanır.
+
+use crate::geometry::{self, Edge, Monitor, Rect, Reservation};
+use crate::taskbar::mouse_position;
+use arui_protocol::{AruiError, ErrorCode, Result, TaskbarEvent, TaskbarEventKind, WindowHandle};
+use std::cell::RefCell;
+use std::sync::mpsc::SyncSender;
+use std::thread::JoinHandle;
+use tokio::sync::mpsc;
+use windows::core::*;
+use windows::Win32::Foundation::*;
+use windows::Win32::System::LibraryLoader::GetModuleHandleW;
+use windows::Win32::System::Threading::GetCurrentThreadId;
+use windows::Win32::UI::Shell::{
+    SHAppBarMessage, ABE_BOTTOM, ABE_LEFT, ABE_RIGHT, ABE_TOP, ABM_NEW, ABM_QUERYPOS, ABM_REMOVE,
+    ABM_SETPOS, ABN_FULLSCREENAPP, ABN_POSCHANGED, APPBARDATA,
+};
+use windows::Win32::UI::WindowsAndMessaging::*;
+
+// Explorer'ın AppBar bildirimlerini gönderdiği mesaj
+const APPBAR_CALLBACK: u32 = WM_APP + 1;
+
+thread_local! {
+    // Bildirimler AppBar penceresini açan thread'in mesaj döngüsünde gelir
+    static APPBAR: RefCell<Option<AppBarState>> = const { RefCell::new(None) };
+}
+
+/// AppBar thread'inin bildirimleri işlerken kullandığı durum
+struct AppBarState {
+    // HWND thread'ler arasında taşınamadığı için handle olarak tutulur
+    bar: WindowHandle,
+    bounds: Rect,
+    reservation: Reservation,
+    events: mpsc::UnboundedSender<TaskbarEvent>,
+}
+
+/// Kayıtlı AppBar ve onun mesaj döngüsünü çalıştıran thread. Drop
+/// edildiğinde kayıt silinir ve Explorer çalışma alanını yeniden hesaplar.
+pub struct AppBar {
+    thread_id: u32,
+    thread: Option<JoinHandle<()>>,
+    bar: WindowHandle,
+    monitor: String,
+    reservation: Reservation,
+}
+
+impl AppBar {
+    /// `bar` penceresini monitörün verilen kenarına AppBar olarak yerleştirir
+    pub fn register(
+        bar: WindowHandle,
+        monitor: &Monitor,
+        reservation: Reservation,
+        events: mpsc::UnboundedSender<TaskbarEvent>,
+    ) -> Result<Self> {
+        if !unsafe { IsWindow(hwnd(bar)) }.as_bool() {
+            let message = format!("Window not found: {}", bar);
+            return Err(AruiError::new(ErrorCode::NotFound, message));
+        }
+
+        let state = AppBarState {
+            bar,
+            bounds: monitor.bounds,
+            reservation,
+            events,
+        };
+
+        let (started_tx, started_rx) = std::sync::mpsc::sync_channel(1);
+        let thread = std::thread::Builder::new()
+            .name("appbar".to_string())
+            .spawn(move || run_appbar_thread(state, started_tx))
+            .map_err(|e| AruiError::io(e, "Could not start the AppBar thread"))?;
+
+        match started_rx.recv() {
+            Ok(Ok(thread_id)) => Ok(Self {
+                thread_id,
+                thread: Some(thread),
+                bar,
+                monitor: monitor.device.clone(),
+                reservation,
+            }),
+            Ok(Err(error)) => {
+                let _ = thread.join();
+                Err(error)
+            }
+            Err(_) => Err(AruiError::new(
+                ErrorCode::Internal,
+                "AppBar thread stopped before registering",
+            )),
+        }
+    }
+
+    pub fn bar(&self) -> WindowHandle {
+        self.bar
+    }
+
+    pub fn monitor(&self) -> &str {
+        &self.monitor
+    }
+
+    pub fn reservation(&self) -> Reservation {
+        self.reservation
+    }
+}
+
+impl Drop for AppBar {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = PostThreadMessageW(self.thread_id, WM_QUIT, WPARAM(0), LPARAM(0));
+        }
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn run_appbar_thread(state: AppBarState, started: SyncSender<Result<u32>>) {
+    let window = match create_window() {
+        Ok(window) => window,
+        Err(error) => {
+            let _ = started.send(Err(error));
+            return;
+        }
+    };
+
+    let mut data = appbar_data(window);
+    data.uCallbackMessage = APPBAR_CALLBACK;
+    if unsafe { SHAppBarMessage(ABM_NEW, &mut data) } == 0 {
+        unsafe {
+            let _ = DestroyWindow(window);
+        }
+        let error = AruiError::new(ErrorCode::Failed, "Could not register the AppBar");
+        let _ = started.send(Err(error));
+        return;
+    }
+
+    set_position(window, &state);
+    APPBAR.with(|cell| *cell.borrow_mut() = Some(state));
+
+    let mut msg = MSG::default();
+    unsafe {
+        // PostThreadMessageW'nin WM_QUIT'i bırakabilmesi için mesaj kuyruğu,
+        // thread'in hazır olduğu bildirilmeden önce oluşturulur
+        let _ = PeekMessageW(&mut msg, None, 0, 0, PM_NOREMOVE);
+        let _ = started.send(Ok(GetCurrentThreadId()));
+
+        // WM_QUIT'te 0, hatada -1 döner
+        while GetMessageW(&mut msg, None, 0, 0).0 > 0 {
+            let _ = TranslateMessage(&msg);
+            DispatchMessageW(&msg);
+        }
+
+        SHAppBarMessage(ABM_REMOVE, &mut appbar_data(window));
+        let _ = DestroyWindow(window);
+    }
+    APPBAR.with(|cell| *cell.borrow_mut() = None);
+}
+
+/// AppBar olarak kaydedilecek görünmez pencere
+fn create_window() -> Result<HWND> {
+    unsafe {
+        let instance: HINSTANCE = GetModuleHandleW(None)
+            .map_err(|e| {
+                AruiError::new(ErrorCode::Failed, "Could not get the module handle")
+                    .with_hresult(e.code().0)
+            })?
+            .into();
+        let class = WNDCLASSW {
+            lpfnWndProc: Some(window_proc),
+            hInstance: instance,
+            lpszClassName: w!("ARUI_AppBar"),
+            ..Default::default()
+        };
+        // Sınıf önceki bir kayıttan kalmış olabilir; gerçekten kaydedilemediyse
+        // pencere de açılamaz
+        RegisterClassW(&class);
+
+        CreateWindowExW(
+            WS_EX_TOOLWINDOW,
+            w!("ARUI_AppBar"),
+            w!("ARUI AppBar"),
+            WS_POPUP,
+            0,
+            0,
+            0,
+            0,
+            None,
+            None,
+            instance,
+            None,
+        )
+        .map_err(|e| {
+            AruiError::new(ErrorCode::Failed, "Could not create the AppBar window")
+                .with_hresult(e.code().0)
+        })
+    }
+}
+
+/// Kenardaki şeridi Explorer'a önerir, verdiği yeri ayırır ve bar'ı oraya taşır
+fn set_position(window: HWND, state: &AppBarState) {
+    let mut data = appbar_data(window);
+    data.uEdge = match state.reservation.edge {
+        Edge::Top => ABE_TOP,
+        Edge::Bottom => ABE_BOTTOM,
+        Edge::Left => ABE_LEFT,
+        Edge::Right => ABE_RIGHT,
+    };
+    data.rc = geometry::edge_strip(state.bounds, state.reservation).into();
+
+    unsafe {
+        // Explorer diğer AppBar'lara göre dikdörtgeni daraltabilir; kalınlık korunur
+        SHAppBarMessage(ABM_QUERYPOS, &mut data);
+        data.rc = geometry::edge_strip(data.rc.into(), state.reservation).into();
+        SHAppBarMessage(ABM_SETPOS, &mut data);
+
+        let rect = data.rc;
+        for target in [window, hwnd(state.bar)] {
+            let _ = SetWindowPos(
+                target,
+                None,
+                rect.left,
+                rect.top,
+                rect.right - rect.left,
+                rect.bottom - rect.top,
+                SWP_NOZORDER | SWP_NOACTIVATE,
+            );
+        }
+    }
+}
+
+unsafe extern "system" fn window_proc(
+    window: HWND,
+    message: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+) -> LRESULT {
+    if message != APPBAR_CALLBACK {
+        return DefWindowProcW(window, message, wparam, lparam);
+    }
+
+    APPBAR.with(|cell| {
+        let cell = cell.borrow();
+        let Some(state) = cell.as_ref() else {
+            return;
+        };
+
+        let kind = match wparam.0 as u32 {
+            // Başka bir AppBar ya da taskbar yer değiştirdi; yerimizi yeniden iste
+            ABN_POSCHANGED => {
+                set_position(window, state);
+                TaskbarEventKind::AppbarPositionChanged
+            }
+            // Bar'ın monitöründe tam ekran bir uygulama açıldı ya da kapandı
+            ABN_FULLSCREENAPP if lparam.0 != 0 => {
+                let _ = ShowWindow(hwnd(state.bar), SW_HIDE);
+                TaskbarEventKind::AppbarFullscreenApp { active: true }
+            }
+            ABN_FULLSCREENAPP => {
+                let _ = ShowWindow(hwnd(state.bar), SW_SHOWNA);
+                TaskbarEventKind::AppbarFullscreenApp { active: false }
+            }
+            _ => return,
+        };
+
+        let _ = state.events.send(TaskbarEvent::new(kind, mouse_position()));
+    });
+    LRESULT(0)
+}
+
+fn appbar_data(window: HWND) -> APPBARDATA {
+    APPBARDATA {
+        cbSize: std::mem::size_of::<APPBARDATA>() as u32,
+        hWnd: window,
+        ..Default::default()
+    }
+}
+
+fn hwnd(handle: WindowHandle) -> HWND {
+    HWND(handle.as_raw() as _)
+}
@@ -0,0 +1,108 @@
+//! stdin'den gelen JSON-RPC kontrol komutları. ARUI bunlarla taskbar'ı
+//! çalışma sırasında geçici olarak Windows'a geri verebilir.
+
+use crate::control_command::{parse_request, ControlCommand, GuardianSettings};
+use crate::geometry::Reservation;
+use crate::hot_zone::HotZone;
+use crate::taskbar::TaskbarManager;
+use arui_protocol::rpc;
+use arui_protocol::{Envelope, Result};
+use serde_json::Value;
+use std::time::Duration;
+use tokio::sync::watch;
+
+/// Tek satırlık isteği çalıştırır ve yazılacak JSON-RPC yanıtını döner
+pub fn handle_request(
+    line: &str,
+    manager: &mut TaskbarManager,
+    guardian: &watch::Sender<GuardianSettings>,
+) -> Value {
+    match parse_request(line) {
+        Ok((id, command)) => {
+            let result = execute(command, manager, guardian);
+            rpc::response(id, Envelope::from(result).to_value())
+        }
+        Err(error) => error,
+    }
+}
+
+fn execute(
+    command: ControlCommand,
+    manager: &mut TaskbarManager,
+    guardian: &watch::Sender<GuardianSettings>,
+) -> Result<Value> {
+    let action = command.name();
+
+    match command {
+        ControlCommand::Hide => {
+            manager.hide_taskbar()?;
+            guardian.send_modify(|settings| settings.enabled = true);
+        }
+        ControlCommand::Show => {
+            // Önce guardian'ı durdur, yoksa gösterilen taskbar hemen tekrar gizlenir
+            guardian.send_modify(|settings| settings.enabled = false);
+            manager.show_taskbar()?;
+        }
+        ControlCommand::Restore => {
+            guardian.send_modify(|settings| settings.enabled = false);
+            manager.restore_taskbar()?;
+        }
+        ControlCommand::Status => {}
+        ControlCommand::SetGuardianInterval { interval_ms } => {
+            // Aralık parse_request'te doğrulandı
+            let interval = interval_ms.map(Duration::from_millis);
+            guardian.send_modify(|settings| settings.interval = interval);
+        }
+        ControlCommand::SetReservedEdge {
+            monitor,
+            edge,
+            thickness,
+        } => {
+            manager.set_reservation(monitor.as_deref(), Reservation { edge, thickness })?;
+        }
+        ControlCommand::RegisterAppbar {
+            hwnd,
+            monitor,
+            edge,
+            thickness,
+        } => {
+            let reservation = Reservation { edge, thickness };
+            manager.register_appbar(hwnd, monitor.as_deref(), reservation)?;
+        }
+        ControlCommand::UnregisterAppbar => manager.unregister_appbar(),
+        ControlCommand::SetHotZones { zones } => {
+            manager.set_hot_zones(zones.unwrap_or_else(HotZone::default_zones))?;
+        }
+    }
+
+    let monitors: Vec<Value> = manager
+        .monitors()
+        .into_iter()
+        .map(|(monitor, reserved)| {
+            serde_json::json!({
+                "device": monitor.device,
+                "primary": monitor.primary,
+                "bounds": monitor.bounds,
+                "work_area": monitor.work_area,
+                "reserved": reserved,
+            })
+        })
+        .collect();
+
+    let settings = *guardian.borrow();
+    Ok(serde_json::json!({
+        "action": action,
+        "taskbar_state": if manager.is_hidden() { "hidden" } else { "visible" },
+        "guardian_enabled": settings.enabled,
+        "guardian_interval_ms": settings.interval.map(|interval| interval.as_millis() as u64),
+        "mouse_position": manager.get_mouse_position(),
+        "monitors": monitors,
+        "hot_zones": manager.hot_zones(),
+        "appbar": manager.appbar().map(|appbar| serde_json::json!({
+            "hwnd": appbar.bar(),
+            "monitor": appbar.monitor(),
+            "edge": appbar.reservation().edge,
+            "thickness": appbar.reservation().thickness,
+        })),
+    }))
+}
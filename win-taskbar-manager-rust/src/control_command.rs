@@ -0,0 +1,279 @@
+//! stdin'den gelen JSON-RPC kontrol komutlarının ayrıştırılması ve
+//! doğrulanması. Win32'ye bağımlı değildir; komutları control modülü çalıştırır.
+
+use crate::geometry::Edge;
+use crate::hot_zone::HotZone;
+use arui_protocol::rpc::{self, RpcRequest};
+use arui_protocol::{AruiError, Envelope, ErrorCode, Result, WindowHandle};
+use serde::Deserialize;
+use serde_json::Value;
+use std::time::Duration;
+
+// set_guardian_interval'ın kabul ettiği aralık
+const MIN_GUARDIAN_INTERVAL: Duration = Duration::from_millis(50);
+const MAX_GUARDIAN_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(tag = "method", content = "params", rename_all = "snake_case")]
+pub enum ControlCommand {
+    /// Taskbar'ı gizle ve guardian'ı tekrar aç
+    Hide,
+    /// Taskbar'ı göster; guardian tekrar gizlemesin diye durdurulur
+    Show,
+    /// Taskbar'ı gizlemeden önceki durumuna getir; gerekirse Explorer'ı yeniden başlat
+    Restore,
+    Status,
+    /// Guardian'ın kontrol aralığı; `null` varsayılana döner
+    SetGuardianInterval {
+        interval_ms: Option<u64>,
+    },
+    /// ARUI bar'ı için monitörün bir kenarını ayır; `monitor` verilmezse tüm
+    /// monitörler, `thickness: 0` kenarı serbest bırakır
+    SetReservedEdge {
+        #[serde(default)]
+        monitor: Option<String>,
+        edge: Edge,
+        thickness: u32,
+    },
+    /// ARUI bar'ının penceresini monitörün (verilmezse ana monitörün) bir
+    /// kenarına AppBar olarak kaydet
+    RegisterAppbar {
+        hwnd: WindowHandle,
+        #[serde(default)]
+        monitor: Option<String>,
+        edge: Edge,
+        thickness: u32,
+    },
+    UnregisterAppbar,
+    /// Mouse event'lerini üreten hot zone'ları değiştir; `null` varsayılana döner
+    SetHotZones {
+        zones: Option<Vec<HotZone>>,
+    },
+}
+
+impl ControlCommand {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ControlCommand::Hide => "hide",
+            ControlCommand::Show => "show",
+            ControlCommand::Restore => "restore",
+            ControlCommand::Status => "status",
+            ControlCommand::SetGuardianInterval { .. } => "set_guardian_interval",
+            ControlCommand::SetReservedEdge { .. } => "set_reserved_edge",
+            ControlCommand::RegisterAppbar { .. } => "register_appbar",
+            ControlCommand::UnregisterAppbar => "unregister_appbar",
+            ControlCommand::SetHotZones { .. } => "set_hot_zones",
+        }
+    }
+
+    /// Parametrelerin serde'nin yakalayamadığı sınırlarını kontrol eder
+    fn validate(&self) -> Result<()> {
+        if let ControlCommand::SetGuardianInterval {
+            interval_ms: Some(interval_ms),
+        } = self
+        {
+            let interval = Duration::from_millis(*interval_ms);
+            if !(MIN_GUARDIAN_INTERVAL..=MAX_GUARDIAN_INTERVAL).contains(&interval) {
+                return Err(AruiError::new(
+                    ErrorCode::InvalidArgument,
+                    format!(
+                        "Guardian interval must be between {} and {} ms",
+                        MIN_GUARDIAN_INTERVAL.as_millis(),
+                        MAX_GUARDIAN_INTERVAL.as_millis()
+                    ),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Guardian task'ının ana döngüden aldığı ayarlar
+#[derive(Clone, Copy, Debug)]
+pub struct GuardianSettings {
+    pub enabled: bool,
+    // Verilmediyse hook'un çalışıp çalışmadığına göre varsayılan aralık
+    pub interval: Option<Duration>,
+}
+
+impl Default for GuardianSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval: None,
+        }
+    }
+}
+
+/// Tek satırlık isteği doğrulanmış bir komuta çevirir. Hata durumunda
+/// doğrudan yazılacak JSON-RPC yanıtını döner.
+pub fn parse_request(line: &str) -> std::result::Result<(Value, ControlCommand), Value> {
+    let request = RpcRequest::parse(line)?;
+    let command = request.command::<ControlCommand>()?;
+    match command.validate() {
+        Ok(()) => Ok((request.id, command)),
+        Err(error) => {
+            let envelope = Envelope::<Value>::error(error);
+            Err(rpc::response(request.id, envelope.to_value()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn parse(request: Value) -> std::result::Result<(Value, ControlCommand), Value> {
+        parse_request(&request.to_string())
+    }
+
+    fn set_guardian_interval(
+        interval_ms: Value,
+    ) -> std::result::Result<(Value, ControlCommand), Value> {
+        parse(json!({
+            "id": 7,
+            "method": "set_guardian_interval",
+            "params": { "interval_ms": interval_ms }
+        }))
+    }
+
+    /// JSON-RPC hata yanıtının kodu
+    fn rpc_error_code(response: &Value) -> i64 {
+        response["error"]["code"].as_i64().unwrap()
+    }
+
+    #[test]
+    fn parameterless_methods_parse_with_or_without_params() {
+        let (id, command) = parse(json!({ "id": 1, "method": "hide" })).unwrap();
+        assert_eq!(id, json!(1));
+        assert_eq!(command, ControlCommand::Hide);
+
+        let (id, command) = parse(json!({ "id": "a", "method": "status", "params": {} })).unwrap();
+        assert_eq!(id, json!("a"));
+        assert_eq!(command, ControlCommand::Status);
+    }
+
+    #[test]
+    fn guardian_interval_bounds_are_inclusive() {
+        for interval_ms in [50, 1000, 60_000] {
+            let (_, command) = set_guardian_interval(json!(interval_ms)).unwrap();
+            assert_eq!(
+                command,
+                ControlCommand::SetGuardianInterval {
+                    interval_ms: Some(interval_ms)
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn guardian_interval_outside_the_bounds_is_rejected() {
+        for interval_ms in [0, 49, 60_001, u64::MAX] {
+            let response = set_guardian_interval(json!(interval_ms)).unwrap_err();
+            // Doğrulama hatası JSON-RPC hatası değil, isteğin id'siyle bir hata zarfıdır
+            assert_eq!(response["id"], json!(7));
+            assert_eq!(response["result"]["ok"], json!(false));
+            assert_eq!(
+                response["result"]["error"]["code"],
+                json!("invalid_argument")
+            );
+        }
+    }
+
+    #[test]
+    fn guardian_interval_can_be_reset_to_the_default() {
+        let (_, command) = set_guardian_interval(Value::Null).unwrap();
+        assert_eq!(
+            command,
+            ControlCommand::SetGuardianInterval { interval_ms: None }
+        );
+
+        let (_, command) = parse(json!({ "id": 1, "method": "set_guardian_interval" })).unwrap();
+        assert_eq!(
+            command,
+            ControlCommand::SetGuardianInterval { interval_ms: None }
+        );
+    }
+
+    #[test]
+    fn commands_with_parameters_parse() {
+        let (_, command) = parse(json!({
+            "id": 1,
+            "method": "register_appbar",
+            "params": { "hwnd": "0x1234", "edge": "top", "thickness": 32 }
+        }))
+        .unwrap();
+        assert_eq!(
+            command,
+            ControlCommand::RegisterAppbar {
+                hwnd: WindowHandle::from_raw(0x1234),
+                monitor: None,
+                edge: Edge::Top,
+                thickness: 32,
+            }
+        );
+
+        let (_, command) = parse(json!({
+            "id": 1,
+            "method": "set_reserved_edge",
+            "params": { "monitor": "\\\\.\\DISPLAY2", "edge": "left", "thickness": 0 }
+        }))
+        .unwrap();
+        assert_eq!(
+            command,
+            ControlCommand::SetReservedEdge {
+                monitor: Some("\\\\.\\DISPLAY2".to_string()),
+                edge: Edge::Left,
+                thickness: 0,
+            }
+        );
+
+        let (_, command) =
+            parse(json!({ "id": 1, "method": "set_hot_zones", "params": { "zones": null } }))
+                .unwrap();
+        assert_eq!(command, ControlCommand::SetHotZones { zones: None });
+    }
+
+    #[test]
+    fn unknown_method_is_not_found() {
+        let response = parse(json!({ "id": 3, "method": "explode" })).unwrap_err();
+        assert_eq!(response["id"], json!(3));
+        assert_eq!(rpc_error_code(&response), rpc::METHOD_NOT_FOUND);
+    }
+
+    #[test]
+    fn malformed_params_are_invalid() {
+        let requests = [
+            json!({ "id": 4, "method": "set_guardian_interval", "params": { "interval_ms": "fast" } }),
+            json!({ "id": 4, "method": "set_guardian_interval", "params": { "interval_ms": -1 } }),
+            json!({ "id": 4, "method": "set_reserved_edge", "params": { "edge": "middle", "thickness": 10 } }),
+            json!({ "id": 4, "method": "set_reserved_edge", "params": { "edge": "top" } }),
+            json!({ "id": 4, "method": "register_appbar", "params": { "hwnd": "window", "edge": "top", "thickness": 32 } }),
+        ];
+        for request in requests {
+            let response = parse(request.clone()).unwrap_err();
+            assert_eq!(response["id"], json!(4), "{}", request);
+            assert_eq!(
+                rpc_error_code(&response),
+                rpc::INVALID_PARAMS,
+                "{}",
+                request
+            );
+        }
+    }
+
+    #[test]
+    fn malformed_requests_are_rejected() {
+        let response = parse_request("{not json").unwrap_err();
+        assert_eq!(response["id"], Value::Null);
+        assert_eq!(rpc_error_code(&response), rpc::PARSE_ERROR);
+
+        let response = parse(json!({ "id": 5 })).unwrap_err();
+        assert_eq!(response["id"], json!(5));
+        assert_eq!(rpc_error_code(&response), rpc::INVALID_REQUEST);
+
+        let response = parse(json!({ "id": 5, "method": 42 })).unwrap_err();
+        assert_eq!(rpc_error_code(&response), rpc::INVALID_REQUEST);
+    }
+}
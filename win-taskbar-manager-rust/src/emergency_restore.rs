@@ -0,0 +1,71 @@
+//! Çökmüş bir çalışmanın bıraktığı durum kaydından taskbar'ı geri getirir.
+//! Hem `win-taskbar-manager emergency-restore` komutu hem de manager'ın
+//! başlangıçtaki kontrolü bunu kullanır.
+
+use crate::journal::StateJournal;
+use crate::taskbar_state;
+use arui_protocol::{AruiError, ErrorCode, RestoreMethod, Result};
+use std::path::Path;
+
+/// Kayıttan geri getirmenin sonucu
+pub struct Replay {
+    pub method: RestoreMethod,
+    // Kayıt okunamadıysa nedeni; kayıt kenara taşınmış ve taskbar Explorer
+    // yeniden başlatılarak geri getirilmiştir
+    pub discarded: Option<AruiError>,
+}
+
+/// Kayıttaki durumu uygular ve kaydı siler. Kayıt yoksa `None`, varsa
+/// taskbar'ın hangi yolla geri getirildiği döner.
+pub fn replay_journal(path: &Path) -> Result<Option<Replay>> {
+    let journal = match StateJournal::load(path) {
+        Ok(Some(journal)) => journal,
+        Ok(None) => return Ok(None),
+        // Bozuk ya da yarım kayıt her çalışmayı durdurmasın. Kayıt varsa önceki
+        // çalışma taskbar'ı gizlemiş olabilir; kaydedilen durum olmadan geri getir.
+        Err(error) if error.code == ErrorCode::InvalidArgument => {
+            let bad_path = StateJournal::discard(path)?;
+            let discarded = AruiError::new(
+                error.code,
+                format!("{} (moved to {})", error.message, bad_path.display()),
+            );
+            return Ok(Some(Replay {
+                method: taskbar_state::restore(None)?,
+                discarded: Some(discarded),
+            }));
+        }
+        Err(error) => return Err(error),
+    };
+
+    // Kaydın sahibi hâlâ çalışıyorsa taskbar'ı o yönetiyor
+    if journal.process_id != std::process::id() && journal.is_owner_running() {
+        return Err(AruiError::new(
+            ErrorCode::Failed,
+            format!(
+                "win-taskbar-manager is still running (process {})",
+                journal.process_id
+            ),
+        ));
+    }
+
+    let method = taskbar_state::restore(Some(&journal.state))?;
+    StateJournal::remove(path)?;
+    Ok(Some(Replay {
+        method,
+        discarded: None,
+    }))
+}
+
+/// `emergency-restore` komutu: varsayılan kaydı uygular ve sonucu döner
+pub fn emergency_restore() -> Result<serde_json::Value> {
+    let path = StateJournal::default_path();
+    let replay = replay_journal(&path)?;
+
+    Ok(serde_json::json!({
+        "action": "emergency_restore",
+        "journal": path.display().to_string(),
+        // Kayıt yoksa geri getirilecek bir şey yoktu
+        "taskbar_state": replay.as_ref().map(|replay| replay.method),
+        "discarded_journal": replay.and_then(|replay| replay.discarded),
+    }))
+}
@@ -0,0 +1,211 @@
+//! Explorer'ın yeniden başlamasını izleyen durum makinesi. Explorer
+//! yeniden başlayınca taskbar yeni bir penceredir ve çalışma alanları
+//! sıfırlanır; gizleme yeni pencerelere yeniden uygulanmalıdır.
+//!
+//! Win32'ye bağımlı değildir; Explorer'a `ShellBackend` üzerinden erişilir.
+
+use arui_protocol::WindowHandle;
+
+/// Durum makinesinin Explorer'la ilgili ihtiyaç duyduğu işlemler
+pub trait ShellBackend {
+    /// Ana taskbar penceresi; Explorer henüz hazır değilse `None`
+    fn find_taskbar(&self) -> Option<WindowHandle>;
+    fn is_window(&self, hwnd: WindowHandle) -> bool;
+    /// Handle'ları yeni taskbar'a göre günceller, gizlemeyi ve çalışma
+    /// alanlarını yeniden uygular
+    fn reapply(&mut self, taskbar: WindowHandle);
+}
+
+/// Durum makinesine gelen sinyaller
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ShellSignal {
+    /// Explorer taskbar'ı yeniden oluşturdu (`TaskbarCreated` mesajı)
+    TaskbarCreated,
+    /// Periyodik kontrol; mesaj kaçarsa eski handle'ın geçersizleşmesinden anlaşılır
+    Tick,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ExplorerState {
+    Running(WindowHandle),
+    // Taskbar kayboldu; yenisi bulunana kadar her sinyalde aranır
+    Restarting,
+}
+
+#[derive(Debug)]
+pub struct ExplorerWatch {
+    state: ExplorerState,
+}
+
+impl ExplorerWatch {
+    pub fn new(taskbar: WindowHandle) -> Self {
+        Self {
+            state: ExplorerState::Running(taskbar),
+        }
+    }
+
+    /// Sinyali işler. Explorer geri geldiyse ve gizleme yeniden
+    /// uygulandıysa yeni taskbar penceresini döner.
+    pub fn handle(
+        &mut self,
+        signal: ShellSignal,
+        shell: &mut impl ShellBackend,
+    ) -> Option<WindowHandle> {
+        match (self.state, signal) {
+            (ExplorerState::Running(taskbar), ShellSignal::Tick) if shell.is_window(taskbar) => {
+                return None;
+            }
+            (_, ShellSignal::TaskbarCreated) | (ExplorerState::Running(_), ShellSignal::Tick) => {
+                self.state = ExplorerState::Restarting;
+            }
+            (ExplorerState::Restarting, ShellSignal::Tick) => {}
+        }
+
+        let taskbar = shell.find_taskbar()?;
+        shell.reapply(taskbar);
+        self.state = ExplorerState::Running(taskbar);
+        Some(taskbar)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Explorer'ı taklit eden backend; yeniden uygulamaları kaydeder
+    struct FakeShell {
+        // Explorer'ın şu anki taskbar'ı; yeniden başlarken `None`
+        taskbar: Option<WindowHandle>,
+        reapplied: Vec<WindowHandle>,
+    }
+
+    impl FakeShell {
+        fn running(taskbar: WindowHandle) -> Self {
+            Self {
+                taskbar: Some(taskbar),
+                reapplied: Vec::new(),
+            }
+        }
+    }
+
+    impl ShellBackend for FakeShell {
+        fn find_taskbar(&self) -> Option<WindowHandle> {
+            self.taskbar
+        }
+
+        fn is_window(&self, hwnd: WindowHandle) -> bool {
+            self.taskbar == Some(hwnd)
+        }
+
+        fn reapply(&mut self, taskbar: WindowHandle) {
+            self.reapplied.push(taskbar);
+        }
+    }
+
+    fn handle(raw: isize) -> WindowHandle {
+        WindowHandle::from_raw(raw)
+    }
+
+    #[test]
+    fn ticks_do_nothing_while_the_taskbar_lives() {
+        let mut shell = FakeShell::running(handle(1));
+        let mut watch = ExplorerWatch::new(handle(1));
+
+        for _ in 0..3 {
+            assert_eq!(watch.handle(ShellSignal::Tick, &mut shell), None);
+        }
+        assert!(shell.reapplied.is_empty());
+    }
+
+    #[test]
+    fn taskbar_created_reapplies_once() {
+        let mut shell = FakeShell::running(handle(1));
+        let mut watch = ExplorerWatch::new(handle(1));
+
+        shell.taskbar = Some(handle(2));
+        assert_eq!(
+            watch.handle(ShellSignal::TaskbarCreated, &mut shell),
+            Some(handle(2))
+        );
+        for _ in 0..3 {
+            assert_eq!(watch.handle(ShellSignal::Tick, &mut shell), None);
+        }
+
+        assert_eq!(shell.reapplied, [handle(2)]);
+    }
+
+    #[test]
+    fn tick_notices_a_handle_change_when_the_message_is_missed() {
+        let mut shell = FakeShell::running(handle(1));
+        let mut watch = ExplorerWatch::new(handle(1));
+
+        shell.taskbar = Some(handle(2));
+        assert_eq!(watch.handle(ShellSignal::Tick, &mut shell), Some(handle(2)));
+        assert_eq!(watch.handle(ShellSignal::Tick, &mut shell), None);
+
+        assert_eq!(shell.reapplied, [handle(2)]);
+    }
+
+    #[test]
+    fn waits_for_the_new_taskbar_then_reapplies_once() {
+        let mut shell = FakeShell::running(handle(1));
+        let mut watch = ExplorerWatch::new(handle(1));
+
+        // Explorer kapandı; yeni taskbar henüz yok
+        shell.taskbar = None;
+        assert_eq!(watch.handle(ShellSignal::Tick, &mut shell), None);
+        assert_eq!(watch.handle(ShellSignal::Tick, &mut shell), None);
+        assert!(shell.reapplied.is_empty());
+
+        shell.taskbar = Some(handle(2));
+        assert_eq!(watch.handle(ShellSignal::Tick, &mut shell), Some(handle(2)));
+        assert_eq!(watch.handle(ShellSignal::Tick, &mut shell), None);
+
+        assert_eq!(shell.reapplied, [handle(2)]);
+    }
+
+    #[test]
+    fn taskbar_created_before_the_window_exists_is_resolved_by_a_tick() {
+        let mut shell = FakeShell::running(handle(1));
+        let mut watch = ExplorerWatch::new(handle(1));
+
+        shell.taskbar = None;
+        assert_eq!(watch.handle(ShellSignal::TaskbarCreated, &mut shell), None);
+
+        shell.taskbar = Some(handle(2));
+        assert_eq!(watch.handle(ShellSignal::Tick, &mut shell), Some(handle(2)));
+
+        assert_eq!(shell.reapplied, [handle(2)]);
+    }
+
+    #[test]
+    fn taskbar_created_for_the_same_window_still_reapplies() {
+        // Explorer mesajı yeniden başlamadan da yayınlayabilir (ör. DPI değişimi)
+        let mut shell = FakeShell::running(handle(1));
+        let mut watch = ExplorerWatch::new(handle(1));
+
+        assert_eq!(
+            watch.handle(ShellSignal::TaskbarCreated, &mut shell),
+            Some(handle(1))
+        );
+        assert_eq!(watch.handle(ShellSignal::Tick, &mut shell), None);
+
+        assert_eq!(shell.reapplied, [handle(1)]);
+    }
+
+    #[test]
+    fn each_restart_reapplies_once() {
+        let mut shell = FakeShell::running(handle(1));
+        let mut watch = ExplorerWatch::new(handle(1));
+
+        for raw in 2..5 {
+            shell.taskbar = None;
+            watch.handle(ShellSignal::Tick, &mut shell);
+            shell.taskbar = Some(handle(raw));
+            watch.handle(ShellSignal::TaskbarCreated, &mut shell);
+            watch.handle(ShellSignal::Tick, &mut shell);
+        }
+
+        assert_eq!(shell.reapplied, [handle(2), handle(3), handle(4)]);
+    }
+}
@@ -0,0 +1,355 @@
+//! Ön plandaki pencerenin bir monitörde tam ekran (ya da çerçevesiz tam
+//! ekran) olup olmadığını tespit eder. Win32'ye bağımlı değildir; pencere
+//! bilgileri `taskbar_state::window_info` ile toplanıp buraya verilir.
+
+use crate::geometry::{Monitor, Rect};
+use arui_protocol::WindowHandle;
+use std::collections::BTreeMap;
+
+// Masaüstü ve taskbar'lar monitörü kaplasa da tam ekran uygulama sayılmaz
+const SHELL_CLASSES: [&str; 4] = [
+    "Progman",
+    "WorkerW",
+    "Shell_TrayWnd",
+    "Shell_SecondaryTrayWnd",
+];
+
+/// Tespit için gereken pencere bilgileri
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WindowInfo {
+    pub hwnd: WindowHandle,
+    pub class_name: String,
+    pub rect: Rect,
+    // Başlık çubuğu (WS_CAPTION) olan pencereler büyütülmüş sayılır
+    pub has_caption: bool,
+    // Görünür ve simge durumunda değil
+    pub visible: bool,
+}
+
+impl WindowInfo {
+    /// Pencere bu monitörün tamamını kaplıyor ve başlık çubuğu yoksa tam ekrandır
+    pub fn is_fullscreen_on(&self, monitor: &Monitor) -> bool {
+        let bounds = monitor.bounds;
+        self.visible
+            && !self.has_caption
+            && !SHELL_CLASSES.contains(&self.class_name.as_str())
+            && self.rect.left <= bounds.left
+            && self.rect.top <= bounds.top
+            && self.rect.right >= bounds.right
+            && self.rect.bottom >= bounds.bottom
+    }
+
+    /// Pencerenin merkezinin bulunduğu monitör
+    pub fn monitor<'a>(&self, monitors: &'a [Monitor]) -> Option<&'a Monitor> {
+        let x = self.rect.left + (self.rect.right - self.rect.left) / 2;
+        let y = self.rect.top + (self.rect.bottom - self.rect.top) / 2;
+        monitors
+            .iter()
+            .find(|monitor| monitor.bounds.contains(x, y))
+    }
+}
+
+/// Tam ekran durumundaki değişiklikler
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FullscreenEvent {
+    Entered { hwnd: WindowHandle, monitor: String },
+    Exited { hwnd: WindowHandle, monitor: String },
+}
+
+/// Her monitörde tam ekran olan pencereyi izler
+#[derive(Clone, Debug, Default)]
+pub struct FullscreenTracker {
+    active: BTreeMap<String, WindowHandle>,
+}
+
+impl FullscreenTracker {
+    /// Ön plandaki pencereyi işler. `query` izlenen pencerelerin güncel
+    /// bilgisini döner; kapanmış pencereler için `None`.
+    ///
+    /// Tam ekran pencere kapanınca, küçülünce, monitörü kaplamayı bırakınca
+    /// ya da aynı monitörde başka bir pencere ön plana gelince çıkılmış sayılır.
+    pub fn update(
+        &mut self,
+        monitors: &[Monitor],
+        foreground: Option<&WindowInfo>,
+        query: impl Fn(WindowHandle) -> Option<WindowInfo>,
+    ) -> Vec<FullscreenEvent> {
+        let foreground_monitor = foreground
+            .and_then(|window| window.monitor(monitors))
+            .map(|monitor| monitor.device.as_str());
+
+        let mut events = Vec::new();
+        self.active.retain(|device, hwnd| {
+            let still_fullscreen = monitors
+                .iter()
+                .find(|monitor| monitor.device == *device)
+                .zip(query(*hwnd))
+                .is_some_and(|(monitor, window)| window.is_fullscreen_on(monitor));
+            let covered = foreground.is_some_and(|window| window.hwnd != *hwnd)
+                && foreground_monitor == Some(device.as_str());
+
+            if still_fullscreen && !covered {
+                return true;
+            }
+            events.push(FullscreenEvent::Exited {
+                hwnd: *hwnd,
+                monitor: device.clone(),
+            });
+            false
+        });
+
+        let Some(window) = foreground else {
+            return events;
+        };
+        let Some(monitor) = monitors
+            .iter()
+            .find(|monitor| window.is_fullscreen_on(monitor))
+        else {
+            return events;
+        };
+        if self.active.get(&monitor.device) != Some(&window.hwnd) {
+            self.active.insert(monitor.device.clone(), window.hwnd);
+            events.push(FullscreenEvent::Entered {
+                hwnd: window.hwnd,
+                monitor: monitor.device.clone(),
+            });
+        }
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DISPLAY1: &str = "\\\\.\\DISPLAY1";
+    const DISPLAY2: &str = "\\\\.\\DISPLAY2";
+
+    fn rect(left: i32, top: i32, right: i32, bottom: i32) -> Rect {
+        Rect {
+            left,
+            top,
+            right,
+            bottom,
+        }
+    }
+
+    /// 1920x1080 birincil monitör ve solunda, daha aşağıda 1280x1024 ikinci monitör
+    fn monitors() -> Vec<Monitor> {
+        vec![
+            Monitor {
+                device: DISPLAY1.to_string(),
+                primary: true,
+                bounds: rect(0, 0, 1920, 1080),
+                work_area: rect(0, 0, 1920, 1032),
+            },
+            Monitor {
+                device: DISPLAY2.to_string(),
+                primary: false,
+                bounds: rect(-1280, 56, 0, 1080),
+                work_area: rect(-1280, 56, 0, 1040),
+            },
+        ]
+    }
+
+    fn window(hwnd: isize, rect: Rect) -> WindowInfo {
+        WindowInfo {
+            hwnd: WindowHandle::from_raw(hwnd),
+            class_name: "UnityWndClass".to_string(),
+            rect,
+            has_caption: false,
+            visible: true,
+        }
+    }
+
+    fn fullscreen_on(window: &WindowInfo) -> Vec<&'static str> {
+        let monitors = monitors();
+        [DISPLAY1, DISPLAY2]
+            .into_iter()
+            .zip(&monitors)
+            .filter(|(_, monitor)| window.is_fullscreen_on(monitor))
+            .map(|(device, _)| device)
+            .collect()
+    }
+
+    fn entered(hwnd: isize, monitor: &str) -> FullscreenEvent {
+        FullscreenEvent::Entered {
+            hwnd: WindowHandle::from_raw(hwnd),
+            monitor: monitor.to_string(),
+        }
+    }
+
+    fn exited(hwnd: isize, monitor: &str) -> FullscreenEvent {
+        FullscreenEvent::Exited {
+            hwnd: WindowHandle::from_raw(hwnd),
+            monitor: monitor.to_string(),
+        }
+    }
+
+    /// İzlenen pencerelerin güncel hali; listede olmayan pencereler kapanmıştır
+    fn query(windows: &[WindowInfo]) -> impl Fn(WindowHandle) -> Option<WindowInfo> + '_ {
+        move |hwnd| windows.iter().find(|window| window.hwnd == hwnd).cloned()
+    }
+
+    #[test]
+    fn window_exactly_covering_a_monitor_is_fullscreen() {
+        assert_eq!(
+            fullscreen_on(&window(1, rect(0, 0, 1920, 1080))),
+            [DISPLAY1]
+        );
+        assert_eq!(
+            fullscreen_on(&window(1, rect(-1280, 56, 0, 1080))),
+            [DISPLAY2]
+        );
+    }
+
+    #[test]
+    fn window_overhanging_the_monitor_is_fullscreen() {
+        // Bazı oyunlar ve eski API'ler pencereyi kenarlık kadar taşırır
+        assert_eq!(
+            fullscreen_on(&window(1, rect(-8, -8, 1928, 1088))),
+            [DISPLAY1]
+        );
+    }
+
+    #[test]
+    fn window_one_pixel_short_is_not_fullscreen() {
+        for rect in [
+            rect(1, 0, 1920, 1080),
+            rect(0, 1, 1920, 1080),
+            rect(0, 0, 1919, 1080),
+            rect(0, 0, 1920, 1079),
+            rect(-1279, 56, 0, 1080),
+            rect(-1280, 57, 0, 1080),
+        ] {
+            assert!(fullscreen_on(&window(1, rect)).is_empty(), "{:?}", rect);
+        }
+    }
+
+    #[test]
+    fn window_spanning_both_monitors_covers_both() {
+        let window = window(1, rect(-1280, 0, 1920, 1080));
+
+        assert_eq!(fullscreen_on(&window), [DISPLAY1, DISPLAY2]);
+        assert_eq!(window.monitor(&monitors()).unwrap().device, DISPLAY1);
+    }
+
+    #[test]
+    fn captioned_hidden_and_shell_windows_are_not_fullscreen() {
+        let maximized = WindowInfo {
+            has_caption: true,
+            ..window(1, rect(0, 0, 1920, 1080))
+        };
+        let minimized = WindowInfo {
+            visible: false,
+            ..window(2, rect(0, 0, 1920, 1080))
+        };
+        assert!(fullscreen_on(&maximized).is_empty());
+        assert!(fullscreen_on(&minimized).is_empty());
+
+        for class_name in SHELL_CLASSES {
+            let shell = WindowInfo {
+                class_name: class_name.to_string(),
+                ..window(3, rect(-1280, 0, 1920, 1080))
+            };
+            assert!(fullscreen_on(&shell).is_empty(), "{}", class_name);
+        }
+    }
+
+    #[test]
+    fn taskbar_sized_windows_are_not_fullscreen() {
+        for rect in [
+            rect(0, 1032, 1920, 1080),
+            rect(0, 0, 1920, 48),
+            rect(0, 0, 48, 1080),
+            rect(-1280, 1040, 0, 1080),
+        ] {
+            assert!(fullscreen_on(&window(1, rect)).is_empty(), "{:?}", rect);
+        }
+    }
+
+    #[test]
+    fn tracker_reports_entry_once() {
+        let monitors = monitors();
+        let game = window(1, rect(0, 0, 1920, 1080));
+        let windows = [game.clone()];
+        let mut tracker = FullscreenTracker::default();
+
+        assert_eq!(
+            tracker.update(&monitors, Some(&game), query(&windows)),
+            [entered(1, DISPLAY1)]
+        );
+        assert!(tracker
+            .update(&monitors, Some(&game), query(&windows))
+            .is_empty());
+        // Ön planda pencere yokken (ör. kilit ekranı geçişi) durum korunur
+        assert!(tracker.update(&monitors, None, query(&windows)).is_empty());
+    }
+
+    #[test]
+    fn tracker_keeps_one_fullscreen_window_per_monitor() {
+        let monitors = monitors();
+        let game = window(1, rect(0, 0, 1920, 1080));
+        let video = window(2, rect(-1280, 56, 0, 1080));
+        let windows = [game.clone(), video.clone()];
+        let mut tracker = FullscreenTracker::default();
+
+        tracker.update(&monitors, Some(&game), query(&windows));
+        // Diğer monitördeki pencere ön plana gelince ilki tam ekran kalır
+        assert_eq!(
+            tracker.update(&monitors, Some(&video), query(&windows)),
+            [entered(2, DISPLAY2)]
+        );
+
+        // DISPLAY2'de başka bir pencere öne gelince sadece orası çıkar
+        let browser = WindowInfo {
+            has_caption: true,
+            ..window(3, rect(-1200, 100, -100, 900))
+        };
+        assert_eq!(
+            tracker.update(&monitors, Some(&browser), query(&windows)),
+            [exited(2, DISPLAY2)]
+        );
+    }
+
+    #[test]
+    fn tracker_exits_when_the_window_changes_or_closes() {
+        let monitors = monitors();
+        let game = window(1, rect(0, 0, 1920, 1080));
+        let mut tracker = FullscreenTracker::default();
+
+        tracker.update(&monitors, Some(&game), query(std::slice::from_ref(&game)));
+        // Pencere moduna geçti: monitörü kaplamıyor
+        let windowed = window(1, rect(100, 100, 1380, 820));
+        assert_eq!(
+            tracker.update(
+                &monitors,
+                Some(&windowed),
+                query(std::slice::from_ref(&windowed))
+            ),
+            [exited(1, DISPLAY1)]
+        );
+
+        tracker.update(&monitors, Some(&game), query(std::slice::from_ref(&game)));
+        // Kapandı
+        assert_eq!(
+            tracker.update(&monitors, None, query(&[])),
+            [exited(1, DISPLAY1)]
+        );
+    }
+
+    #[test]
+    fn tracker_exits_when_the_monitor_disappears() {
+        let game = window(1, rect(-1280, 56, 0, 1080));
+        let windows = [game.clone()];
+        let mut tracker = FullscreenTracker::default();
+
+        tracker.update(&monitors(), Some(&game), query(&windows));
+        let remaining = &monitors()[..1];
+
+        assert_eq!(
+            tracker.update(remaining, Some(&game), query(&windows)),
+            [exited(1, DISPLAY2)]
+        );
+    }
+}
@@ -0,0 +1,317 @@
+//! Monitör ve çalışma alanı hesapları. Win32'ye bağımlı değildir; monitör
+//! listesi `taskbar_state`'ten gelir, sonuç orada `SPI_SETWORKAREA` ile uygulanır.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Ekran koordinatlarında bir dikdörtgen
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Rect {
+    pub left: i32,
+    pub top: i32,
+    pub right: i32,
+    pub bottom: i32,
+}
+
+impl Rect {
+    /// Sağ ve alt kenarlar dikdörtgene dahil değildir
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        self.left <= x && x < self.right && self.top <= y && y < self.bottom
+    }
+}
+
+/// Monitörün ARUI bar'ı için ayrılabilecek kenarı
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Edge {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+/// ARUI bar'ının bir monitörde ayırdığı kenar ve kalınlığı (piksel)
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Reservation {
+    pub edge: Edge,
+    pub thickness: u32,
+}
+
+/// Bir monitörün tamamı ve o anki çalışma alanı
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct Monitor {
+    // Windows'un monitöre verdiği ad (ör. `\\.\DISPLAY1`)
+    #[serde(default)]
+    pub device: String,
+    #[serde(default)]
+    pub primary: bool,
+    pub bounds: Rect,
+    pub work_area: Rect,
+}
+
+/// Monitörün tamamından ayrılan kenar düşüldükten sonra kalan çalışma alanı.
+/// Kalınlık monitörden büyükse çalışma alanı sıfır genişliğe iner.
+pub fn work_area(bounds: Rect, reservation: Option<Reservation>) -> Rect {
+    let Some(reservation) = reservation else {
+        return bounds;
+    };
+
+    let thickness = i32::try_from(reservation.thickness).unwrap_or(i32::MAX);
+    let mut area = bounds;
+    match reservation.edge {
+        Edge::Top => area.top = bounds.top.saturating_add(thickness).min(bounds.bottom),
+        Edge::Bottom => area.bottom = bounds.bottom.saturating_sub(thickness).max(bounds.top),
+        Edge::Left => area.left = bounds.left.saturating_add(thickness).min(bounds.right),
+        Edge::Right => area.right = bounds.right.saturating_sub(thickness).max(bounds.left),
+    }
+    area
+}
+
+/// Ayrılan kenarda bar'ın kapladığı şerit. AppBar'a önerilen ve
+/// Explorer'ın `ABM_QUERYPOS` ile daralttığı dikdörtgen buna göre kesilir.
+pub fn edge_strip(rect: Rect, reservation: Reservation) -> Rect {
+    let thickness = i32::try_from(reservation.thickness).unwrap_or(i32::MAX);
+    let mut strip = rect;
+    match reservation.edge {
+        Edge::Top => strip.bottom = rect.top.saturating_add(thickness).min(rect.bottom),
+        Edge::Bottom => strip.top = rect.bottom.saturating_sub(thickness).max(rect.top),
+        Edge::Left => strip.right = rect.left.saturating_add(thickness).min(rect.right),
+        Edge::Right => strip.left = rect.right.saturating_sub(thickness).max(rect.left),
+    }
+    strip
+}
+
+/// Monitör başına ayrılan kenarlar. Adı verilmeyen monitörler varsayılanı kullanır.
+#[derive(Clone, Debug, Default)]
+pub struct Reservations {
+    default: Option<Reservation>,
+    per_monitor: HashMap<String, Reservation>,
+}
+
+impl Reservations {
+    /// `monitor` verilmezse tüm monitörlerin ayarı değişir ve monitöre özel
+    /// ayarlar silinir. Kalınlığı 0 olan rezervasyon kenarı serbest bırakır.
+    pub fn set(&mut self, monitor: Option<&str>, reservation: Reservation) {
+        let reservation = Some(reservation).filter(|reservation| reservation.thickness > 0);
+        match (monitor, reservation) {
+            (None, reservation) => {
+                self.default = reservation;
+                self.per_monitor.clear();
+            }
+            (Some(monitor), Some(reservation)) => {
+                self.per_monitor.insert(monitor.to_string(), reservation);
+            }
+            (Some(monitor), None) => {
+                // Varsayılan bir kenar ayrılmışsa bu monitörde açıkça serbest bırak
+                let none = Reservation {
+                    edge: Edge::Bottom,
+                    thickness: 0,
+                };
+                self.per_monitor.insert(monitor.to_string(), none);
+            }
+        }
+    }
+
+    pub fn get(&self, device: &str) -> Option<Reservation> {
+        self.per_monitor
+            .get(device)
+            .copied()
+            .or(self.default)
+            .filter(|reservation| reservation.thickness > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(left: i32, top: i32, right: i32, bottom: i32) -> Rect {
+        Rect {
+            left,
+            top,
+            right,
+            bottom,
+        }
+    }
+
+    fn reserve(edge: Edge, thickness: u32) -> Reservation {
+        Reservation { edge, thickness }
+    }
+
+    const PRIMARY: Rect = Rect {
+        left: 0,
+        top: 0,
+        right: 1920,
+        bottom: 1080,
+    };
+    // Birincilin solunda, negatif koordinatlarda ve dikey
+    const SECONDARY: Rect = Rect {
+        left: -1080,
+        top: -420,
+        right: 0,
+        bottom: 1500,
+    };
+
+    #[test]
+    fn work_area_without_reservation_is_the_whole_monitor() {
+        assert_eq!(work_area(PRIMARY, None), PRIMARY);
+        assert_eq!(work_area(SECONDARY, None), SECONDARY);
+    }
+
+    #[test]
+    fn work_area_loses_the_reserved_edge() {
+        assert_eq!(
+            work_area(PRIMARY, Some(reserve(Edge::Top, 40))),
+            rect(0, 40, 1920, 1080)
+        );
+        assert_eq!(
+            work_area(PRIMARY, Some(reserve(Edge::Bottom, 40))),
+            rect(0, 0, 1920, 1040)
+        );
+        assert_eq!(
+            work_area(PRIMARY, Some(reserve(Edge::Left, 64))),
+            rect(64, 0, 1920, 1080)
+        );
+        assert_eq!(
+            work_area(PRIMARY, Some(reserve(Edge::Right, 64))),
+            rect(0, 0, 1856, 1080)
+        );
+    }
+
+    #[test]
+    fn work_area_on_a_monitor_with_negative_coordinates() {
+        assert_eq!(
+            work_area(SECONDARY, Some(reserve(Edge::Top, 32))),
+            rect(-1080, -388, 0, 1500)
+        );
+        assert_eq!(
+            work_area(SECONDARY, Some(reserve(Edge::Bottom, 32))),
+            rect(-1080, -420, 0, 1468)
+        );
+        assert_eq!(
+            work_area(SECONDARY, Some(reserve(Edge::Left, 32))),
+            rect(-1048, -420, 0, 1500)
+        );
+        assert_eq!(
+            work_area(SECONDARY, Some(reserve(Edge::Right, 32))),
+            rect(-1080, -420, -32, 1500)
+        );
+    }
+
+    #[test]
+    fn oversized_reservation_collapses_the_work_area() {
+        assert_eq!(
+            work_area(PRIMARY, Some(reserve(Edge::Top, 5000))),
+            rect(0, 1080, 1920, 1080)
+        );
+        assert_eq!(
+            work_area(PRIMARY, Some(reserve(Edge::Right, u32::MAX))),
+            rect(0, 0, 0, 1080)
+        );
+        assert_eq!(
+            work_area(SECONDARY, Some(reserve(Edge::Bottom, u32::MAX))),
+            rect(-1080, -420, 0, -420)
+        );
+    }
+
+    #[test]
+    fn edge_strip_is_the_complement_of_the_work_area() {
+        for edge in [Edge::Top, Edge::Bottom, Edge::Left, Edge::Right] {
+            for bounds in [PRIMARY, SECONDARY] {
+                let reservation = reserve(edge, 48);
+                let strip = edge_strip(bounds, reservation);
+                let area = work_area(bounds, Some(reservation));
+
+                let (strip_size, area_size, total) = match edge {
+                    Edge::Top | Edge::Bottom => (
+                        strip.bottom - strip.top,
+                        area.bottom - area.top,
+                        bounds.bottom - bounds.top,
+                    ),
+                    Edge::Left | Edge::Right => (
+                        strip.right - strip.left,
+                        area.right - area.left,
+                        bounds.right - bounds.left,
+                    ),
+                };
+                assert_eq!(strip_size, 48, "{:?} {:?}", edge, bounds);
+                assert_eq!(strip_size + area_size, total, "{:?} {:?}", edge, bounds);
+            }
+        }
+
+        assert_eq!(
+            edge_strip(PRIMARY, reserve(Edge::Bottom, 48)),
+            rect(0, 1032, 1920, 1080)
+        );
+        assert_eq!(
+            edge_strip(SECONDARY, reserve(Edge::Left, 48)),
+            rect(-1080, -420, -1032, 1500)
+        );
+    }
+
+    #[test]
+    fn contains_excludes_the_right_and_bottom_edges() {
+        assert!(PRIMARY.contains(0, 0));
+        assert!(PRIMARY.contains(1919, 1079));
+        assert!(!PRIMARY.contains(1920, 500));
+        assert!(!PRIMARY.contains(500, 1080));
+        assert!(SECONDARY.contains(-1, -420));
+        assert!(!SECONDARY.contains(0, 0));
+    }
+
+    #[test]
+    fn reservations_fall_back_to_the_default() {
+        let mut reservations = Reservations::default();
+        assert_eq!(reservations.get("\\\\.\\DISPLAY1"), None);
+
+        reservations.set(None, reserve(Edge::Bottom, 40));
+        reservations.set(Some("\\\\.\\DISPLAY2"), reserve(Edge::Left, 64));
+
+        assert_eq!(
+            reservations.get("\\\\.\\DISPLAY1"),
+            Some(reserve(Edge::Bottom, 40))
+        );
+        assert_eq!(
+            reservations.get("\\\\.\\DISPLAY2"),
+            Some(reserve(Edge::Left, 64))
+        );
+    }
+
+    #[test]
+    fn zero_thickness_releases_one_monitor_or_all() {
+        let mut reservations = Reservations::default();
+        reservations.set(None, reserve(Edge::Bottom, 40));
+        reservations.set(Some("\\\\.\\DISPLAY2"), reserve(Edge::Top, 0));
+
+        assert_eq!(
+            reservations.get("\\\\.\\DISPLAY1"),
+            Some(reserve(Edge::Bottom, 40))
+        );
+        assert_eq!(reservations.get("\\\\.\\DISPLAY2"), None);
+
+        // Tüm monitörler için ayar monitöre özel ayarları siler
+        reservations.set(Some("\\\\.\\DISPLAY3"), reserve(Edge::Right, 20));
+        reservations.set(None, reserve(Edge::Top, 0));
+        for device in ["\\\\.\\DISPLAY1", "\\\\.\\DISPLAY2", "\\\\.\\DISPLAY3"] {
+            assert_eq!(reservations.get(device), None, "{}", device);
+        }
+    }
+
+    #[test]
+    fn multi_monitor_work_areas_use_each_monitors_reservation() {
+        let monitors = [("\\\\.\\DISPLAY1", PRIMARY), ("\\\\.\\DISPLAY2", SECONDARY)];
+        let mut reservations = Reservations::default();
+        reservations.set(None, reserve(Edge::Top, 36));
+        reservations.set(Some("\\\\.\\DISPLAY2"), reserve(Edge::Right, 48));
+
+        let areas: Vec<Rect> = monitors
+            .iter()
+            .map(|(device, bounds)| work_area(*bounds, reservations.get(device)))
+            .collect();
+
+        assert_eq!(
+            areas,
+            [rect(0, 36, 1920, 1080), rect(-1080, -420, -48, 1500)]
+        );
+    }
+}
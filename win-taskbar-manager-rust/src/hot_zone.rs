@@ -0,0 +1,598 @@
+//! Mouse'un ARUI bar'ını göstermesi istenen hot zone'lara girip çıkmasını
+//! izler. Win32'ye bağımlı değildir; monitör listesi ve imleç konumu
+//! dışarıdan verilir, zaman da parametre olarak geçer.
+
+use crate::geometry::{self, Edge, Monitor, Rect, Reservation};
+use arui_protocol::MousePosition;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// Monitörün köşesi
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Corner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+/// Hot zone'un monitörde kapladığı alan
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum ZoneArea {
+    /// Kenar boyunca `thickness` piksellik şerit
+    Edge { edge: Edge, thickness: u32 },
+    /// Köşede `size` x `size` piksellik kare
+    Corner { corner: Corner, size: u32 },
+}
+
+/// Bir hot zone. `monitor` verilmezse tüm monitörlerde geçerlidir.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct HotZone {
+    pub name: String,
+    #[serde(default)]
+    pub monitor: Option<String>,
+    #[serde(flatten)]
+    pub area: ZoneArea,
+    /// Girişin sayılması için imlecin zone'da kalması gereken süre
+    #[serde(default)]
+    pub dwell_ms: u64,
+    /// Çıkış için zone'un her yönde bu kadar genişletilmiş halinden çıkmak
+    /// gerekir; kenarda titreyen imleç sürekli event üretmez
+    #[serde(default)]
+    pub leave_margin: u32,
+}
+
+impl HotZone {
+    /// Eski sabit eşiğin karşılığı: her monitörün alt kenarında 50 piksel
+    pub fn default_zones() -> Vec<HotZone> {
+        vec![HotZone {
+            name: "bottom".to_string(),
+            monitor: None,
+            area: ZoneArea::Edge {
+                edge: Edge::Bottom,
+                thickness: 50,
+            },
+            dwell_ms: 0,
+            leave_margin: 10,
+        }]
+    }
+
+    fn applies_to(&self, monitor: &Monitor) -> bool {
+        self.monitor
+            .as_deref()
+            .is_none_or(|device| device == monitor.device)
+    }
+
+    /// Zone'un monitördeki dikdörtgeni, `margin` kadar genişletilmiş ama
+    /// monitörün dışına taşmamış halde
+    pub fn rect(&self, bounds: Rect, margin: u32) -> Rect {
+        let rect = match self.area {
+            ZoneArea::Edge { edge, thickness } => {
+                geometry::edge_strip(bounds, Reservation { edge, thickness })
+            }
+            ZoneArea::Corner { corner, size } => {
+                let (vertical, horizontal) = match corner {
+                    Corner::TopLeft => (Edge::Top, Edge::Left),
+                    Corner::TopRight => (Edge::Top, Edge::Right),
+                    Corner::BottomLeft => (Edge::Bottom, Edge::Left),
+                    Corner::BottomRight => (Edge::Bottom, Edge::Right),
+                };
+                let strip = geometry::edge_strip(
+                    bounds,
+                    Reservation {
+                        edge: vertical,
+                        thickness: size,
+                    },
+                );
+                geometry::edge_strip(
+                    strip,
+                    Reservation {
+                        edge: horizontal,
+                        thickness: size,
+                    },
+                )
+            }
+        };
+
+        let margin = i32::try_from(margin).unwrap_or(i32::MAX);
+        Rect {
+            left: rect.left.saturating_sub(margin).max(bounds.left),
+            top: rect.top.saturating_sub(margin).max(bounds.top),
+            right: rect.right.saturating_add(margin).min(bounds.right),
+            bottom: rect.bottom.saturating_add(margin).min(bounds.bottom),
+        }
+    }
+
+    fn contains(&self, bounds: Rect, point: &MousePosition, margin: u32) -> bool {
+        self.rect(bounds, margin).contains(point.x, point.y)
+    }
+
+    /// Adı boş ya da alanı olmayan (kalınlığı 0) zone'ları reddeder
+    pub fn validate(&self) -> Result<(), String> {
+        let size = match self.area {
+            ZoneArea::Edge { thickness, .. } => thickness,
+            ZoneArea::Corner { size, .. } => size,
+        };
+        if self.name.is_empty() {
+            return Err("Hot zone name must not be empty".to_string());
+        }
+        if size == 0 {
+            return Err(format!("Hot zone '{}' has no area", self.name));
+        }
+        Ok(())
+    }
+}
+
+/// İzleyicinin ürettiği geçişler
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HotZoneEvent {
+    Entered { monitor: String, zone: String },
+    Left { monitor: String, zone: String },
+}
+
+#[derive(Clone, Debug)]
+enum TrackerState {
+    Outside,
+    // İmleç zone'da ama dwell süresi dolmadı
+    Pending {
+        zone: usize,
+        monitor: String,
+        since: Instant,
+    },
+    Inside {
+        zone: usize,
+        monitor: String,
+    },
+}
+
+/// İmlecin hot zone'lara giriş ve çıkışlarını izleyen durum makinesi
+#[derive(Clone, Debug)]
+pub struct HotZoneTracker {
+    zones: Vec<HotZone>,
+    state: TrackerState,
+}
+
+impl Default for HotZoneTracker {
+    fn default() -> Self {
+        Self::new(HotZone::default_zones())
+    }
+}
+
+impl HotZoneTracker {
+    pub fn new(zones: Vec<HotZone>) -> Self {
+        Self {
+            zones,
+            state: TrackerState::Outside,
+        }
+    }
+
+    pub fn zones(&self) -> &[HotZone] {
+        &self.zones
+    }
+
+    /// Zone'ları değiştirir. İmleç bir zone'daysa çıkış event'i döner.
+    pub fn set_zones(&mut self, zones: Vec<HotZone>) -> Option<HotZoneEvent> {
+        let left = match &self.state {
+            TrackerState::Inside { zone, monitor } => Some(HotZoneEvent::Left {
+                monitor: monitor.clone(),
+                zone: self.zones[*zone].name.clone(),
+            }),
+            _ => None,
+        };
+        self.zones = zones;
+        self.state = TrackerState::Outside;
+        left
+    }
+
+    /// Yeni imleç konumunu işler; bir zone'a girildiyse ya da çıkıldıysa
+    /// event döner
+    pub fn update(
+        &mut self,
+        monitors: &[Monitor],
+        point: &MousePosition,
+        now: Instant,
+    ) -> Option<HotZoneEvent> {
+        let monitor = monitors
+            .iter()
+            .find(|monitor| monitor.bounds.contains(point.x, point.y));
+
+        match std::mem::replace(&mut self.state, TrackerState::Outside) {
+            TrackerState::Inside {
+                zone,
+                monitor: device,
+            } => {
+                let hot_zone = &self.zones[zone];
+                let still_inside = monitor.is_some_and(|monitor| {
+                    monitor.device == device
+                        && hot_zone.contains(monitor.bounds, point, hot_zone.leave_margin)
+                });
+                if still_inside {
+                    self.state = TrackerState::Inside {
+                        zone,
+                        monitor: device,
+                    };
+                    return None;
+                }
+                Some(HotZoneEvent::Left {
+                    monitor: device,
+                    zone: hot_zone.name.clone(),
+                })
+            }
+            TrackerState::Pending {
+                zone,
+                monitor: device,
+                since,
+            } => {
+                let hot_zone = &self.zones[zone];
+                let still_inside = monitor.is_some_and(|monitor| {
+                    monitor.device == device && hot_zone.contains(monitor.bounds, point, 0)
+                });
+                if still_inside {
+                    return self.enter(zone, device, since, now);
+                }
+                self.start(monitor?, point, now)
+            }
+            TrackerState::Outside => self.start(monitor?, point, now),
+        }
+    }
+
+    /// İmlecin bulunduğu ilk zone için dwell süresini başlatır
+    fn start(
+        &mut self,
+        monitor: &Monitor,
+        point: &MousePosition,
+        now: Instant,
+    ) -> Option<HotZoneEvent> {
+        let zone = self
+            .zones
+            .iter()
+            .position(|zone| zone.applies_to(monitor) && zone.contains(monitor.bounds, point, 0))?;
+        self.enter(zone, monitor.device.clone(), now, now)
+    }
+
+    fn enter(
+        &mut self,
+        zone: usize,
+        monitor: String,
+        since: Instant,
+        now: Instant,
+    ) -> Option<HotZoneEvent> {
+        let dwell = Duration::from_millis(self.zones[zone].dwell_ms);
+        if now.duration_since(since) < dwell {
+            self.state = TrackerState::Pending {
+                zone,
+                monitor,
+                since,
+            };
+            return None;
+        }
+
+        self.state = TrackerState::Inside {
+            zone,
+            monitor: monitor.clone(),
+        };
+        Some(HotZoneEvent::Entered {
+            monitor,
+            zone: self.zones[zone].name.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monitor(device: &str, left: i32, top: i32, right: i32, bottom: i32) -> Monitor {
+        let bounds = Rect {
+            left,
+            top,
+            right,
+            bottom,
+        };
+        Monitor {
+            device: device.to_string(),
+            primary: left == 0 && top == 0,
+            bounds,
+            work_area: bounds,
+        }
+    }
+
+    /// 1920x1080 birincil monitör ve sağında 1280x1024 ikinci monitör
+    fn monitors() -> Vec<Monitor> {
+        vec![
+            monitor("\\\\.\\DISPLAY1", 0, 0, 1920, 1080),
+            monitor("\\\\.\\DISPLAY2", 1920, 0, 3200, 1024),
+        ]
+    }
+
+    fn edge(name: &str, edge: Edge, thickness: u32) -> HotZone {
+        HotZone {
+            name: name.to_string(),
+            monitor: None,
+            area: ZoneArea::Edge { edge, thickness },
+            dwell_ms: 0,
+            leave_margin: 0,
+        }
+    }
+
+    fn entered(monitor: &str, zone: &str) -> Option<HotZoneEvent> {
+        Some(HotZoneEvent::Entered {
+            monitor: monitor.to_string(),
+            zone: zone.to_string(),
+        })
+    }
+
+    fn left(monitor: &str, zone: &str) -> Option<HotZoneEvent> {
+        Some(HotZoneEvent::Left {
+            monitor: monitor.to_string(),
+            zone: zone.to_string(),
+        })
+    }
+
+    /// Sentetik bir imleç izini (x, y, başlangıçtan ms) oynatır ve her adımın sonucunu döner
+    fn trace(
+        tracker: &mut HotZoneTracker,
+        points: &[(i32, i32, u64)],
+    ) -> Vec<Option<HotZoneEvent>> {
+        let start = Instant::now();
+        let monitors = monitors();
+        points
+            .iter()
+            .map(|&(x, y, ms)| {
+                let now = start + Duration::from_millis(ms);
+                tracker.update(&monitors, &MousePosition { x, y }, now)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn default_zone_enters_and_leaves_with_margin() {
+        let mut tracker = HotZoneTracker::default();
+
+        let events = trace(
+            &mut tracker,
+            &[
+                (960, 500, 0),
+                // Alt kenardaki 50 piksel: y 1030..1080
+                (960, 1029, 10),
+                (960, 1030, 20),
+                (960, 1079, 30),
+                // 10 piksellik çıkış payı içinde kalır
+                (960, 1020, 40),
+                (960, 1019, 50),
+                (960, 1019, 60),
+            ],
+        );
+
+        assert_eq!(
+            events,
+            [
+                None,
+                None,
+                entered("\\\\.\\DISPLAY1", "bottom"),
+                None,
+                None,
+                left("\\\\.\\DISPLAY1", "bottom"),
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn dwell_delays_the_entry() {
+        let mut tracker = HotZoneTracker::new(vec![HotZone {
+            dwell_ms: 300,
+            ..edge("bottom", Edge::Bottom, 5)
+        }]);
+
+        let events = trace(
+            &mut tracker,
+            &[
+                (960, 1078, 0),
+                (960, 1078, 100),
+                (960, 1079, 299),
+                (960, 1079, 300),
+            ],
+        );
+
+        assert_eq!(
+            events,
+            [None, None, None, entered("\\\\.\\DISPLAY1", "bottom")]
+        );
+    }
+
+    #[test]
+    fn leaving_during_dwell_restarts_it() {
+        let mut tracker = HotZoneTracker::new(vec![HotZone {
+            dwell_ms: 300,
+            ..edge("bottom", Edge::Bottom, 5)
+        }]);
+
+        let events = trace(
+            &mut tracker,
+            &[
+                (960, 1078, 0),
+                (960, 900, 200),
+                (960, 1078, 250),
+                // İlk girişten 300 ms geçti ama dwell 250'de yeniden başladı
+                (960, 1078, 400),
+                (960, 1078, 550),
+                (960, 1078, 600),
+            ],
+        );
+
+        assert_eq!(
+            events,
+            [
+                None,
+                None,
+                None,
+                None,
+                entered("\\\\.\\DISPLAY1", "bottom"),
+                None
+            ]
+        );
+    }
+
+    #[test]
+    fn per_monitor_zones_only_fire_on_their_monitor() {
+        let mut tracker = HotZoneTracker::new(vec![HotZone {
+            monitor: Some("\\\\.\\DISPLAY2".to_string()),
+            leave_margin: 20,
+            ..edge("left", Edge::Left, 5)
+        }]);
+
+        let events = trace(
+            &mut tracker,
+            &[
+                // DISPLAY1'in sol kenarı
+                (2, 500, 0),
+                (1924, 500, 10),
+                (1920, 500, 20),
+                // Payın içinde ama DISPLAY1'de; payı monitörün dışına taşmaz
+                (1919, 500, 30),
+                (1939, 500, 40),
+            ],
+        );
+
+        assert_eq!(
+            events,
+            [
+                None,
+                entered("\\\\.\\DISPLAY2", "left"),
+                None,
+                left("\\\\.\\DISPLAY2", "left"),
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn moving_between_monitors_leaves_before_entering() {
+        let mut tracker = HotZoneTracker::default();
+
+        let events = trace(
+            &mut tracker,
+            &[(960, 1070, 0), (2500, 1000, 10), (2500, 1000, 20)],
+        );
+
+        assert_eq!(
+            events,
+            [
+                entered("\\\\.\\DISPLAY1", "bottom"),
+                left("\\\\.\\DISPLAY1", "bottom"),
+                entered("\\\\.\\DISPLAY2", "bottom"),
+            ]
+        );
+    }
+
+    #[test]
+    fn points_outside_every_monitor_are_ignored() {
+        let mut tracker = HotZoneTracker::default();
+
+        // Sağ ve alt kenarlar monitöre dahil değildir; DISPLAY2'nin altı boşluktur
+        let events = trace(
+            &mut tracker,
+            &[(960, 1080, 0), (2500, 1050, 10), (-1, 1070, 20)],
+        );
+
+        assert_eq!(events, [None, None, None]);
+    }
+
+    #[test]
+    fn corner_zones_cover_only_the_corner() {
+        let mut tracker = HotZoneTracker::new(vec![HotZone {
+            name: "corner".to_string(),
+            monitor: None,
+            area: ZoneArea::Corner {
+                corner: Corner::TopRight,
+                size: 10,
+            },
+            dwell_ms: 0,
+            leave_margin: 0,
+        }]);
+
+        let events = trace(
+            &mut tracker,
+            &[
+                (1909, 0, 0),
+                (1919, 10, 10),
+                (1910, 9, 20),
+                (1919, 0, 30),
+                (1905, 5, 40),
+            ],
+        );
+
+        assert_eq!(
+            events,
+            [
+                None,
+                None,
+                entered("\\\\.\\DISPLAY1", "corner"),
+                None,
+                left("\\\\.\\DISPLAY1", "corner"),
+            ]
+        );
+    }
+
+    #[test]
+    fn first_matching_zone_wins() {
+        let mut tracker = HotZoneTracker::new(vec![
+            edge("left", Edge::Left, 10),
+            edge("bottom", Edge::Bottom, 10),
+        ]);
+
+        let events = trace(&mut tracker, &[(5, 1075, 0)]);
+
+        assert_eq!(events, [entered("\\\\.\\DISPLAY1", "left")]);
+    }
+
+    #[test]
+    fn replacing_zones_leaves_the_current_one() {
+        let mut tracker = HotZoneTracker::default();
+        trace(&mut tracker, &[(960, 1070, 0)]);
+
+        let event = tracker.set_zones(vec![edge("top", Edge::Top, 5)]);
+
+        assert_eq!(event, left("\\\\.\\DISPLAY1", "bottom"));
+        assert_eq!(tracker.set_zones(HotZone::default_zones()), None);
+    }
+
+    #[test]
+    fn zones_without_a_name_or_area_are_rejected() {
+        assert!(edge("bottom", Edge::Bottom, 1).validate().is_ok());
+        assert!(edge("", Edge::Bottom, 1).validate().is_err());
+        assert!(edge("bottom", Edge::Bottom, 0).validate().is_err());
+    }
+
+    #[test]
+    fn zones_parse_from_control_json() {
+        let zones: Vec<HotZone> = serde_json::from_str(
+            r#"[
+                {"name": "bottom", "edge": "bottom", "thickness": 4, "leave_margin": 8},
+                {"name": "corner", "monitor": "\\\\.\\DISPLAY2", "corner": "bottom_left", "size": 16, "dwell_ms": 200}
+            ]"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            zones[0].area,
+            ZoneArea::Edge {
+                edge: Edge::Bottom,
+                thickness: 4
+            }
+        );
+        assert_eq!(zones[0].leave_margin, 8);
+        assert_eq!(zones[1].monitor.as_deref(), Some("\\\\.\\DISPLAY2"));
+        assert_eq!(
+            zones[1].area,
+            ZoneArea::Corner {
+                corner: Corner::BottomLeft,
+                size: 16
+            }
+        );
+        assert_eq!(zones[1].dwell_ms, 200);
+    }
+}
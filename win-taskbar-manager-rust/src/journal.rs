@@ -0,0 +1,134 @@
+//! Taskbar gizlenmeden önce diske yazılan durum kaydı. Manager zorla
+//! kapatılırsa (ör. `taskkill /F`) ne Drop ne de Ctrl+C handler'ı çalışır;
+//! bir sonraki çalışma ya da `emergency-restore` taskbar'ı bu kayıttan geri getirir.
+
+use crate::taskbar_state::TaskbarState;
+use arui_protocol::{AruiError, ErrorCode, Result};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use windows::Win32::Foundation::{CloseHandle, FILETIME, HANDLE, STILL_ACTIVE};
+use windows::Win32::System::Threading::{
+    GetCurrentProcess, GetExitCodeProcess, GetProcessTimes, OpenProcess,
+    PROCESS_QUERY_LIMITED_INFORMATION,
+};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct StateJournal {
+    #[serde(default = "arui_protocol::protocol_version")]
+    pub protocol_version: u32,
+    // Kaydı yazan manager; artık çalışmıyorsa kayıt çökmüş bir çalışmadan kalmıştır
+    pub process_id: u32,
+    // PID'ler yeniden kullanılır; aynı PID'li başka bir process'i sahip sanmamak
+    // için oluşturulma zamanı da karşılaştırılır. Eski kayıtlarda yok.
+    #[serde(default)]
+    pub process_created: Option<u64>,
+    pub state: TaskbarState,
+}
+
+impl StateJournal {
+    pub fn new(state: TaskbarState) -> Self {
+        Self {
+            protocol_version: arui_protocol::PROTOCOL_VERSION,
+            process_id: std::process::id(),
+            process_created: unsafe { creation_time(GetCurrentProcess()) },
+            state,
+        }
+    }
+
+    pub fn default_path() -> PathBuf {
+        let appdata = std::env::var("APPDATA").unwrap_or_default();
+        Path::new(&appdata).join("ARUI").join("taskbar-state.json")
+    }
+
+    /// Kaydı okur; dosya yoksa `None`
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        let json = match std::fs::read_to_string(path) {
+            Ok(json) => json,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => {
+                return Err(AruiError::io(
+                    e,
+                    format!("Could not read taskbar state journal {}", path.display()),
+                ))
+            }
+        };
+
+        serde_json::from_str(&json).map(Some).map_err(|e| {
+            AruiError::new(
+                ErrorCode::InvalidArgument,
+                format!("Invalid taskbar state journal {}: {}", path.display(), e),
+            )
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| AruiError::io(e, format!("Could not create {}", parent.display())))?;
+        }
+
+        // Önce geçici dosyaya yazıp diske indir, sonra taşı; process ya da
+        // sistem yazma sırasında çökerse yarım bir kayıt kalmasın
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| AruiError::new(ErrorCode::Internal, e.to_string()))?;
+        let tmp_path = path.with_extension("json.tmp");
+        let write = || -> std::io::Result<()> {
+            let mut file = std::fs::File::create(&tmp_path)?;
+            file.write_all(json.as_bytes())?;
+            file.sync_all()
+        };
+        write().map_err(|e| AruiError::io(e, format!("Could not write {}", tmp_path.display())))?;
+        std::fs::rename(&tmp_path, path)
+            .map_err(|e| AruiError::io(e, format!("Could not write {}", path.display())))
+    }
+
+    /// Taskbar geri getirildikten sonra kaydı siler; dosya yoksa hata değil
+    pub fn remove(path: &Path) -> Result<()> {
+        match std::fs::remove_file(path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(AruiError::io(
+                e,
+                format!("Could not remove {}", path.display()),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Okunamayan kaydı `.bad` uzantısıyla kenara taşır; sonraki çalışmalar
+    /// aynı kayıtta takılmasın ama inceleme için kaybolmasın
+    pub fn discard(path: &Path) -> Result<PathBuf> {
+        let bad_path = path.with_extension("json.bad");
+        std::fs::rename(path, &bad_path)
+            .map_err(|e| AruiError::io(e, format!("Could not move {}", path.display())))?;
+        Ok(bad_path)
+    }
+
+    /// Kaydı yazan process hâlâ çalışıyor mu
+    pub fn is_owner_running(&self) -> bool {
+        unsafe {
+            let Ok(handle) = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, self.process_id)
+            else {
+                return false;
+            };
+            let mut exit_code = 0u32;
+            let running = GetExitCodeProcess(handle, &mut exit_code).is_ok()
+                && exit_code == STILL_ACTIVE.0 as u32
+                && match self.process_created {
+                    Some(created) => creation_time(handle) == Some(created),
+                    None => true,
+                };
+            let _ = CloseHandle(handle);
+            running
+        }
+    }
+}
+
+/// Process'in oluşturulma zamanı (FILETIME, 100 ns birimleri)
+unsafe fn creation_time(process: HANDLE) -> Option<u64> {
+    let mut created = FILETIME::default();
+    let mut exited = FILETIME::default();
+    let mut kernel = FILETIME::default();
+    let mut user = FILETIME::default();
+    GetProcessTimes(process, &mut created, &mut exited, &mut kernel, &mut user).ok()?;
+    Some((u64::from(created.dwHighDateTime) << 32) | u64::from(created.dwLowDateTime))
+}
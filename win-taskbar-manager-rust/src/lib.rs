@@ -0,0 +1,320 @@
+//! Windows taskbar'ını gizleyip mouse event'lerini raporlayan yardımcı.
+//! Tek başına `win-taskbar-manager` binary'si olarak ya da arui-native
+//! içinde `taskbar_manager` alt sistemi olarak çalışır.
+
+#[cfg(windows)]
+mod appbar;
+#[cfg(windows)]
+mod control;
+#[cfg(windows)]
+mod emergency_restore;
+#[cfg(windows)]
+mod journal;
+#[cfg(windows)]
+mod shell_listener;
+#[cfg(windows)]
+mod taskbar;
+#[cfg(windows)]
+mod taskbar_state;
+
+// Win32'den bağımsız kararlar; diğer platformlarda sadece testlerde kullanılır
+#[cfg_attr(not(windows), allow(dead_code))]
+mod control_command;
+#[cfg_attr(not(windows), allow(dead_code))]
+mod explorer_watch;
+#[cfg_attr(not(windows), allow(dead_code))]
+mod fullscreen;
+#[cfg_attr(not(windows), allow(dead_code))]
+mod geometry;
+#[cfg_attr(not(windows), allow(dead_code))]
+mod hot_zone;
+
+#[cfg(windows)]
+pub use emergency_restore::emergency_restore;
+
+use arui_protocol::Output;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+#[cfg(windows)]
+use arui_protocol::{
+    AruiError, ErrorCode, TaskbarEvent, TaskbarEventKind, TaskbarKind, WindowHandle,
+};
+#[cfg(windows)]
+use control_command::GuardianSettings;
+#[cfg(windows)]
+use explorer_watch::{ExplorerWatch, ShellSignal};
+#[cfg(windows)]
+use journal::StateJournal;
+#[cfg(windows)]
+use shell_listener::TaskbarCreatedListener;
+#[cfg(windows)]
+use std::sync::atomic::Ordering;
+#[cfg(windows)]
+use std::time::Duration;
+#[cfg(windows)]
+use taskbar::TaskbarManager;
+#[cfg(windows)]
+use tokio::sync::watch;
+#[cfg(windows)]
+use tokio::time;
+#[cfg(windows)]
+use win_window_events::{EventCoalescer, WinEventHook, WindowEventKind};
+
+// Hook çalışırken kaçan event'lere karşı yapılan kontrolün varsayılan aralığı
+#[cfg(windows)]
+const GUARDIAN_RECHECK_INTERVAL: Duration = Duration::from_secs(2);
+// Hook kurulamadıysa taskbar'ların varsayılan kontrol aralığı
+#[cfg(windows)]
+const GUARDIAN_POLL_INTERVAL: Duration = Duration::from_millis(200);
+// Taskbar göründükten sonra kısa süre görünür kalabileceği için guardian
+// event'leri neredeyse beklemeden işler
+#[cfg(windows)]
+const GUARDIAN_DEBOUNCE: Duration = Duration::from_millis(10);
+#[cfg(windows)]
+const GUARDIAN_MAX_DELAY: Duration = Duration::from_millis(50);
+
+/// Görünür hale gelmiş ana ve ikincil taskbar'ları tekrar gizler
+#[cfg(windows)]
+fn hide_visible_taskbars(out: Output) {
+    let auto_hide = |which, hwnd: windows::Win32::Foundation::HWND| {
+        let hwnd = WindowHandle::from_raw(hwnd.0 as isize);
+        let kind = TaskbarEventKind::AutoHide { which, hwnd };
+        out.emit(&TaskbarEvent::new(kind, taskbar::mouse_position()));
+    };
+
+    unsafe {
+        use windows::Win32::UI::WindowsAndMessaging::*;
+
+        // Ana taskbar'ı kontrol et
+        if let Ok(taskbar_hwnd) = windows::Win32::UI::WindowsAndMessaging::FindWindowW(
+            windows::core::w!("Shell_TrayWnd"),
+            None,
+        ) {
+            if IsWindowVisible(taskbar_hwnd).as_bool() {
+                auto_hide(TaskbarKind::Main, taskbar_hwnd);
+                let _ = ShowWindow(taskbar_hwnd, SW_HIDE);
+            }
+        }
+
+        // İkincil taskbar'ları kontrol et (çoklu monitör)
+        let secondary_classes = [
+            windows::core::w!("Shell_SecondaryTrayWnd"),
+            windows::core::w!("WorkerW"),
+        ];
+
+        for class_name in &secondary_classes {
+            if let Ok(mut current_hwnd) =
+                windows::Win32::UI::WindowsAndMessaging::FindWindowW(*class_name, None)
+            {
+                loop {
+                    if IsWindowVisible(current_hwnd).as_bool() {
+                        auto_hide(TaskbarKind::Secondary, current_hwnd);
+                        let _ = ShowWindow(current_hwnd, SW_HIDE);
+                    }
+
+                    // Sonraki pencereyi ara
+                    match FindWindowExW(None, current_hwnd, *class_name, None) {
+                        Ok(next_hwnd) => current_hwnd = next_hwnd,
+                        Err(_) => break,
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Explorer'ın bir sonraki `TaskbarCreated` mesajını bekler. Dinleyici
+/// açılamadıysa ya da kapandıysa hiç dönmez; Explorer'ın yeniden başlaması
+/// o zaman sadece periyodik kontrolde fark edilir.
+#[cfg(windows)]
+async fn next_taskbar_created(events: &mut Option<mpsc::UnboundedReceiver<()>>) {
+    match events {
+        Some(receiver) => {
+            if receiver.recv().await.is_none() {
+                // Kapanan kanal her seferinde hemen döner; select! boşa dönmesin
+                *events = None;
+                std::future::pending().await
+            }
+        }
+        None => std::future::pending().await,
+    }
+}
+
+/// Taskbar'ı gizler ve `shutdown_flag` set edilene ya da Ctrl+C gelene kadar
+/// event'leri `out`'a yazar. `input`'tan gelen satırlar JSON-RPC kontrol
+/// komutu olarak çalıştırılır (`hide`, `show`, `restore`, `status`,
+/// `set_guardian_interval`). Dönmeden önce taskbar geri getirilir.
+#[cfg(windows)]
+pub async fn run(
+    out: Output,
+    mut input: mpsc::UnboundedReceiver<String>,
+    shutdown_flag: Arc<AtomicBool>,
+) -> arui_protocol::Result<()> {
+    // Önceki çalışma zorla kapatıldıysa taskbar'ı önce onun kaydettiği duruma
+    // getir; yoksa yeni kayıt gizli taskbar'ı orijinal durum diye kaydeder.
+    // Geri getirme doğrulama için bekler ve Explorer'ı yeniden başlatabilir.
+    let replay = tokio::task::spawn_blocking(|| {
+        emergency_restore::replay_journal(&StateJournal::default_path())
+    })
+    .await
+    .map_err(|e| AruiError::new(ErrorCode::Internal, e.to_string()))?;
+    if let Some(replay) = replay? {
+        if let Some(error) = replay.discarded {
+            let kind = TaskbarEventKind::Error { error };
+            out.emit(&TaskbarEvent::new(kind, taskbar::mouse_position()));
+        }
+        let kind = TaskbarEventKind::TaskbarRecovered {
+            method: replay.method,
+        };
+        out.emit(&TaskbarEvent::new(kind, taskbar::mouse_position()));
+    }
+
+    // TaskbarManager'ı oluştur; henüz hiçbir şey değişmediği için hata
+    // durumunda geri getirilecek bir şey yok
+    let (mut taskbar_manager, mut event_receiver) = TaskbarManager::new()?;
+
+    // Taskbar'ı gizle
+    if let Err(e) = taskbar_manager.hide_taskbar() {
+        let _ = taskbar_manager.restore_taskbar();
+        return Err(e);
+    }
+
+    // Event listener task
+    let event_task = tokio::spawn(async move {
+        while let Some(event) = event_receiver.recv().await {
+            out.emit(&event);
+        }
+    });
+
+    // Background taskbar guardian - TÜM MONİTÖRLERDEKİ taskbar'ları sürekli gizli tut.
+    // Bir pencere görünür olduğunda hook haber verir; hook'un kaçırdıkları
+    // için seyrek bir kontrol de sürer. `show`/`restore` komutları guardian'ı durdurur.
+    let (guardian_tx, mut guardian_rx) = watch::channel(GuardianSettings::default());
+    let guardian_task = tokio::spawn(async move {
+        // `_hook` task abort edilene kadar yaşar; drop edildiğinde hook kaldırılır
+        let (_hook, events) = match WinEventHook::start() {
+            Ok((hook, events)) => (Some(hook), Some(events)),
+            // Guardian yine çalışır, sadece daha sık kontrol eder
+            Err(error) => {
+                let kind = TaskbarEventKind::Error { error };
+                out.emit(&TaskbarEvent::new(kind, taskbar::mouse_position()));
+                (None, None)
+            }
+        };
+        let mut events = EventCoalescer::new(events, GUARDIAN_DEBOUNCE, GUARDIAN_MAX_DELAY);
+
+        loop {
+            let settings = *guardian_rx.borrow_and_update();
+            let interval = settings.interval.unwrap_or(if events.is_active() {
+                GUARDIAN_RECHECK_INTERVAL
+            } else {
+                GUARDIAN_POLL_INTERVAL
+            });
+
+            tokio::select! {
+                _ = time::sleep(interval) => {}
+                // Ayarlar değişti (ör. `hide` komutu); yeni ayarlarla hemen kontrol et
+                changed = guardian_rx.changed() => {
+                    if changed.is_err() {
+                        break;
+                    }
+                }
+                batch = events.next_batch() => {
+                    // Taskbar'ı sadece görünür olan pencereler geri getirebilir
+                    if !batch.iter().any(|event| event.kind == WindowEventKind::Shown) {
+                        continue;
+                    }
+                }
+            }
+
+            if guardian_rx.borrow().enabled {
+                hide_visible_taskbars(out);
+            }
+        }
+    });
+
+    // Explorer yeniden başlarsa taskbar yeni bir penceredir ve çalışma
+    // alanları sıfırlanır; gizleme yeni pencerelere yeniden uygulanır
+    let mut explorer_watch = ExplorerWatch::new(taskbar_manager.taskbar_handle());
+    let (_listener, mut taskbar_created) = match TaskbarCreatedListener::start() {
+        Ok((listener, events)) => (Some(listener), Some(events)),
+        Err(error) => {
+            let kind = TaskbarEventKind::Error { error };
+            out.emit(&TaskbarEvent::new(kind, taskbar::mouse_position()));
+            (None, None)
+        }
+    };
+
+    // Ana event loop
+    let mut interval = time::interval(Duration::from_millis(100));
+    let mut input_open = true;
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                if shutdown_flag.load(Ordering::SeqCst) {
+                    break;
+                }
+                explorer_watch.handle(ShellSignal::Tick, &mut taskbar_manager);
+                taskbar_manager.check_mouse_events();
+                taskbar_manager.check_fullscreen();
+            }
+            _ = next_taskbar_created(&mut taskbar_created) => {
+                explorer_watch.handle(ShellSignal::TaskbarCreated, &mut taskbar_manager);
+            }
+            line = input.recv(), if input_open => match line {
+                Some(line) if !line.trim().is_empty() => {
+                    // `restore` kaydedilen durumu doğrulamak için bekler ve
+                    // Explorer'ı yeniden başlatabilir; diğer task'lar durmasın
+                    let response = tokio::task::block_in_place(|| {
+                        control::handle_request(&line, &mut taskbar_manager, &guardian_tx)
+                    });
+                    out.emit(&response);
+                }
+                Some(_) => {}
+                // Girdi kapandıysa sadece Ctrl+C ya da shutdown_flag beklenir
+                None => input_open = false,
+            },
+            _ = tokio::signal::ctrl_c() => {
+                break;
+            }
+        }
+    }
+
+    // Guardian taskbar'ı tekrar gizlemesin; host'ta process bu noktada bitmeyebilir
+    guardian_task.abort();
+
+    // Program sonlanırken taskbar'ı geri göster; kaydedilen durum tutmazsa
+    // restore_taskbar Explorer'ı yeniden başlatır
+    let restored = tokio::task::block_in_place(|| taskbar_manager.restore_taskbar());
+
+    // restore_taskbar'ın gönderdiği son event'in yazılması için kanalı kapat
+    drop(taskbar_manager);
+    let _ = event_task.await;
+
+    restored
+}
+
+/// Taskbar sadece Windows'ta yönetilebilir
+#[cfg(not(windows))]
+pub async fn run(
+    _out: Output,
+    _input: mpsc::UnboundedReceiver<String>,
+    _shutdown_flag: Arc<AtomicBool>,
+) -> arui_protocol::Result<()> {
+    Err(unsupported())
+}
+
+#[cfg(not(windows))]
+pub fn emergency_restore() -> arui_protocol::Result<serde_json::Value> {
+    Err(unsupported())
+}
+
+#[cfg(not(windows))]
+fn unsupported() -> arui_protocol::AruiError {
+    arui_protocol::AruiError::new(
+        arui_protocol::ErrorCode::Unsupported,
+        "The taskbar manager is only available on Windows",
+    )
+}
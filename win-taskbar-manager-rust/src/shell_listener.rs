@@ -0,0 +1,193 @@
+//! Explorer'ın yayınladığı `TaskbarCreated` mesajını dinler. Explorer her
+//! başladığında (çöküp yeniden açıldığında da) bu mesajı tüm üst düzey
+//! pencerelere gönderir; mesajı almak için gizli bir pencere açılır.
+
+use arui_protocol::{AruiError, ErrorCode, Result};
+use std::cell::RefCell;
+use std::sync::mpsc::SyncSender;
+use std::thread::JoinHandle;
+use tokio::sync::mpsc;
+use windows::core::*;
+use windows::Win32::Foundation::*;
+use windows::Win32::System::LibraryLoader::GetModuleHandleW;
+use windows::Win32::System::Threading::GetCurrentThreadId;
+use windows::Win32::UI::WindowsAndMessaging::*;
+
+thread_local! {
+    // Mesaj, pencereyi açan thread'in mesaj döngüsünde gelir
+    static LISTENER: RefCell<Option<ListenerState>> = const { RefCell::new(None) };
+}
+
+struct ListenerState {
+    // RegisterWindowMessageW'nin verdiği, oturum boyunca sabit mesaj numarası
+    message: u32,
+    events: mpsc::UnboundedSender<()>,
+}
+
+/// `TaskbarCreated` dinleyicisi ve onun mesaj döngüsünü çalıştıran thread.
+/// Drop edildiğinde pencere kapatılır.
+pub struct TaskbarCreatedListener {
+    thread_id: u32,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl TaskbarCreatedListener {
+    /// Dinlemeye başlar; Explorer her başladığında alıcıya `()` gönderilir
+    pub fn start() -> Result<(Self, mpsc::UnboundedReceiver<()>)> {
+        let (events, receiver) = mpsc::unbounded_channel();
+
+        let (started_tx, started_rx) = std::sync::mpsc::sync_channel(1);
+        let thread = std::thread::Builder::new()
+            .name("taskbar-created".to_string())
+            .spawn(move || run_listener_thread(events, started_tx))
+            .map_err(|e| AruiError::io(e, "Could not start the TaskbarCreated listener thread"))?;
+
+        match started_rx.recv() {
+            Ok(Ok(thread_id)) => Ok((
+                Self {
+                    thread_id,
+                    thread: Some(thread),
+                },
+                receiver,
+            )),
+            Ok(Err(error)) => {
+                let _ = thread.join();
+                Err(error)
+            }
+            Err(_) => Err(AruiError::new(
+                ErrorCode::Internal,
+                "TaskbarCreated listener thread stopped before starting",
+            )),
+        }
+    }
+}
+
+impl Drop for TaskbarCreatedListener {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = PostThreadMessageW(self.thread_id, WM_QUIT, WPARAM(0), LPARAM(0));
+        }
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn run_listener_thread(events: mpsc::UnboundedSender<()>, started: SyncSender<Result<u32>>) {
+    let message = unsafe { RegisterWindowMessageW(w!("TaskbarCreated")) };
+    if message == 0 {
+        let error = AruiError::new(
+            ErrorCode::Failed,
+            "Could not register the TaskbarCreated message",
+        );
+        let _ = started.send(Err(error));
+        return;
+    }
+
+    let window = match create_window() {
+        Ok(window) => window,
+        Err(error) => {
+            let _ = started.send(Err(error));
+            return;
+        }
+    };
+
+    // Manager yönetici olarak çalışıyorsa UIPI, daha düşük yetkili
+    // Explorer'dan gelen mesajı pencereye iletmez
+    if let Err(e) = unsafe { ChangeWindowMessageFilterEx(window, message, MSGFLT_ALLOW, None) } {
+        unsafe {
+            let _ = DestroyWindow(window);
+        }
+        let error = AruiError::new(
+            ErrorCode::Failed,
+            "Could not allow the TaskbarCreated message",
+        )
+        .with_hresult(e.code().0);
+        let _ = started.send(Err(error));
+        return;
+    }
+
+    LISTENER.with(|cell| *cell.borrow_mut() = Some(ListenerState { message, events }));
+
+    let mut msg = MSG::default();
+    unsafe {
+        // PostThreadMessageW'nin WM_QUIT'i bırakabilmesi için mesaj kuyruğu,
+        // thread'in hazır olduğu bildirilmeden önce oluşturulur
+        let _ = PeekMessageW(&mut msg, None, 0, 0, PM_NOREMOVE);
+        let _ = started.send(Ok(GetCurrentThreadId()));
+
+        // WM_QUIT'te 0, hatada -1 döner
+        while GetMessageW(&mut msg, None, 0, 0).0 > 0 {
+            let _ = TranslateMessage(&msg);
+            DispatchMessageW(&msg);
+        }
+
+        let _ = DestroyWindow(window);
+    }
+    LISTENER.with(|cell| *cell.borrow_mut() = None);
+}
+
+/// Mesajı alacak görünmez pencere. Yayınlanan mesajlar sadece üst düzey
+/// pencerelere gittiği için message-only pencere kullanılamaz.
+fn create_window() -> Result<HWND> {
+    unsafe {
+        let instance: HINSTANCE = GetModuleHandleW(None)
+            .map_err(|e| {
+                AruiError::new(ErrorCode::Failed, "Could not get the module handle")
+                    .with_hresult(e.code().0)
+            })?
+            .into();
+        let class = WNDCLASSW {
+            lpfnWndProc: Some(window_proc),
+            hInstance: instance,
+            lpszClassName: w!("ARUI_TaskbarCreatedListener"),
+            ..Default::default()
+        };
+        // Sınıf önceki bir çalışmadan kalmış olabilir; gerçekten kaydedilemediyse
+        // pencere de açılamaz
+        RegisterClassW(&class);
+
+        CreateWindowExW(
+            WS_EX_TOOLWINDOW,
+            w!("ARUI_TaskbarCreatedListener"),
+            w!("ARUI TaskbarCreated Listener"),
+            WS_POPUP,
+            0,
+            0,
+            0,
+            0,
+            None,
+            None,
+            instance,
+            None,
+        )
+        .map_err(|e| {
+            AruiError::new(
+                ErrorCode::Failed,
+                "Could not create the TaskbarCreated listener window",
+            )
+            .with_hresult(e.code().0)
+        })
+    }
+}
+
+unsafe extern "system" fn window_proc(
+    window: HWND,
+    message: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+) -> LRESULT {
+    let handled = LISTENER.with(|cell| match cell.borrow().as_ref() {
+        Some(state) if state.message == message => {
+            let _ = state.events.send(());
+            true
+        }
+        _ => false,
+    });
+
+    if handled {
+        LRESULT(0)
+    } else {
+        DefWindowProcW(window, message, wparam, lparam)
+    }
+}
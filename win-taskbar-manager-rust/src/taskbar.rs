@@ -1,57 +1,195 @@
-use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
+use crate::appbar::AppBar;
+use crate::explorer_watch::ShellBackend;
+use crate::fullscreen::{FullscreenEvent, FullscreenTracker};
+use crate::geometry::{self, Monitor, Reservation, Reservations};
+use crate::hot_zone::{HotZone, HotZoneEvent, HotZoneTracker};
+use crate::journal::StateJournal;
+use crate::taskbar_state::{self, TaskbarState};
+use arui_protocol::{
+    AruiError, ErrorCode, MousePosition, TaskbarEvent, TaskbarEventKind, WindowHandle,
+};
+use std::path::PathBuf;
+use std::time::Instant;
 use tokio::sync::mpsc;
 use windows::core::*;
 use windows::Win32::Foundation::*;
 use windows::Win32::UI::WindowsAndMessaging::*;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct TaskbarEvent {
-    pub event_type: String,
-    pub timestamp: DateTime<Utc>,
-    pub mouse_position: MousePosition,
-    pub taskbar_state: String,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct MousePosition {
-    pub x: i32,
-    pub y: i32,
-}
-
 pub struct TaskbarManager {
     taskbar_hwnd: HWND,
     is_hidden: bool,
+    // İlk gizlemeden önceki durum; restore bunu geri uygular
+    saved_state: Option<TaskbarState>,
+    // saved_state'in diskteki kopyası; manager çökerse buradan geri getirilir
+    journal_path: PathBuf,
+    // ARUI bar'ının monitörlerde ayırdığı kenarlar
+    reservations: Reservations,
+    // AppBar olarak kaydedilen ARUI bar'ı; kendi monitöründe ayrılan kenarı belirler
+    appbar: Option<AppBar>,
     event_sender: mpsc::UnboundedSender<TaskbarEvent>,
-    last_mouse_in_taskbar: bool,
+    hot_zones: HotZoneTracker,
+    fullscreen: FullscreenTracker,
 }
 
+// HWND sadece bir tanıtıcı; ShowWindow gibi çağrılar her thread'den yapılabilir.
+// Manager arui-native'de runtime'ın worker thread'leri arasında taşınabilir.
+unsafe impl Send for TaskbarManager {}
+
 impl TaskbarManager {
-    pub fn new() -> std::result::Result<
-        (Self, mpsc::UnboundedReceiver<TaskbarEvent>),
-        Box<dyn std::error::Error>,
-    > {
+    pub fn new() -> arui_protocol::Result<(Self, mpsc::UnboundedReceiver<TaskbarEvent>)> {
         let (event_sender, event_receiver) = mpsc::unbounded_channel();
 
         // Taskbar pencere handle'ını bul
-        let taskbar_hwnd = unsafe { FindWindowW(w!("Shell_TrayWnd"), None).ok() };
-
-        let hwnd = match taskbar_hwnd {
-            Some(hwnd) => hwnd,
-            None => return Err("Taskbar window not found".into()),
-        };
+        let hwnd = unsafe { FindWindowW(w!("Shell_TrayWnd"), None) }.map_err(|e| {
+            AruiError::new(ErrorCode::NotFound, "Taskbar window not found").with_hresult(e.code().0)
+        })?;
 
         let manager = TaskbarManager {
             taskbar_hwnd: hwnd,
             is_hidden: false,
+            saved_state: None,
+            journal_path: StateJournal::default_path(),
+            reservations: Reservations::default(),
+            appbar: None,
             event_sender,
-            last_mouse_in_taskbar: false,
+            hot_zones: HotZoneTracker::default(),
+            fullscreen: FullscreenTracker::default(),
         };
 
         Ok((manager, event_receiver))
     }
 
-    pub fn hide_taskbar(&mut self) -> std::result::Result<(), Box<dyn std::error::Error>> {
+    pub fn is_hidden(&self) -> bool {
+        self.is_hidden
+    }
+
+    pub fn taskbar_handle(&self) -> WindowHandle {
+        WindowHandle::from_raw(self.taskbar_hwnd.0 as isize)
+    }
+
+    /// Explorer yeniden başladıysa (ör. `restore` sonrası) taskbar artık
+    /// başka bir penceredir; handle'ı yeniden bul
+    fn refresh_taskbar_hwnd(&mut self) {
+        if let Ok(hwnd) = unsafe { FindWindowW(w!("Shell_TrayWnd"), None) } {
+            self.taskbar_hwnd = hwnd;
+        }
+    }
+
+    /// Bağlı monitörler ve ayrılan kenarları
+    pub fn monitors(&self) -> Vec<(Monitor, Option<Reservation>)> {
+        taskbar_state::monitors()
+            .into_iter()
+            .map(|monitor| {
+                let reservation = self.reservation(&monitor.device);
+                (monitor, reservation)
+            })
+            .collect()
+    }
+
+    /// ARUI bar'ı için bir monitörün (`None` ise tüm monitörlerin) kenarını
+    /// ayırır. Taskbar gizliyse çalışma alanları hemen yeniden hesaplanır.
+    pub fn set_reservation(
+        &mut self,
+        monitor: Option<&str>,
+        reservation: Reservation,
+    ) -> arui_protocol::Result<()> {
+        if let Some(device) = monitor {
+            if !taskbar_state::monitors().iter().any(|m| m.device == device) {
+                let message = format!("Monitor not found: {}", device);
+                return Err(AruiError::new(ErrorCode::NotFound, message));
+            }
+        }
+
+        self.reservations.set(monitor, reservation);
+        if self.is_hidden {
+            self.apply_work_areas();
+        }
+        Ok(())
+    }
+
+    pub fn appbar(&self) -> Option<&AppBar> {
+        self.appbar.as_ref()
+    }
+
+    /// `bar` penceresini bir monitörün (`None` ise ana monitörün) kenarına
+    /// AppBar olarak kaydeder. Önceki kayıt varsa silinir.
+    pub fn register_appbar(
+        &mut self,
+        bar: WindowHandle,
+        monitor: Option<&str>,
+        reservation: Reservation,
+    ) -> arui_protocol::Result<()> {
+        if reservation.thickness == 0 {
+            return Err(AruiError::new(
+                ErrorCode::InvalidArgument,
+                "AppBar thickness must be greater than 0",
+            ));
+        }
+
+        let monitors = taskbar_state::monitors();
+        let target = match monitor {
+            Some(device) => monitors.iter().find(|m| m.device == device),
+            None => monitors.iter().find(|m| m.primary),
+        };
+        let Some(target) = target else {
+            let message = format!("Monitor not found: {}", monitor.unwrap_or("primary"));
+            return Err(AruiError::new(ErrorCode::NotFound, message));
+        };
+
+        // Aynı bar iki kez kaydedilmesin diye önce eski kayıt silinir
+        self.appbar = None;
+        let appbar = AppBar::register(bar, target, reservation, self.event_sender.clone())?;
+        self.appbar = Some(appbar);
+        if self.is_hidden {
+            self.apply_work_areas();
+        }
+        Ok(())
+    }
+
+    pub fn unregister_appbar(&mut self) {
+        if self.appbar.take().is_some() && self.is_hidden {
+            self.apply_work_areas();
+        }
+    }
+
+    /// Monitörde ayrılan kenar; AppBar'ın kenarı `set_reservation`'dan önce gelir
+    fn reservation(&self, device: &str) -> Option<Reservation> {
+        match &self.appbar {
+            Some(appbar) if appbar.monitor() == device => Some(appbar.reservation()),
+            _ => self.reservations.get(device),
+        }
+    }
+
+    /// Taskbar'lar gizliyken her monitörün çalışma alanını monitörün
+    /// tamamı yapar; ARUI bar'ının ayırdığı kenar bundan düşülür
+    fn apply_work_areas(&self) {
+        for monitor in taskbar_state::monitors() {
+            let reservation = self.reservation(&monitor.device);
+            taskbar_state::set_work_area(geometry::work_area(monitor.bounds, reservation));
+        }
+    }
+
+    pub fn hide_taskbar(&mut self) -> arui_protocol::Result<()> {
+        self.refresh_taskbar_hwnd();
+
+        // Zaten gizliyse kaydedilecek durum gizli durumdur; ilk kaydı koru.
+        // Kayıt, bir şey gizlenmeden önce diske yazılır.
+        if !self.is_hidden {
+            let state = TaskbarState::capture();
+            StateJournal::new(state.clone()).save(&self.journal_path)?;
+            self.saved_state = Some(state);
+        }
+
+        self.hide_windows();
+        self.is_hidden = true;
+        self.apply_work_areas();
+
+        self.send(TaskbarEventKind::TaskbarHidden);
+        Ok(())
+    }
+
+    /// Tüm monitörlerdeki taskbar pencerelerini gizler ve auto-hide'ı açar
+    fn hide_windows(&self) {
         unsafe {
             use windows::Win32::UI::WindowsAndMessaging::*;
 
@@ -117,163 +255,183 @@ impl TaskbarManager {
                     lParam: windows::Win32::Foundation::LPARAM(
                         windows::Win32::UI::Shell::ABS_AUTOHIDE as isize,
                     ),
-                    ..Default::default()
                 },
             );
+        }
+    }
 
-            // FULL EKRAN İÇİN: Çalışma alanını tam ekran yap
-            let screen_width = GetSystemMetrics(SM_CXSCREEN);
-            let screen_height = GetSystemMetrics(SM_CYSCREEN);
+    /// Gizlemeden önce kaydedilen durumu uygulayarak taskbar'ı gösterir.
+    /// Doğrulama yapmaz ve Explorer'ı hiçbir durumda yeniden başlatmaz.
+    pub fn show_taskbar(&mut self) -> arui_protocol::Result<()> {
+        self.refresh_taskbar_hwnd();
 
-            let mut full_work_area = windows::Win32::Foundation::RECT {
-                left: 0,
-                top: 0,
-                right: screen_width,
-                bottom: screen_height, // TAM EKRAN: taskbar yok, tüm alan kullanılabilir
-            };
+        match self.saved_state.take() {
+            Some(state) => {
+                state.apply();
+                // Taskbar göründü; bir sonraki çalışma eski kayıttan kurtarma yapmasın
+                StateJournal::remove(&self.journal_path)?;
+            }
+            // Hiç gizlenmediyse sadece göster
+            None => unsafe {
+                let _ = ShowWindow(self.taskbar_hwnd, SW_SHOW);
+            },
+        }
 
-            let _ = SystemParametersInfoW(
-                SPI_SETWORKAREA,
-                0,
-                Some(&mut full_work_area as *mut _ as *mut _),
-                SPIF_UPDATEINIFILE | SPIF_SENDCHANGE,
-            );
+        self.is_hidden = false;
 
-            self.is_hidden = true;
+        self.send(TaskbarEventKind::TaskbarShown);
+        Ok(())
+    }
 
-            // Event gönder
-            let event = TaskbarEvent {
-                event_type: "taskbar_hidden".to_string(),
-                timestamp: Utc::now(),
-                mouse_position: self.get_mouse_position(),
-                taskbar_state: "hidden_aggressive_mode_all_monitors".to_string(),
-            };
+    pub fn get_mouse_position(&self) -> MousePosition {
+        mouse_position()
+    }
 
-            let _ = self.event_sender.send(event);
-            Ok(())
-        }
+    fn send(&self, kind: TaskbarEventKind) {
+        let _ = self
+            .event_sender
+            .send(TaskbarEvent::new(kind, mouse_position()));
     }
 
-    pub fn show_taskbar(&mut self) -> std::result::Result<(), Box<dyn std::error::Error>> {
-        unsafe {
-            let _result = ShowWindow(self.taskbar_hwnd, SW_SHOW);
+    pub fn hot_zones(&self) -> &[HotZone] {
+        self.hot_zones.zones()
+    }
 
-            self.is_hidden = false;
+    /// Hot zone'ları değiştirir; imleç eski bir zone'daysa çıkışı bildirilir
+    pub fn set_hot_zones(&mut self, zones: Vec<HotZone>) -> arui_protocol::Result<()> {
+        for zone in &zones {
+            zone.validate()
+                .map_err(|message| AruiError::new(ErrorCode::InvalidArgument, message))?;
+        }
 
-            let event = TaskbarEvent {
-                event_type: "taskbar_shown".to_string(),
-                timestamp: Utc::now(),
-                mouse_position: self.get_mouse_position(),
-                taskbar_state: "visible".to_string(),
-            };
+        let event = self.hot_zones.set_zones(zones);
+        self.send_hot_zone_event(event);
+        Ok(())
+    }
 
-            let _ = self.event_sender.send(event);
-            Ok(())
+    pub fn check_mouse_events(&mut self) {
+        let position = self.get_mouse_position();
+        let event = self
+            .hot_zones
+            .update(&taskbar_state::monitors(), &position, Instant::now());
+        self.send_hot_zone_event(event);
+    }
+
+    /// Ön plandaki pencerenin bir monitörde tam ekran olmaya başlamasını
+    /// ya da bırakmasını bildirir
+    pub fn check_fullscreen(&mut self) {
+        let events = self.fullscreen.update(
+            &taskbar_state::monitors(),
+            taskbar_state::foreground_window().as_ref(),
+            taskbar_state::window_info,
+        );
+
+        for event in events {
+            self.send(match event {
+                FullscreenEvent::Entered { hwnd, monitor } => {
+                    TaskbarEventKind::FullscreenEntered { hwnd, monitor }
+                }
+                FullscreenEvent::Exited { hwnd, monitor } => {
+                    TaskbarEventKind::FullscreenExited { hwnd, monitor }
+                }
+            });
         }
     }
 
-    pub fn get_mouse_position(&self) -> MousePosition {
-        unsafe {
-            let mut point = POINT { x: 0, y: 0 };
-            let _ = GetCursorPos(&mut point);
-            MousePosition {
-                x: point.x,
-                y: point.y,
+    fn send_hot_zone_event(&self, event: Option<HotZoneEvent>) {
+        // Sadece bildirilir; taskbar'ı göstermek ARUI'nin kararıdır
+        match event {
+            Some(HotZoneEvent::Entered { monitor, zone }) => {
+                self.send(TaskbarEventKind::HotZoneEntered { monitor, zone })
+            }
+            Some(HotZoneEvent::Left { monitor, zone }) => {
+                self.send(TaskbarEventKind::HotZoneLeft { monitor, zone })
             }
+            None => {}
         }
     }
 
-    pub fn is_mouse_in_taskbar_area(&self) -> bool {
-        let pos = self.get_mouse_position();
-        let screen_height = unsafe { GetSystemMetrics(SM_CYSCREEN) };
-        let taskbar_threshold = screen_height - 50; // Taskbar alanı eşiği
+    /// Taskbar'ı gizlemeden önceki durumuna getirir. Kaydedilen durum
+    /// uygulanıp doğrulanamazsa son çare olarak Explorer yeniden başlatılır.
+    pub fn restore_taskbar(&mut self) -> arui_protocol::Result<()> {
+        if self.is_hidden {
+            let method = taskbar_state::restore(self.saved_state.as_ref())?;
 
-        pos.y >= taskbar_threshold
-    }
+            self.is_hidden = false;
+            self.saved_state = None;
+            self.send(TaskbarEventKind::TaskbarRestored { method });
 
-    pub fn check_mouse_events(&mut self) {
-        let is_in_taskbar = self.is_mouse_in_taskbar_area();
-
-        // State değişikliklerini kontrol et
-        if is_in_taskbar && !self.last_mouse_in_taskbar {
-            // Mouse taskbar alanına girdi - SADECE LOG ET, taskbar'ı gösterme!
-            let event = TaskbarEvent {
-                event_type: "mouse_request_show".to_string(),
-                timestamp: Utc::now(),
-                mouse_position: self.get_mouse_position(),
-                taskbar_state: "hidden_show_requested".to_string(),
-            };
-
-            let _ = self.event_sender.send(event);
-        } else if !is_in_taskbar && self.last_mouse_in_taskbar {
-            // Mouse taskbar alanından çıktı
-            let event = TaskbarEvent {
-                event_type: "mouse_request_hide".to_string(),
-                timestamp: Utc::now(),
-                mouse_position: self.get_mouse_position(),
-                taskbar_state: "hidden_maintained".to_string(),
-            };
-
-            let _ = self.event_sender.send(event);
+            // Taskbar geri geldi; bir sonraki çalışma kurtarma yapmasın
+            StateJournal::remove(&self.journal_path)?;
         }
-
-        self.last_mouse_in_taskbar = is_in_taskbar;
+        Ok(())
     }
+}
 
-    /// Acil durum için manuel taskbar restore fonksiyonu
-    pub fn emergency_restore_taskbar() -> std::result::Result<(), Box<dyn std::error::Error>> {
-        // EXPLORER RESTART - En etkili emergency restore
-        std::process::Command::new("taskkill")
-            .args(&["/F", "/IM", "explorer.exe"])
-            .output()?;
-
-        std::thread::sleep(std::time::Duration::from_millis(500));
-
-        std::process::Command::new("explorer.exe").spawn()?;
-
-        Ok(())
+impl ShellBackend for TaskbarManager {
+    fn find_taskbar(&self) -> Option<WindowHandle> {
+        let hwnd = unsafe { FindWindowW(w!("Shell_TrayWnd"), None) }.ok()?;
+        Some(WindowHandle::from_raw(hwnd.0 as isize))
     }
 
-    /// Güvenli şekilde taskbar'ı restore et
-    pub fn restore_taskbar(&mut self) -> std::result::Result<(), Box<dyn std::error::Error>> {
-        if self.is_hidden {
-            // EXPLORER RESTART - En etkili çözüm
-            std::process::Command::new("taskkill")
-                .args(&["/F", "/IM", "explorer.exe"])
-                .output()?;
+    fn is_window(&self, hwnd: WindowHandle) -> bool {
+        unsafe { IsWindow(HWND(hwnd.as_raw() as _)) }.as_bool()
+    }
 
-            std::thread::sleep(std::time::Duration::from_millis(500));
+    /// Yeni Explorer'ın taskbar'larını gizler, çalışma alanlarını ve
+    /// AppBar kaydını yeniden uygular. Kaydedilen durum ve kaydı yeni
+    /// pencerelere taşınır. Gizli değilse sadece handle güncellenir.
+    fn reapply(&mut self, taskbar: WindowHandle) {
+        self.taskbar_hwnd = HWND(taskbar.as_raw() as _);
+
+        // AppBar kayıtları Explorer'la birlikte kaybolur
+        if let Some((bar, monitor, reservation)) = self.appbar.as_ref().map(|appbar| {
+            (
+                appbar.bar(),
+                appbar.monitor().to_string(),
+                appbar.reservation(),
+            )
+        }) {
+            if let Err(error) = self.register_appbar(bar, Some(&monitor), reservation) {
+                self.send(TaskbarEventKind::Error { error });
+            }
+        }
 
-            std::process::Command::new("explorer.exe").spawn()?;
+        if self.is_hidden {
+            // Kayıttaki pencereler eski Explorer'ındı; yenileri gizlenmeden
+            // önce kaydedilmezse restore yeni taskbar'ı gizli bırakır ve
+            // doğrulama hep başarısız olur
+            if let Some(state) = self.saved_state.as_mut() {
+                state.adopt_new_windows();
+                if let Err(error) = StateJournal::new(state.clone()).save(&self.journal_path) {
+                    self.send(TaskbarEventKind::Error { error });
+                }
+            }
 
-            self.is_hidden = false;
+            self.hide_windows();
+            self.apply_work_areas();
+        }
 
-            let event = TaskbarEvent {
-                event_type: "taskbar_restored".to_string(),
-                timestamp: Utc::now(),
-                mouse_position: self.get_mouse_position(),
-                taskbar_state: "restored_via_explorer_restart".to_string(),
-            };
+        self.send(TaskbarEventKind::ExplorerRestarted { taskbar });
+    }
+}
 
-            let _ = self.event_sender.send(event);
-        }
-        Ok(())
+pub fn mouse_position() -> MousePosition {
+    let mut point = POINT::default();
+    unsafe {
+        let _ = GetCursorPos(&mut point);
+    }
+    MousePosition {
+        x: point.x,
+        y: point.y,
     }
 }
 
 impl Drop for TaskbarManager {
     fn drop(&mut self) {
-        // EXPLORER RESTART - En basit ve etkili cleanup
+        // Önce kaydedilen durumu dene; Explorer ancak o tutmazsa yeniden başlar
         if self.is_hidden {
-            let _ = std::process::Command::new("taskkill")
-                .args(&["/F", "/IM", "explorer.exe"])
-                .output();
-
-            std::thread::sleep(std::time::Duration::from_millis(500));
-
-            let _ = std::process::Command::new("explorer.exe").spawn();
-
-            self.is_hidden = false;
+            let _ = self.restore_taskbar();
         }
     }
 }
@@ -0,0 +1,337 @@
+use crate::fullscreen::WindowInfo;
+use crate::geometry::{Monitor, Rect};
+use arui_protocol::{RestoreMethod, WindowHandle};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use windows::core::*;
+use windows::Win32::Foundation::*;
+use windows::Win32::Graphics::Gdi::{
+    EnumDisplayMonitors, GetMonitorInfoW, HDC, HMONITOR, MONITORINFO, MONITORINFOEXW,
+};
+use windows::Win32::UI::Shell::{SHAppBarMessage, ABM_GETSTATE, ABM_SETSTATE, APPBARDATA};
+use windows::Win32::UI::WindowsAndMessaging::*;
+
+// Kaydedilen durum en fazla bu kadar uygulanır; tutmazsa Explorer yeniden başlatılır
+const RESTORE_ATTEMPTS: usize = 2;
+// Explorer'ın uygulanan durumu işlemesi için doğrulamadan önce beklenen süre
+const RESTORE_SETTLE_TIME: Duration = Duration::from_millis(200);
+
+impl From<RECT> for Rect {
+    fn from(rect: RECT) -> Self {
+        Self {
+            left: rect.left,
+            top: rect.top,
+            right: rect.right,
+            bottom: rect.bottom,
+        }
+    }
+}
+
+impl From<Rect> for RECT {
+    fn from(rect: Rect) -> Self {
+        Self {
+            left: rect.left,
+            top: rect.top,
+            right: rect.right,
+            bottom: rect.bottom,
+        }
+    }
+}
+
+/// hide_taskbar'ın dokunduğu bir pencerenin gizlenmeden önceki hali
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct WindowState {
+    pub hwnd: isize,
+    pub class_name: String,
+    pub rect: Rect,
+    pub visible: bool,
+}
+
+/// Taskbar gizlenmeden önceki durum. Geri getirirken Explorer'ı yeniden
+/// başlatmak yerine bu durum aynen uygulanır.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct TaskbarState {
+    pub windows: Vec<WindowState>,
+    pub appbar_state: u32,
+    // Her monitörün gizlemeden önceki çalışma alanı
+    #[serde(default)]
+    pub monitors: Vec<Monitor>,
+}
+
+// hide_taskbar'ın gizlediği pencere sınıfları
+const TASKBAR_CLASSES: [&str; 3] = ["Shell_TrayWnd", "Shell_SecondaryTrayWnd", "WorkerW"];
+
+impl TaskbarState {
+    /// Taskbar pencerelerinin, appbar durumunun ve her monitörün çalışma
+    /// alanının şu anki halini kaydeder
+    pub fn capture() -> Self {
+        let mut data = appbar_data();
+        let appbar_state = unsafe { SHAppBarMessage(ABM_GETSTATE, &mut data) } as u32;
+
+        Self {
+            windows: taskbar_windows(),
+            appbar_state,
+            monitors: monitors(),
+        }
+    }
+
+    /// Explorer yeniden başladıktan sonra kayıttaki pencereler artık yoktur.
+    /// Onların yerine yeni Explorer'ın pencereleri, Explorer'ın verdiği
+    /// konumda kaydedilir; taskbar'lar görünür geri getirilir. Hâlâ var olan
+    /// pencerelerin kaydı değişmez, gizlenmiş halleri kaydedilmesin.
+    pub fn adopt_new_windows(&mut self) {
+        self.windows
+            .retain(|window| unsafe { IsWindow(HWND(window.hwnd as _)).as_bool() });
+
+        for mut window in taskbar_windows() {
+            if self.windows.iter().any(|saved| saved.hwnd == window.hwnd) {
+                continue;
+            }
+            // Guardian yeni taskbar'ı çoktan gizlemiş olabilir
+            if window.class_name != "WorkerW" {
+                window.visible = true;
+            }
+            self.windows.push(window);
+        }
+    }
+
+    /// Kaydedilen durumu uygular. Artık olmayan pencereler atlanır; Explorer
+    /// gizliyken yeniden başladıysa `adopt_new_windows` kaydı yeni
+    /// pencerelerle güncellemiş olmalıdır, yoksa yeni taskbar gizli kalır.
+    pub fn apply(&self) {
+        unsafe {
+            // Önce auto-hide'ı geri al; Explorer çalışma alanını buna göre hesaplar
+            let mut data = appbar_data();
+            data.lParam = LPARAM(self.appbar_state as isize);
+            SHAppBarMessage(ABM_SETSTATE, &mut data);
+
+            for window in &self.windows {
+                let hwnd = HWND(window.hwnd as _);
+                if !IsWindow(hwnd).as_bool() {
+                    continue;
+                }
+
+                let rect = window.rect;
+                let visibility = if window.visible {
+                    SWP_SHOWWINDOW
+                } else {
+                    SWP_HIDEWINDOW
+                };
+                let _ = SetWindowPos(
+                    hwnd,
+                    None,
+                    rect.left,
+                    rect.top,
+                    rect.right - rect.left,
+                    rect.bottom - rect.top,
+                    SWP_NOZORDER | SWP_NOACTIVATE | visibility,
+                );
+                let _ = ShowWindow(hwnd, if window.visible { SW_SHOWNA } else { SW_HIDE });
+            }
+        }
+
+        for monitor in &self.monitors {
+            set_work_area(monitor.work_area);
+        }
+    }
+
+    /// Uygulanan durumun tuttuğunu kontrol eder. Artık olmayan pencereler ve
+    /// monitörler atlanır; ana taskbar görünürdüyse yine görünür olmalıdır.
+    pub fn verify(&self) -> bool {
+        let windows_restored = self.windows.iter().all(|window| unsafe {
+            let hwnd = HWND(window.hwnd as _);
+            if !IsWindow(hwnd).as_bool() {
+                return true;
+            }
+            if IsWindowVisible(hwnd).as_bool() != window.visible {
+                return false;
+            }
+            let mut rect = RECT::default();
+            !window.visible
+                || (GetWindowRect(hwnd, &mut rect).is_ok() && Rect::from(rect) == window.rect)
+        });
+
+        let taskbar_expected = self
+            .windows
+            .iter()
+            .any(|window| window.class_name == "Shell_TrayWnd" && window.visible);
+        let taskbar_visible = find_windows("Shell_TrayWnd")
+            .into_iter()
+            .any(|hwnd| unsafe { IsWindowVisible(hwnd).as_bool() });
+
+        let current_monitors = monitors();
+        let work_areas_restored = self.monitors.iter().all(|saved| {
+            current_monitors
+                .iter()
+                .find(|current| current.bounds == saved.bounds)
+                .is_none_or(|current| current.work_area == saved.work_area)
+        });
+
+        windows_restored && (!taskbar_expected || taskbar_visible) && work_areas_restored
+    }
+}
+
+/// Kaydedilen durumu uygulayıp doğrular; tutmazsa ya da durum yoksa son
+/// çare olarak Explorer'ı yeniden başlatır. Taskbar'ın hangi yolla geri
+/// getirildiğini döner.
+pub fn restore(state: Option<&TaskbarState>) -> arui_protocol::Result<RestoreMethod> {
+    let restored_in_place = state.is_some_and(|state| {
+        (0..RESTORE_ATTEMPTS).any(|_| {
+            state.apply();
+            std::thread::sleep(RESTORE_SETTLE_TIME);
+            state.verify()
+        })
+    });
+
+    if restored_in_place {
+        Ok(RestoreMethod::RestoredInPlace)
+    } else {
+        restart_explorer()?;
+        Ok(RestoreMethod::RestoredViaExplorerRestart)
+    }
+}
+
+/// Son çare: Explorer'ı yeniden başlatır. Açık File Explorer pencereleri ve
+/// tray ikonlarının durumu kaybolur.
+fn restart_explorer() -> arui_protocol::Result<()> {
+    std::process::Command::new("taskkill")
+        .args(["/F", "/IM", "explorer.exe"])
+        .output()?;
+
+    std::thread::sleep(Duration::from_millis(500));
+
+    std::process::Command::new("explorer.exe").spawn()?;
+
+    Ok(())
+}
+
+/// hide_taskbar'ın dokunduğu pencerelerin şu anki hali
+fn taskbar_windows() -> Vec<WindowState> {
+    let mut windows = Vec::new();
+    for class_name in TASKBAR_CLASSES {
+        for hwnd in find_windows(class_name) {
+            let mut rect = RECT::default();
+            unsafe {
+                let _ = GetWindowRect(hwnd, &mut rect);
+                windows.push(WindowState {
+                    hwnd: hwnd.0 as isize,
+                    class_name: class_name.to_string(),
+                    rect: rect.into(),
+                    visible: IsWindowVisible(hwnd).as_bool(),
+                });
+            }
+        }
+    }
+    windows
+}
+
+/// Verilen sınıftaki tüm top-level pencereler
+fn find_windows(class_name: &str) -> Vec<HWND> {
+    let class_name = HSTRING::from(class_name);
+    let mut windows = Vec::new();
+    unsafe {
+        let mut current = FindWindowExW(None, None, &class_name, None);
+        while let Ok(hwnd) = current {
+            windows.push(hwnd);
+            current = FindWindowExW(None, hwnd, &class_name, None);
+        }
+    }
+    windows
+}
+
+fn appbar_data() -> APPBARDATA {
+    APPBARDATA {
+        cbSize: std::mem::size_of::<APPBARDATA>() as u32,
+        hWnd: find_windows("Shell_TrayWnd")
+            .first()
+            .copied()
+            .unwrap_or_default(),
+        ..Default::default()
+    }
+}
+
+/// Bağlı monitörler, o anki çalışma alanlarıyla
+pub fn monitors() -> Vec<Monitor> {
+    let mut monitors: Vec<Monitor> = Vec::new();
+    unsafe {
+        let _ = EnumDisplayMonitors(
+            None,
+            None,
+            Some(collect_monitor),
+            LPARAM(&mut monitors as *mut Vec<Monitor> as isize),
+        );
+    }
+    monitors
+}
+
+/// Tam ekran tespiti için pencerenin şu anki hali; pencere yoksa `None`
+pub fn window_info(hwnd: WindowHandle) -> Option<WindowInfo> {
+    let handle = HWND(hwnd.as_raw() as _);
+    unsafe {
+        if hwnd.is_null() || !IsWindow(handle).as_bool() {
+            return None;
+        }
+
+        let mut rect = RECT::default();
+        GetWindowRect(handle, &mut rect).ok()?;
+        let mut class_name = [0u16; 256];
+        let len = GetClassNameW(handle, &mut class_name).max(0) as usize;
+        let style = WINDOW_STYLE(GetWindowLongW(handle, GWL_STYLE) as u32);
+
+        Some(WindowInfo {
+            hwnd,
+            class_name: String::from_utf16_lossy(&class_name[..len]),
+            rect: rect.into(),
+            has_caption: style.contains(WS_CAPTION),
+            visible: IsWindowVisible(handle).as_bool() && !IsIconic(handle).as_bool(),
+        })
+    }
+}
+
+pub fn foreground_window() -> Option<WindowInfo> {
+    let hwnd = unsafe { GetForegroundWindow() };
+    window_info(WindowHandle::from_raw(hwnd.0 as isize))
+}
+
+/// Dikdörtgenin bulunduğu monitörün çalışma alanını değiştirir
+pub fn set_work_area(work_area: Rect) {
+    let mut work_area = RECT::from(work_area);
+    unsafe {
+        let _ = SystemParametersInfoW(
+            SPI_SETWORKAREA,
+            0,
+            Some(&mut work_area as *mut _ as *mut _),
+            SPIF_UPDATEINIFILE | SPIF_SENDCHANGE,
+        );
+    }
+}
+
+unsafe extern "system" fn collect_monitor(
+    monitor: HMONITOR,
+    _hdc: HDC,
+    _clip: *mut RECT,
+    data: LPARAM,
+) -> BOOL {
+    let monitors = &mut *(data.0 as *mut Vec<Monitor>);
+    let mut info = MONITORINFOEXW::default();
+    info.monitorInfo.cbSize = std::mem::size_of::<MONITORINFOEXW>() as u32;
+    if GetMonitorInfoW(
+        monitor,
+        &mut info as *mut MONITORINFOEXW as *mut MONITORINFO,
+    )
+    .as_bool()
+    {
+        let device_len = info
+            .szDevice
+            .iter()
+            .position(|&c| c == 0)
+            .unwrap_or(info.szDevice.len());
+        monitors.push(Monitor {
+            device: String::from_utf16_lossy(&info.szDevice[..device_len]),
+            primary: info.monitorInfo.dwFlags & MONITORINFOF_PRIMARY != 0,
+            bounds: info.monitorInfo.rcMonitor.into(),
+            work_area: info.monitorInfo.rcWork.into(),
+        });
+    }
+    TRUE
+}
@@ -0,0 +1,257 @@
+use crate::event::{WindowEvent, WindowEvents};
+use std::time::Duration;
+use tokio::time::{timeout_at, Instant};
+
+// Bir grupta tutulan en fazla event. Grup zaten yenileme tetikleyeceği için
+// event fırtınasında fazlası atılır.
+const MAX_PENDING: usize = 256;
+
+/// Art arda gelen event'leri tek bir grupta toplar. Son event'ten sonra
+/// `debounce` kadar sessizlik olunca ya da ilk event'in üzerinden
+/// `max_delay` geçince grup teslim edilir; böylece sürekli event üreten bir
+/// pencere yenilemeleri sonsuza kadar geciktiremez.
+#[derive(Debug)]
+pub struct EventCoalescer {
+    events: Option<WindowEvents>,
+    debounce: Duration,
+    max_delay: Duration,
+    pending: Vec<WindowEvent>,
+    first_at: Option<Instant>,
+    last_at: Option<Instant>,
+}
+
+impl EventCoalescer {
+    /// `events` yoksa (ör. hook kurulamadıysa) hiç grup teslim etmez
+    pub fn new(events: Option<WindowEvents>, debounce: Duration, max_delay: Duration) -> Self {
+        Self {
+            events,
+            debounce,
+            max_delay,
+            pending: Vec::new(),
+            first_at: None,
+            last_at: None,
+        }
+    }
+
+    /// Event kaynağı açık mı; değilse çağıran taraf polling'e dönmeli
+    pub fn is_active(&self) -> bool {
+        self.events.is_some()
+    }
+
+    /// Bir sonraki event grubunu bekler. `select!` içinde iptal edilmesi
+    /// güvenlidir: toplanan event'ler bir sonraki çağrıda teslim edilir.
+    /// Kaynak yoksa ya da kapandıysa hiç dönmez.
+    pub async fn next_batch(&mut self) -> Vec<WindowEvent> {
+        loop {
+            let deadline = self.deadline();
+            let Some(events) = self.events.as_mut() else {
+                return std::future::pending().await;
+            };
+
+            let event = match deadline {
+                // Kuyrukta bekleyen event olsa bile süre dolduysa grubu teslim et
+                Some(deadline) if Instant::now() >= deadline => return self.take(),
+                Some(deadline) => match timeout_at(deadline, events.recv()).await {
+                    Ok(event) => event,
+                    Err(_) => return self.take(),
+                },
+                None => events.recv().await,
+            };
+
+            match event {
+                Some(event) => self.push(event),
+                None => {
+                    // Kaynak kapandı; toplananları teslim et, sonrası polling
+                    self.events = None;
+                    if !self.pending.is_empty() {
+                        return self.take();
+                    }
+                }
+            }
+        }
+    }
+
+    fn push(&mut self, event: WindowEvent) {
+        let now = Instant::now();
+        self.first_at.get_or_insert(now);
+        self.last_at = Some(now);
+
+        // Aynı pencerenin aynı event'i grupta bir kez yeter
+        if self.pending.len() < MAX_PENDING && !self.pending.contains(&event) {
+            self.pending.push(event);
+        }
+    }
+
+    fn deadline(&self) -> Option<Instant> {
+        let first = self.first_at?;
+        let last = self.last_at?;
+        Some((last + self.debounce).min(first + self.max_delay))
+    }
+
+    fn take(&mut self) -> Vec<WindowEvent> {
+        self.first_at = None;
+        self.last_at = None;
+        std::mem::take(&mut self.pending)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::WindowEventKind;
+    use crate::injector::EventInjector;
+
+    const DEBOUNCE: Duration = Duration::from_millis(10);
+    const MAX_DELAY: Duration = Duration::from_millis(50);
+
+    fn coalescer() -> (EventInjector, EventCoalescer) {
+        let (injector, events) = EventInjector::new();
+        let coalescer = EventCoalescer::new(Some(events), DEBOUNCE, MAX_DELAY);
+        (injector, coalescer)
+    }
+
+    fn shown(hwnd: isize) -> WindowEvent {
+        WindowEvent::new(WindowEventKind::Shown, hwnd)
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn batch_is_delivered_after_the_debounce() {
+        let (injector, mut coalescer) = coalescer();
+        let start = Instant::now();
+
+        injector.inject(WindowEventKind::Shown, 1);
+        injector.inject(WindowEventKind::Hidden, 2);
+
+        let batch = coalescer.next_batch().await;
+        assert_eq!(
+            batch,
+            [shown(1), WindowEvent::new(WindowEventKind::Hidden, 2)]
+        );
+        assert_eq!(start.elapsed(), DEBOUNCE);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn new_events_extend_the_debounce() {
+        let (injector, mut coalescer) = coalescer();
+        let start = Instant::now();
+
+        injector.inject(WindowEventKind::Shown, 1);
+        let late = injector.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(8)).await;
+            late.inject(WindowEventKind::Shown, 2);
+        });
+
+        assert_eq!(coalescer.next_batch().await, [shown(1), shown(2)]);
+        assert_eq!(start.elapsed(), Duration::from_millis(18));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn continuous_stream_is_capped_by_max_delay() {
+        let (injector, mut coalescer) = coalescer();
+        let start = Instant::now();
+
+        // Debounce'tan kısa aralıklarla hiç durmayan bir pencere
+        tokio::spawn(async move {
+            for hwnd in 0.. {
+                if !injector.inject(WindowEventKind::NameChanged, hwnd) {
+                    break;
+                }
+                tokio::time::sleep(Duration::from_millis(4)).await;
+            }
+        });
+
+        let first = coalescer.next_batch().await;
+        assert_eq!(start.elapsed(), MAX_DELAY);
+        // 0, 4, ..., 48 ms'de gelenler
+        assert_eq!(first.len(), 13);
+        assert_eq!(first[0].hwnd, 0);
+        assert_eq!(first[12].hwnd, 12);
+
+        // Sonraki grup kendi ilk event'inden itibaren yine en fazla max_delay bekler
+        let second = coalescer.next_batch().await;
+        assert_eq!(second[0].hwnd, 13);
+        assert_eq!(start.elapsed(), Duration::from_millis(52) + MAX_DELAY);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn duplicate_events_are_collapsed() {
+        let (injector, mut coalescer) = coalescer();
+
+        injector.inject(WindowEventKind::Shown, 1);
+        injector.inject(WindowEventKind::Shown, 1);
+        injector.inject(WindowEventKind::Hidden, 1);
+        injector.inject(WindowEventKind::Shown, 2);
+        injector.inject(WindowEventKind::Shown, 1);
+
+        assert_eq!(
+            coalescer.next_batch().await,
+            [
+                shown(1),
+                WindowEvent::new(WindowEventKind::Hidden, 1),
+                shown(2)
+            ]
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn event_storm_is_truncated_to_max_pending() {
+        let (injector, mut coalescer) = coalescer();
+
+        for hwnd in 0..(MAX_PENDING as isize + 100) {
+            injector.inject(WindowEventKind::Created, hwnd);
+        }
+
+        let batch = coalescer.next_batch().await;
+        assert_eq!(batch.len(), MAX_PENDING);
+        assert_eq!(batch.last().unwrap().hwnd, MAX_PENDING as isize - 1);
+
+        // Atılanlar sonraki gruba taşınmaz
+        injector.inject(WindowEventKind::Destroyed, 7);
+        assert_eq!(
+            coalescer.next_batch().await,
+            [WindowEvent::new(WindowEventKind::Destroyed, 7)]
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn closed_source_drains_pending_events() {
+        let (injector, mut coalescer) = coalescer();
+        let start = Instant::now();
+
+        injector.inject(WindowEventKind::Shown, 1);
+        drop(injector);
+
+        // Kaynak kapanınca debounce beklenmez
+        assert_eq!(coalescer.next_batch().await, [shown(1)]);
+        assert_eq!(start.elapsed(), Duration::ZERO);
+        assert!(!coalescer.is_active());
+
+        let next = tokio::time::timeout(Duration::from_secs(60), coalescer.next_batch()).await;
+        assert!(next.is_err());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn missing_source_never_delivers() {
+        let mut coalescer = EventCoalescer::new(None, DEBOUNCE, MAX_DELAY);
+
+        assert!(!coalescer.is_active());
+        let next = tokio::time::timeout(Duration::from_secs(60), coalescer.next_batch()).await;
+        assert!(next.is_err());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn cancelled_wait_keeps_collected_events() {
+        let (injector, mut coalescer) = coalescer();
+        let start = Instant::now();
+
+        injector.inject(WindowEventKind::Shown, 1);
+        let cancelled =
+            tokio::time::timeout(Duration::from_millis(5), coalescer.next_batch()).await;
+        assert!(cancelled.is_err());
+
+        injector.inject(WindowEventKind::Shown, 2);
+        assert_eq!(coalescer.next_batch().await, [shown(1), shown(2)]);
+        assert_eq!(start.elapsed(), Duration::from_millis(15));
+    }
+}
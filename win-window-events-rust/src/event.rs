@@ -0,0 +1,57 @@
+use tokio::sync::mpsc;
+
+// Dinlenen WinEvent sabitleri (windows crate'ine bağımlı olmadan)
+pub const EVENT_SYSTEM_FOREGROUND: u32 = 0x0003;
+pub const EVENT_SYSTEM_MINIMIZESTART: u32 = 0x0016;
+pub const EVENT_SYSTEM_MINIMIZEEND: u32 = 0x0017;
+pub const EVENT_OBJECT_CREATE: u32 = 0x8000;
+pub const EVENT_OBJECT_DESTROY: u32 = 0x8001;
+pub const EVENT_OBJECT_SHOW: u32 = 0x8002;
+pub const EVENT_OBJECT_HIDE: u32 = 0x8003;
+pub const EVENT_OBJECT_NAMECHANGE: u32 = 0x800C;
+
+/// Bir pencerede olan değişikliğin türü
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum WindowEventKind {
+    Created,
+    Destroyed,
+    Shown,
+    Hidden,
+    NameChanged,
+    Foreground,
+    MinimizeStart,
+    MinimizeEnd,
+}
+
+impl WindowEventKind {
+    /// WinEvent sabitinden türü bulur; dinlenmeyen event'ler için `None`
+    pub fn from_win_event(event: u32) -> Option<Self> {
+        match event {
+            EVENT_OBJECT_CREATE => Some(Self::Created),
+            EVENT_OBJECT_DESTROY => Some(Self::Destroyed),
+            EVENT_OBJECT_SHOW => Some(Self::Shown),
+            EVENT_OBJECT_HIDE => Some(Self::Hidden),
+            EVENT_OBJECT_NAMECHANGE => Some(Self::NameChanged),
+            EVENT_SYSTEM_FOREGROUND => Some(Self::Foreground),
+            EVENT_SYSTEM_MINIMIZESTART => Some(Self::MinimizeStart),
+            EVENT_SYSTEM_MINIMIZEEND => Some(Self::MinimizeEnd),
+            _ => None,
+        }
+    }
+}
+
+/// Bir top-level pencere için gelen tek event
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct WindowEvent {
+    pub kind: WindowEventKind,
+    pub hwnd: isize,
+}
+
+impl WindowEvent {
+    pub fn new(kind: WindowEventKind, hwnd: isize) -> Self {
+        Self { kind, hwnd }
+    }
+}
+
+/// Event kaynağının (hook ya da injector) okunan ucu
+pub type WindowEvents = mpsc::UnboundedReceiver<WindowEvent>;
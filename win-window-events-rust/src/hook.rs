@@ -0,0 +1,157 @@
+use crate::event::*;
+use arui_protocol::{AruiError, ErrorCode, Result};
+use std::cell::RefCell;
+use std::sync::mpsc::SyncSender;
+use std::thread::JoinHandle;
+use tokio::sync::mpsc;
+use windows::Win32::Foundation::{HWND, LPARAM, WPARAM};
+use windows::Win32::System::Threading::GetCurrentThreadId;
+use windows::Win32::UI::Accessibility::{SetWinEventHook, UnhookWinEvent, HWINEVENTHOOK};
+use windows::Win32::UI::WindowsAndMessaging::{
+    DispatchMessageW, GetAncestor, GetMessageW, PeekMessageW, PostThreadMessageW, TranslateMessage,
+    CHILDID_SELF, GA_ROOT, MSG, OBJID_WINDOW, PM_NOREMOVE, WINEVENT_OUTOFCONTEXT,
+    WINEVENT_SKIPOWNPROCESS, WM_QUIT,
+};
+
+// Her biri ayrı bir hook olarak kurulan event aralıkları (min, max)
+const EVENT_RANGES: [(u32, u32); 4] = [
+    (EVENT_SYSTEM_FOREGROUND, EVENT_SYSTEM_FOREGROUND),
+    (EVENT_SYSTEM_MINIMIZESTART, EVENT_SYSTEM_MINIMIZEEND),
+    (EVENT_OBJECT_CREATE, EVENT_OBJECT_HIDE),
+    (EVENT_OBJECT_NAMECHANGE, EVENT_OBJECT_NAMECHANGE),
+];
+
+thread_local! {
+    // WINEVENT_OUTOFCONTEXT callback'leri hook'u kuran thread'in mesaj
+    // döngüsünde çağrılır, bu yüzden kanal o thread'de tutulur
+    static SENDER: RefCell<Option<mpsc::UnboundedSender<WindowEvent>>> = const { RefCell::new(None) };
+}
+
+/// `SetWinEventHook` hook'ları ve onların mesaj döngüsünü çalıştıran thread.
+/// Drop edildiğinde hook'lar kaldırılır ve thread durur.
+pub struct WinEventHook {
+    thread_id: u32,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl WinEventHook {
+    /// Hook'ları kurar; event'ler dönen kanaldan okunur
+    pub fn start() -> Result<(Self, WindowEvents)> {
+        let (sender, events) = mpsc::unbounded_channel();
+        let (started_tx, started_rx) = std::sync::mpsc::sync_channel(1);
+
+        let thread = std::thread::Builder::new()
+            .name("win-event-hook".to_string())
+            .spawn(move || run_hook_thread(sender, started_tx))
+            .map_err(|e| AruiError::io(e, "Could not start the window event thread"))?;
+
+        match started_rx.recv() {
+            Ok(Ok(thread_id)) => Ok((
+                Self {
+                    thread_id,
+                    thread: Some(thread),
+                },
+                events,
+            )),
+            Ok(Err(error)) => {
+                let _ = thread.join();
+                Err(error)
+            }
+            Err(_) => Err(AruiError::new(
+                ErrorCode::Internal,
+                "Window event thread stopped before installing hooks",
+            )),
+        }
+    }
+}
+
+impl Drop for WinEventHook {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = PostThreadMessageW(self.thread_id, WM_QUIT, WPARAM(0), LPARAM(0));
+        }
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn run_hook_thread(sender: mpsc::UnboundedSender<WindowEvent>, started: SyncSender<Result<u32>>) {
+    SENDER.with(|cell| *cell.borrow_mut() = Some(sender));
+
+    let mut hooks: Vec<HWINEVENTHOOK> = Vec::new();
+    for (min, max) in EVENT_RANGES {
+        let hook = unsafe {
+            SetWinEventHook(
+                min,
+                max,
+                None,
+                Some(win_event_proc),
+                0,
+                0,
+                WINEVENT_OUTOFCONTEXT | WINEVENT_SKIPOWNPROCESS,
+            )
+        };
+        if hook.is_invalid() {
+            let error = AruiError::last_os_error(ErrorCode::Failed, "SetWinEventHook failed");
+            unhook_all(hooks);
+            let _ = started.send(Err(error));
+            return;
+        }
+        hooks.push(hook);
+    }
+
+    let mut msg = MSG::default();
+    unsafe {
+        // PostThreadMessageW'nin WM_QUIT'i bırakabilmesi için mesaj kuyruğu,
+        // thread'in hazır olduğu bildirilmeden önce oluşturulur
+        let _ = PeekMessageW(&mut msg, None, 0, 0, PM_NOREMOVE);
+        let _ = started.send(Ok(GetCurrentThreadId()));
+
+        // WM_QUIT'te 0, hatada -1 döner
+        while GetMessageW(&mut msg, None, 0, 0).0 > 0 {
+            let _ = TranslateMessage(&msg);
+            DispatchMessageW(&msg);
+        }
+    }
+
+    unhook_all(hooks);
+}
+
+fn unhook_all(hooks: Vec<HWINEVENTHOOK>) {
+    for hook in hooks {
+        unsafe {
+            let _ = UnhookWinEvent(hook);
+        }
+    }
+}
+
+unsafe extern "system" fn win_event_proc(
+    _hook: HWINEVENTHOOK,
+    event: u32,
+    hwnd: HWND,
+    id_object: i32,
+    id_child: i32,
+    _event_thread: u32,
+    _event_time: u32,
+) {
+    // Caret, imleç ve pencere içi öğeler değil, sadece pencerenin kendisi
+    if hwnd.is_invalid() || id_object != OBJID_WINDOW.0 || id_child != CHILDID_SELF as i32 {
+        return;
+    }
+    let Some(kind) = WindowEventKind::from_win_event(event) else {
+        return;
+    };
+    // Yok edilen pencerenin ancestor'ı sorulamaz; diğerlerinde child
+    // pencereleri ele
+    if kind != WindowEventKind::Destroyed && GetAncestor(hwnd, GA_ROOT) != hwnd {
+        return;
+    }
+
+    let event = WindowEvent::new(kind, hwnd.0 as isize);
+    SENDER.with(|cell| {
+        if let Some(sender) = cell.borrow().as_ref() {
+            let _ = sender.send(event);
+        }
+    });
+}
@@ -0,0 +1,22 @@
+use crate::event::{WindowEvent, WindowEventKind, WindowEvents};
+use tokio::sync::mpsc;
+
+/// Hook yerine elle event üreten kaynak. Windows olmayan ortamlarda
+/// testler, monitor'ü ve guardian'ı bununla sürer.
+#[derive(Clone, Debug)]
+pub struct EventInjector {
+    sender: mpsc::UnboundedSender<WindowEvent>,
+}
+
+impl EventInjector {
+    /// Injector'ı ve event'lerin okunacağı ucu oluşturur
+    pub fn new() -> (Self, WindowEvents) {
+        let (sender, events) = mpsc::unbounded_channel();
+        (Self { sender }, events)
+    }
+
+    /// Event'i gönderir; okuyan taraf kapandıysa `false`
+    pub fn inject(&self, kind: WindowEventKind, hwnd: isize) -> bool {
+        self.sender.send(WindowEvent::new(kind, hwnd)).is_ok()
+    }
+}
@@ -0,0 +1,19 @@
+//! Pencere değişikliği bildirimleri. Taskbar item list ve taskbar manager
+//! guardian'ı sabit aralıklı polling yerine bu event'lerle çalışır.
+//!
+//! Windows'ta event'ler `SetWinEventHook` ile kendi mesaj döngüsü olan bir
+//! thread'den gelir ([`WinEventHook`]). Diğer platformlarda ve testlerde
+//! [`EventInjector`] aynı kanala elle event yazar. [`EventCoalescer`] kısa
+//! sürede gelen event'leri tek bir yenilemede toplar.
+
+mod coalesce;
+mod event;
+#[cfg(windows)]
+mod hook;
+mod injector;
+
+pub use coalesce::EventCoalescer;
+pub use event::{WindowEvent, WindowEventKind, WindowEvents};
+#[cfg(windows)]
+pub use hook::WinEventHook;
+pub use injector::EventInjector;